@@ -0,0 +1,187 @@
+#!/usr/bin/env python3
+# regenerates the case folding and grapheme cluster break tables from the Unicode
+# character database, in place in src/parser/casefold.rs and src/grapheme.rs.
+#
+#   python3 scripts/unicode_tables.py path/to/ucd
+#
+# the directory is an unpacked https://www.unicode.org/Public/<version>/ucd/ of the
+# release in UNICODE_VERSION (src/lib.rs), the files read from it are
+#
+#   CaseFolding.txt                      simple case folding, the C and S mappings
+#   auxiliary/GraphemeBreakProperty.txt  Grapheme_Cluster_Break
+#   emoji/emoji-data.txt                 Extended_Pictographic
+#   DerivedCoreProperties.txt            Indic_Conjunct_Break (InCB)
+#
+# bump UNICODE_VERSION first, a file of another release is refused.
+
+import os
+import re
+import sys
+
+ROOT = os.path.join(os.path.dirname(os.path.abspath(__file__)), "..")
+MAX_WIDTH = 99
+
+
+def unicode_version():
+    with open(os.path.join(ROOT, "src", "lib.rs")) as f:
+        m = re.search(r"UNICODE_VERSION: \(u32, u32, u32\) = \((\d+), (\d+), (\d+)\)", f.read())
+    return "%s.%s.%s" % m.groups()
+
+
+# data lines split at ';', comments and blank lines dropped. the leading comment names
+# the release, like 'CaseFolding-17.0.0.txt', or 'Emoji Version 17.0' in emoji-data.txt
+def read_ucd(ucd, path, version):
+    name = os.path.basename(path)[: -len(".txt")]
+    with open(os.path.join(ucd, path), encoding="utf-8") as f:
+        lines = f.read().splitlines()
+    header = []
+    for line in lines:
+        if not line.startswith("#"):
+            break
+        header.append(line)
+    header = "\n".join(header)
+    major_minor = version.rsplit(".", 1)[0]
+    if "%s-%s.txt" % (name, version) not in header and not re.search(
+        r"Emoji Version %s\b" % re.escape(major_minor), header
+    ):
+        sys.exit("%s: not of Unicode %s, the release in UNICODE_VERSION" % (path, version))
+
+    rows = []
+    for line in lines:
+        line = line.split("#", 1)[0].strip()
+        if line:
+            rows.append([field.strip() for field in line.split(";")])
+    return rows
+
+
+def code_points(field):
+    first, _, last = field.partition("..")
+    return range(int(first, 16), int(last or first, 16) + 1)
+
+
+# chars with the same simple case fold share an orbit
+def case_orbits(ucd, version):
+    parent = {}
+
+    def find(c):
+        while parent.setdefault(c, c) != c:
+            c = parent[c]
+        return c
+
+    for code, status, mapping, *_ in read_ucd(ucd, "CaseFolding.txt", version):
+        if status in ("C", "S"):
+            a, b = find(int(code, 16)), find(int(mapping, 16))
+            parent[max(a, b)] = min(a, b)
+
+    orbits = {}
+    for c in parent:
+        orbits.setdefault(find(c), []).append(c)
+
+    pairs = []
+    for orbit in orbits.values():
+        orbit.sort()
+        pairs.extend(zip(orbit, orbit[1:] + orbit[:1]))
+    return sorted(pairs)
+
+
+# the names of Break in src/grapheme.rs
+BREAKS = {
+    "CR": "CR",
+    "LF": "LF",
+    "Control": "Control",
+    "Extend": "Extend",
+    "ZWJ": "Zwj",
+    "Regional_Indicator": "RegionalIndicator",
+    "Prepend": "Prepend",
+    "SpacingMark": "SpacingMark",
+    "L": "L",
+    "V": "V",
+    "T": "T",
+    "LV": "Lv",
+    "LVT": "Lvt",
+}
+
+
+def grapheme_breaks(ucd, version):
+    breaks = {}
+    for field, value in read_ucd(ucd, "auxiliary/GraphemeBreakProperty.txt", version):
+        for c in code_points(field):
+            breaks[c] = BREAKS[value]
+
+    # Extended_Pictographic and InCB are folded in, where they can't clash
+    for field, value in read_ucd(ucd, "emoji/emoji-data.txt", version):
+        if value == "Extended_Pictographic":
+            for c in code_points(field):
+                assert c not in breaks, "U+%04X is pictographic and %s" % (c, breaks[c])
+                breaks[c] = "Pictographic"
+    for row in read_ucd(ucd, "DerivedCoreProperties.txt", version):
+        if row[1] != "InCB":
+            continue
+        for c in code_points(row[0]):
+            brk = breaks.get(c, "Other")
+            if row[2] == "Consonant":
+                assert brk == "Other", "U+%04X is a consonant and %s" % (c, brk)
+                breaks[c] = "Consonant"
+            elif row[2] == "Linker":
+                assert brk == "Extend", "U+%04X is a linker and %s" % (c, brk)
+                breaks[c] = "Linker"
+            elif row[2] == "Extend":
+                assert brk in ("Extend", "Zwj"), "U+%04X is a conjunct extend and %s" % (c, brk)
+                if brk == "Extend":
+                    breaks[c] = "ConjunctExtend"
+
+    ranges = []
+    for c in sorted(breaks):
+        if ranges and ranges[-1][1] == c - 1 and ranges[-1][2] == breaks[c]:
+            ranges[-1][1] = c
+        else:
+            ranges.append([c, c, breaks[c]])
+    return ranges
+
+
+# as many items per line as fit, or a fixed number. rustfmt leaves the tables alone
+def write_table(path, declaration, items, per_line=None):
+    lines = []
+    for i, item in enumerate(items):
+        if per_line:
+            fits = i % per_line != 0
+        else:
+            fits = lines and len(lines[-1]) + 1 + len(item) <= MAX_WIDTH
+        if fits:
+            lines[-1] += " " + item
+        else:
+            lines.append("    " + item)
+
+    path = os.path.join(ROOT, path)
+    with open(path) as f:
+        text = f.read()
+    name = declaration.split("[", 1)[0]
+    m = re.search(r"^%s\[[^\n]*= \[\n.*?^\];\n" % re.escape(name), text, re.M | re.S)
+    table = "%s = [\n%s\n];\n" % (declaration % len(items), "\n".join(lines))
+    with open(path, "w") as f:
+        f.write(text[: m.start()] + table + text[m.end() :])
+
+
+def main():
+    if len(sys.argv) != 2:
+        sys.exit("usage: %s <ucd directory>" % sys.argv[0])
+    ucd, version = sys.argv[1], unicode_version()
+
+    orbits = case_orbits(ucd, version)
+    write_table(
+        "src/parser/casefold.rs",
+        "pub(crate) static CASE_ORBITS: [(char, char); %d]",
+        ["('\\u{%X}', '\\u{%X}')," % pair for pair in orbits],
+    )
+
+    breaks = grapheme_breaks(ucd, version)
+    write_table(
+        "src/grapheme.rs",
+        "pub(crate) static GRAPHEME_BREAKS: [(char, char, Break); %d]",
+        ["('\\u{%04X}', '\\u{%04X}', Break::%s)," % tuple(r) for r in breaks],
+        per_line=2,
+    )
+
+
+if __name__ == "__main__":
+    main()
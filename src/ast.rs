@@ -0,0 +1,132 @@
+// the parsed tree of a pattern, to look into patterns without compiling them,
+// or to make one in code and compile it with Vm::from_ast and the like
+pub use crate::parser::ast::{
+    Ast, AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind, Span, Visitor,
+};
+use crate::{parser::Error, RegexBuilder};
+
+// parse in Syntax::Default, use RegexBuilder::parse for other syntaxes and options
+pub fn parse(pattern: &str) -> Result<Ast, Error> {
+    RegexBuilder::new(pattern).parse()
+}
+
+// nodes made in code were written nowhere, they all get an empty span
+
+// matches text as it is, no char is special
+pub fn literal(text: &str) -> Ast {
+    concat(
+        text.chars()
+            .map(|c| make(AstKind::Match(MatchKind::Char(c)), vec![]))
+            .collect(),
+    )
+}
+
+// matches the items one after another
+pub fn concat(items: Vec<Ast>) -> Ast {
+    make(AstKind::NonCaptureGroup, items)
+}
+
+// matches the first item that matches
+pub fn alt(items: Vec<Ast>) -> Ast {
+    make(AstKind::Union, items)
+}
+
+// matches item min times or more, up to max, as many as it can
+pub fn repeat(item: Ast, min: u32, max: Option<u32>) -> Ast {
+    let max = max.map_or(RepeatKind::Infinity, RepeatKind::Num);
+    make(
+        AstKind::Repeat(RepeatKind::Num(min), max, GreedyKind::Greedy),
+        vec![item],
+    )
+}
+
+// matches a char in one of the inclusive ranges
+pub fn class(ranges: &[(char, char)]) -> Ast {
+    let children = ranges
+        .iter()
+        .map(|(a, b)| make(AstKind::Match(MatchKind::Range(*a, *b)), vec![]))
+        .collect();
+    make(AstKind::IncludeSet, children)
+}
+
+// captures what item matched as group id, 0 is the whole match and can't be used
+pub fn group(id: usize, item: Ast) -> Ast {
+    make(AstKind::CaptureGroup(id), vec![item])
+}
+
+fn make(kind: AstKind, children: Vec<Ast>) -> Ast {
+    Ast {
+        kind,
+        children,
+        span: Span::default(),
+    }
+}
+
+// trees made in code may not hold what the engines count on, which the parser makes sure of
+pub(crate) fn validate(root: &Ast, options: &RegexBuilder) -> Result<(), String> {
+    root.check_nest(options.nest_limit)?;
+
+    // depth first, the tree may be made deeper than any stack
+    let mut stack = vec![(root, true)];
+    while let Some((ast, is_root)) = stack.pop() {
+        validate_node(root, ast, is_root)?;
+        stack.extend(ast.children.iter().rev().map(|child| (child, false)));
+    }
+    Ok(())
+}
+
+fn validate_node(root: &Ast, ast: &Ast, is_root: bool) -> Result<(), String> {
+    let want_children = match &ast.kind {
+        AstKind::CaptureGroup(0) if !is_root => {
+            return Err("ERROR: group 0 is the whole match, only the root can be it".to_string());
+        }
+        AstKind::NonCaptureGroup
+        | AstKind::CaptureGroup(_)
+        | AstKind::AtomicGroup
+        | AstKind::Union => None,
+        AstKind::Conditional(id) | AstKind::Call(id) if root.find_group(*id).is_none() => {
+            return Err(format!("ERROR: reference to undefined group {}", id));
+        }
+        AstKind::Conditional(_) => Some(2),
+        AstKind::IncludeSet | AstKind::ExcludeSet => {
+            let is_range = |child: &Ast| match child.kind {
+                AstKind::Match(MatchKind::Char(_)) => true,
+                AstKind::Match(MatchKind::Range(a, b)) => a <= b,
+                _ => false,
+            };
+            if !ast.children.iter().all(is_range) {
+                return Err("ERROR: a set holds only chars and char-ranges".to_string());
+            }
+            None
+        }
+        AstKind::Repeat(RepeatKind::Infinity, _, _) => {
+            return Err("ERROR: repeat min is infinity".to_string());
+        }
+        AstKind::Repeat(RepeatKind::Num(n), RepeatKind::Num(m), _) if n > m => {
+            return Err(format!("ERROR: repeat range invalid {{{},{}}}", n, m));
+        }
+        AstKind::Star(_) | AstKind::Plus(_) | AstKind::Option(_) | AstKind::Repeat(_, _, _) => {
+            Some(1)
+        }
+        AstKind::Match(MatchKind::Range(a, b)) if a > b => {
+            return Err(format!("ERROR: char-range is invalid {}-{}", a, b));
+        }
+        AstKind::Match(_)
+        | AstKind::Position(_)
+        | AstKind::Comment(_)
+        | AstKind::ResetStart
+        | AstKind::Call(_) => Some(0),
+    };
+
+    if let Some(count) = want_children {
+        if ast.children.len() != count {
+            return Err(format!(
+                "ERROR: {:?} wants {} children, has {}",
+                ast.kind,
+                count,
+                ast.children.len()
+            ));
+        }
+    }
+    Ok(())
+}
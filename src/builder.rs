@@ -0,0 +1,146 @@
+use crate::parser::{Ast, Error, GlobParser, LikeParser, Parser, PosixParser};
+use crate::sfa::{Dfa, Nfa};
+use crate::vm::Vm;
+
+const DEFAULT_NEST_LIMIT: usize = 250;
+const DEFAULT_RECURSION_LIMIT: usize = 100;
+const DEFAULT_SIZE_LIMIT: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Syntax {
+    Default,       // this crate's perl-like syntax
+    PosixBasic,    // POSIX BRE, as in grep and sed
+    PosixExtended, // POSIX ERE, as in grep -E
+    Glob,          // shell globs, the whole text must match
+    Like,          // SQL LIKE, the whole text must match
+}
+
+pub struct RegexBuilder {
+    pattern: String,
+    syntax: Syntax,
+    literal_separator: bool,
+    pub(crate) case_insensitive: bool,
+    pub(crate) grapheme_dot: bool,
+    pub(crate) nest_limit: usize,
+    pub(crate) recursion_limit: usize,
+    pub(crate) size_limit: usize,
+}
+
+impl RegexBuilder {
+    pub fn new(pattern: &str) -> Self {
+        RegexBuilder {
+            pattern: pattern.to_string(),
+            syntax: Syntax::Default,
+            literal_separator: false,
+            case_insensitive: false,
+            grapheme_dot: false,
+            nest_limit: DEFAULT_NEST_LIMIT,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            size_limit: DEFAULT_SIZE_LIMIT,
+        }
+    }
+
+    pub fn syntax(&mut self, syntax: Syntax) -> &mut Self {
+        self.syntax = syntax;
+        self
+    }
+
+    // with Syntax::Default, same as starting the pattern with '(?i)'
+    pub fn case_insensitive(&mut self, yes: bool) -> &mut Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    // with Syntax::Default, let '.' match a whole grapheme cluster like '\X'
+    pub fn grapheme_dot(&mut self, yes: bool) -> &mut Self {
+        self.grapheme_dot = yes;
+        self
+    }
+
+    // with Syntax::Glob, keep '*', '?' and '[!..]' from matching '/'
+    pub fn literal_separator(&mut self, yes: bool) -> &mut Self {
+        self.literal_separator = yes;
+        self
+    }
+
+    // max depth of the parsed tree, each group, set, union and quantifier is a level.
+    // deeper patterns fail to parse. no pass recurses per level, the limit only bounds
+    // the work spent on a hostile pattern
+    pub fn nest_limit(&mut self, limit: usize) -> &mut Self {
+        self.nest_limit = limit;
+        self
+    }
+
+    // max depth of nested subroutine calls, deeper calls fail to match
+    pub fn recursion_limit(&mut self, limit: usize) -> &mut Self {
+        self.recursion_limit = limit;
+        self
+    }
+
+    // max number of Vm instructions, Nfa nodes or Dfa states, bigger patterns fail to build
+    pub fn size_limit(&mut self, limit: usize) -> &mut Self {
+        self.size_limit = limit;
+        self
+    }
+
+    pub fn build_vm(&self) -> Result<Vm, String> {
+        let ast = self.parse()?;
+        let vm = Vm::build(&ast, self);
+        ast.teardown();
+        vm
+    }
+
+    pub fn build_nfa(&self) -> Result<Nfa, String> {
+        let ast = self.parse()?;
+        let nfa = Nfa::build(&ast, self);
+        ast.teardown();
+        nfa
+    }
+
+    pub fn build_dfa(&self) -> Result<Dfa, String> {
+        let nfa = self.build_nfa()?;
+        Dfa::build(nfa, self)
+    }
+
+    // parse only, the error tells where the pattern failed, see Error::render
+    pub fn check(&self) -> Result<(), Error> {
+        self.parse().map(Ast::teardown)
+    }
+
+    // like check, but with Syntax::Default it steps over unclosed sets and groups, unknown
+    // escapes and quantifiers with nothing to repeat, and reports every error it meets.
+    // the tree is what could be parsed, None when an error couldn't be stepped over
+    pub fn check_all(&self) -> (Option<Ast>, Vec<Error>) {
+        if self.syntax == Syntax::Default {
+            return match Parser::parse_recover(&self.pattern, self) {
+                (Some(ast), mut errors) => match ast.check_nest(self.nest_limit) {
+                    Ok(()) => (Some(ast), errors),
+                    Err(err) => {
+                        errors.push(err);
+                        (None, errors)
+                    }
+                },
+                result => result,
+            };
+        }
+
+        match self.parse() {
+            Ok(ast) => (Some(ast), vec![]),
+            Err(err) => (None, vec![err]),
+        }
+    }
+
+    // the tree of the pattern, nothing is compiled
+    pub fn parse(&self) -> Result<Ast, Error> {
+        let limit = self.nest_limit;
+        let ast = match self.syntax {
+            Syntax::Default => Parser::parse_with(&self.pattern, self),
+            Syntax::PosixBasic => PosixParser::parse(&self.pattern, false, limit),
+            Syntax::PosixExtended => PosixParser::parse(&self.pattern, true, limit),
+            Syntax::Glob => GlobParser::parse(&self.pattern, self.literal_separator, limit),
+            Syntax::Like => LikeParser::parse(&self.pattern),
+        }?;
+        ast.check_nest(limit)?;
+        Ok(ast)
+    }
+}
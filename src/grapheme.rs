@@ -0,0 +1,926 @@
+// extended grapheme cluster boundaries, following the rules of UAX #29
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Break {
+    Other,
+    CR,
+    LF,
+    Control,
+    Extend,
+    ConjunctExtend, // Extend that may sit inside an indic conjunct
+    Linker,         // Extend that links two indic consonants
+    Zwj,
+    RegionalIndicator,
+    Prepend,
+    SpacingMark,
+    L,
+    V,
+    T,
+    Lv,
+    Lvt,
+    Consonant,    // indic consonant
+    Pictographic, // Extended_Pictographic
+}
+
+// end of the grapheme cluster starting at sp, None at the end of the text
+pub(crate) fn next_boundary(str: &str, sp: usize) -> Option<usize> {
+    let mut chars = str[sp..].char_indices();
+    let (_, first) = chars.next()?;
+
+    let mut prev = break_of(first);
+    let mut emoji = prev == Break::Pictographic; // in ExtPict Extend* Zwj?
+    let mut conjunct = None; // in Consonant [Extend Linker]*, and linked yet?
+    if prev == Break::Consonant {
+        conjunct = Some(false);
+    }
+    let mut ri_count = 0;
+    if prev == Break::RegionalIndicator {
+        ri_count = 1;
+    }
+
+    for (i, c) in chars {
+        let next = break_of(c);
+        if !is_joined(prev, next, emoji, conjunct, ri_count) {
+            return Some(sp + i);
+        }
+
+        emoji = match next {
+            Break::Pictographic => true,
+            Break::Extend | Break::ConjunctExtend | Break::Linker | Break::Zwj => {
+                emoji && prev != Break::Zwj
+            }
+            _ => false,
+        };
+        conjunct = match next {
+            Break::Consonant => Some(false),
+            Break::Linker => conjunct.map(|_| true),
+            Break::ConjunctExtend | Break::Zwj => conjunct,
+            _ => None,
+        };
+        ri_count = match next {
+            Break::RegionalIndicator => ri_count + 1,
+            _ => 0,
+        };
+        prev = next;
+    }
+
+    Some(str.len())
+}
+
+fn is_joined(
+    prev: Break,
+    next: Break,
+    emoji: bool,
+    conjunct: Option<bool>,
+    ri_count: usize,
+) -> bool {
+    use Break::*;
+
+    match (prev, next) {
+        (CR, LF) => true,                                            // GB3
+        (CR | LF | Control, _) => false,                             // GB4
+        (_, CR | LF | Control) => false,                             // GB5
+        (L, L | V | Lv | Lvt) => true,                               // GB6
+        (Lv | V, V | T) => true,                                     // GB7
+        (Lvt | T, T) => true,                                        // GB8
+        (_, Extend | ConjunctExtend | Linker | Zwj) => true,         // GB9
+        (_, SpacingMark) => true,                                    // GB9a
+        (Prepend, _) => true,                                        // GB9b
+        (_, Consonant) if conjunct == Some(true) => true,            // GB9c
+        (Zwj, Pictographic) => emoji,                                // GB11
+        (RegionalIndicator, RegionalIndicator) => ri_count % 2 == 1, // GB12, GB13
+        _ => false,                                                  // GB999
+    }
+}
+
+fn break_of(c: char) -> Break {
+    let i = GRAPHEME_BREAKS.partition_point(|(_, b, _)| *b < c);
+    match GRAPHEME_BREAKS.get(i) {
+        Some((a, _, kind)) if *a <= c => *kind,
+        _ => Break::Other,
+    }
+}
+
+// grapheme cluster break of every char that is not Other, generated by
+// scripts/unicode_tables.py from the Unicode character database of UNICODE_VERSION. the
+// Extended_Pictographic and Indic_Conjunct_Break properties are folded in, they never
+// overlap with a break other than Extend or Other.
+#[rustfmt::skip]
+pub(crate) static GRAPHEME_BREAKS: [(char, char, Break); 1631] = [
+    ('\u{0000}', '\u{0009}', Break::Control), ('\u{000A}', '\u{000A}', Break::LF),
+    ('\u{000B}', '\u{000C}', Break::Control), ('\u{000D}', '\u{000D}', Break::CR),
+    ('\u{000E}', '\u{001F}', Break::Control), ('\u{007F}', '\u{009F}', Break::Control),
+    ('\u{00A9}', '\u{00A9}', Break::Pictographic), ('\u{00AD}', '\u{00AD}', Break::Control),
+    ('\u{00AE}', '\u{00AE}', Break::Pictographic), ('\u{0300}', '\u{036F}', Break::ConjunctExtend),
+    ('\u{0483}', '\u{0489}', Break::ConjunctExtend), ('\u{0591}', '\u{05BD}', Break::ConjunctExtend),
+    ('\u{05BF}', '\u{05BF}', Break::ConjunctExtend), ('\u{05C1}', '\u{05C2}', Break::ConjunctExtend),
+    ('\u{05C4}', '\u{05C5}', Break::ConjunctExtend), ('\u{05C7}', '\u{05C7}', Break::ConjunctExtend),
+    ('\u{0600}', '\u{0605}', Break::Prepend), ('\u{0610}', '\u{061A}', Break::ConjunctExtend),
+    ('\u{061C}', '\u{061C}', Break::Control), ('\u{064B}', '\u{065F}', Break::ConjunctExtend),
+    ('\u{0670}', '\u{0670}', Break::ConjunctExtend), ('\u{06D6}', '\u{06DC}', Break::ConjunctExtend),
+    ('\u{06DD}', '\u{06DD}', Break::Prepend), ('\u{06DF}', '\u{06E4}', Break::ConjunctExtend),
+    ('\u{06E7}', '\u{06E8}', Break::ConjunctExtend), ('\u{06EA}', '\u{06ED}', Break::ConjunctExtend),
+    ('\u{070F}', '\u{070F}', Break::Prepend), ('\u{0711}', '\u{0711}', Break::ConjunctExtend),
+    ('\u{0730}', '\u{074A}', Break::ConjunctExtend), ('\u{07A6}', '\u{07B0}', Break::ConjunctExtend),
+    ('\u{07EB}', '\u{07F3}', Break::ConjunctExtend), ('\u{07FD}', '\u{07FD}', Break::ConjunctExtend),
+    ('\u{0816}', '\u{0819}', Break::ConjunctExtend), ('\u{081B}', '\u{0823}', Break::ConjunctExtend),
+    ('\u{0825}', '\u{0827}', Break::ConjunctExtend), ('\u{0829}', '\u{082D}', Break::ConjunctExtend),
+    ('\u{0859}', '\u{085B}', Break::ConjunctExtend), ('\u{0890}', '\u{0891}', Break::Prepend),
+    ('\u{0897}', '\u{089F}', Break::ConjunctExtend), ('\u{08CA}', '\u{08E1}', Break::ConjunctExtend),
+    ('\u{08E2}', '\u{08E2}', Break::Prepend), ('\u{08E3}', '\u{0902}', Break::ConjunctExtend),
+    ('\u{0903}', '\u{0903}', Break::SpacingMark), ('\u{0915}', '\u{0939}', Break::Consonant),
+    ('\u{093A}', '\u{093A}', Break::ConjunctExtend), ('\u{093B}', '\u{093B}', Break::SpacingMark),
+    ('\u{093C}', '\u{093C}', Break::ConjunctExtend), ('\u{093E}', '\u{0940}', Break::SpacingMark),
+    ('\u{0941}', '\u{0948}', Break::ConjunctExtend), ('\u{0949}', '\u{094C}', Break::SpacingMark),
+    ('\u{094D}', '\u{094D}', Break::Linker), ('\u{094E}', '\u{094F}', Break::SpacingMark),
+    ('\u{0951}', '\u{0957}', Break::ConjunctExtend), ('\u{0958}', '\u{095F}', Break::Consonant),
+    ('\u{0962}', '\u{0963}', Break::ConjunctExtend), ('\u{0978}', '\u{097F}', Break::Consonant),
+    ('\u{0981}', '\u{0981}', Break::ConjunctExtend), ('\u{0982}', '\u{0983}', Break::SpacingMark),
+    ('\u{0995}', '\u{09A8}', Break::Consonant), ('\u{09AA}', '\u{09B0}', Break::Consonant),
+    ('\u{09B2}', '\u{09B2}', Break::Consonant), ('\u{09B6}', '\u{09B9}', Break::Consonant),
+    ('\u{09BC}', '\u{09BC}', Break::ConjunctExtend), ('\u{09BE}', '\u{09BE}', Break::ConjunctExtend),
+    ('\u{09BF}', '\u{09C0}', Break::SpacingMark), ('\u{09C1}', '\u{09C4}', Break::ConjunctExtend),
+    ('\u{09C7}', '\u{09C8}', Break::SpacingMark), ('\u{09CB}', '\u{09CC}', Break::SpacingMark),
+    ('\u{09CD}', '\u{09CD}', Break::Linker), ('\u{09D7}', '\u{09D7}', Break::ConjunctExtend),
+    ('\u{09DC}', '\u{09DD}', Break::Consonant), ('\u{09DF}', '\u{09DF}', Break::Consonant),
+    ('\u{09E2}', '\u{09E3}', Break::ConjunctExtend), ('\u{09F0}', '\u{09F1}', Break::Consonant),
+    ('\u{09FE}', '\u{09FE}', Break::ConjunctExtend), ('\u{0A01}', '\u{0A02}', Break::ConjunctExtend),
+    ('\u{0A03}', '\u{0A03}', Break::SpacingMark), ('\u{0A3C}', '\u{0A3C}', Break::ConjunctExtend),
+    ('\u{0A3E}', '\u{0A40}', Break::SpacingMark), ('\u{0A41}', '\u{0A42}', Break::ConjunctExtend),
+    ('\u{0A47}', '\u{0A48}', Break::ConjunctExtend), ('\u{0A4B}', '\u{0A4D}', Break::ConjunctExtend),
+    ('\u{0A51}', '\u{0A51}', Break::ConjunctExtend), ('\u{0A70}', '\u{0A71}', Break::ConjunctExtend),
+    ('\u{0A75}', '\u{0A75}', Break::ConjunctExtend), ('\u{0A81}', '\u{0A82}', Break::ConjunctExtend),
+    ('\u{0A83}', '\u{0A83}', Break::SpacingMark), ('\u{0A95}', '\u{0AA8}', Break::Consonant),
+    ('\u{0AAA}', '\u{0AB0}', Break::Consonant), ('\u{0AB2}', '\u{0AB3}', Break::Consonant),
+    ('\u{0AB5}', '\u{0AB9}', Break::Consonant), ('\u{0ABC}', '\u{0ABC}', Break::ConjunctExtend),
+    ('\u{0ABE}', '\u{0AC0}', Break::SpacingMark), ('\u{0AC1}', '\u{0AC5}', Break::ConjunctExtend),
+    ('\u{0AC7}', '\u{0AC8}', Break::ConjunctExtend), ('\u{0AC9}', '\u{0AC9}', Break::SpacingMark),
+    ('\u{0ACB}', '\u{0ACC}', Break::SpacingMark), ('\u{0ACD}', '\u{0ACD}', Break::Linker),
+    ('\u{0AE2}', '\u{0AE3}', Break::ConjunctExtend), ('\u{0AF9}', '\u{0AF9}', Break::Consonant),
+    ('\u{0AFA}', '\u{0AFF}', Break::ConjunctExtend), ('\u{0B01}', '\u{0B01}', Break::ConjunctExtend),
+    ('\u{0B02}', '\u{0B03}', Break::SpacingMark), ('\u{0B15}', '\u{0B28}', Break::Consonant),
+    ('\u{0B2A}', '\u{0B30}', Break::Consonant), ('\u{0B32}', '\u{0B33}', Break::Consonant),
+    ('\u{0B35}', '\u{0B39}', Break::Consonant), ('\u{0B3C}', '\u{0B3C}', Break::ConjunctExtend),
+    ('\u{0B3E}', '\u{0B3F}', Break::ConjunctExtend), ('\u{0B40}', '\u{0B40}', Break::SpacingMark),
+    ('\u{0B41}', '\u{0B44}', Break::ConjunctExtend), ('\u{0B47}', '\u{0B48}', Break::SpacingMark),
+    ('\u{0B4B}', '\u{0B4C}', Break::SpacingMark), ('\u{0B4D}', '\u{0B4D}', Break::Linker),
+    ('\u{0B55}', '\u{0B57}', Break::ConjunctExtend), ('\u{0B5C}', '\u{0B5D}', Break::Consonant),
+    ('\u{0B5F}', '\u{0B5F}', Break::Consonant), ('\u{0B62}', '\u{0B63}', Break::ConjunctExtend),
+    ('\u{0B71}', '\u{0B71}', Break::Consonant), ('\u{0B82}', '\u{0B82}', Break::ConjunctExtend),
+    ('\u{0BBE}', '\u{0BBE}', Break::ConjunctExtend), ('\u{0BBF}', '\u{0BBF}', Break::SpacingMark),
+    ('\u{0BC0}', '\u{0BC0}', Break::ConjunctExtend), ('\u{0BC1}', '\u{0BC2}', Break::SpacingMark),
+    ('\u{0BC6}', '\u{0BC8}', Break::SpacingMark), ('\u{0BCA}', '\u{0BCC}', Break::SpacingMark),
+    ('\u{0BCD}', '\u{0BCD}', Break::ConjunctExtend), ('\u{0BD7}', '\u{0BD7}', Break::ConjunctExtend),
+    ('\u{0C00}', '\u{0C00}', Break::ConjunctExtend), ('\u{0C01}', '\u{0C03}', Break::SpacingMark),
+    ('\u{0C04}', '\u{0C04}', Break::ConjunctExtend), ('\u{0C15}', '\u{0C28}', Break::Consonant),
+    ('\u{0C2A}', '\u{0C39}', Break::Consonant), ('\u{0C3C}', '\u{0C3C}', Break::ConjunctExtend),
+    ('\u{0C3E}', '\u{0C40}', Break::ConjunctExtend), ('\u{0C41}', '\u{0C44}', Break::SpacingMark),
+    ('\u{0C46}', '\u{0C48}', Break::ConjunctExtend), ('\u{0C4A}', '\u{0C4C}', Break::ConjunctExtend),
+    ('\u{0C4D}', '\u{0C4D}', Break::Linker), ('\u{0C55}', '\u{0C56}', Break::ConjunctExtend),
+    ('\u{0C58}', '\u{0C5A}', Break::Consonant), ('\u{0C62}', '\u{0C63}', Break::ConjunctExtend),
+    ('\u{0C81}', '\u{0C81}', Break::ConjunctExtend), ('\u{0C82}', '\u{0C83}', Break::SpacingMark),
+    ('\u{0CBC}', '\u{0CBC}', Break::ConjunctExtend), ('\u{0CBE}', '\u{0CBE}', Break::SpacingMark),
+    ('\u{0CBF}', '\u{0CC0}', Break::ConjunctExtend), ('\u{0CC1}', '\u{0CC1}', Break::SpacingMark),
+    ('\u{0CC2}', '\u{0CC2}', Break::ConjunctExtend), ('\u{0CC3}', '\u{0CC4}', Break::SpacingMark),
+    ('\u{0CC6}', '\u{0CC8}', Break::ConjunctExtend), ('\u{0CCA}', '\u{0CCD}', Break::ConjunctExtend),
+    ('\u{0CD5}', '\u{0CD6}', Break::ConjunctExtend), ('\u{0CE2}', '\u{0CE3}', Break::ConjunctExtend),
+    ('\u{0CF3}', '\u{0CF3}', Break::SpacingMark), ('\u{0D00}', '\u{0D01}', Break::ConjunctExtend),
+    ('\u{0D02}', '\u{0D03}', Break::SpacingMark), ('\u{0D15}', '\u{0D3A}', Break::Consonant),
+    ('\u{0D3B}', '\u{0D3C}', Break::ConjunctExtend), ('\u{0D3E}', '\u{0D3E}', Break::ConjunctExtend),
+    ('\u{0D3F}', '\u{0D40}', Break::SpacingMark), ('\u{0D41}', '\u{0D44}', Break::ConjunctExtend),
+    ('\u{0D46}', '\u{0D48}', Break::SpacingMark), ('\u{0D4A}', '\u{0D4C}', Break::SpacingMark),
+    ('\u{0D4D}', '\u{0D4D}', Break::Linker), ('\u{0D4E}', '\u{0D4E}', Break::Prepend),
+    ('\u{0D57}', '\u{0D57}', Break::ConjunctExtend), ('\u{0D62}', '\u{0D63}', Break::ConjunctExtend),
+    ('\u{0D81}', '\u{0D81}', Break::ConjunctExtend), ('\u{0D82}', '\u{0D83}', Break::SpacingMark),
+    ('\u{0DCA}', '\u{0DCA}', Break::ConjunctExtend), ('\u{0DCF}', '\u{0DCF}', Break::ConjunctExtend),
+    ('\u{0DD0}', '\u{0DD1}', Break::SpacingMark), ('\u{0DD2}', '\u{0DD4}', Break::ConjunctExtend),
+    ('\u{0DD6}', '\u{0DD6}', Break::ConjunctExtend), ('\u{0DD8}', '\u{0DDE}', Break::SpacingMark),
+    ('\u{0DDF}', '\u{0DDF}', Break::ConjunctExtend), ('\u{0DF2}', '\u{0DF3}', Break::SpacingMark),
+    ('\u{0E31}', '\u{0E31}', Break::ConjunctExtend), ('\u{0E33}', '\u{0E33}', Break::SpacingMark),
+    ('\u{0E34}', '\u{0E3A}', Break::ConjunctExtend), ('\u{0E47}', '\u{0E4E}', Break::ConjunctExtend),
+    ('\u{0EB1}', '\u{0EB1}', Break::ConjunctExtend), ('\u{0EB3}', '\u{0EB3}', Break::SpacingMark),
+    ('\u{0EB4}', '\u{0EBC}', Break::ConjunctExtend), ('\u{0EC8}', '\u{0ECE}', Break::ConjunctExtend),
+    ('\u{0F18}', '\u{0F19}', Break::ConjunctExtend), ('\u{0F35}', '\u{0F35}', Break::ConjunctExtend),
+    ('\u{0F37}', '\u{0F37}', Break::ConjunctExtend), ('\u{0F39}', '\u{0F39}', Break::ConjunctExtend),
+    ('\u{0F3E}', '\u{0F3F}', Break::SpacingMark), ('\u{0F71}', '\u{0F7E}', Break::ConjunctExtend),
+    ('\u{0F7F}', '\u{0F7F}', Break::SpacingMark), ('\u{0F80}', '\u{0F84}', Break::ConjunctExtend),
+    ('\u{0F86}', '\u{0F87}', Break::ConjunctExtend), ('\u{0F8D}', '\u{0F97}', Break::ConjunctExtend),
+    ('\u{0F99}', '\u{0FBC}', Break::ConjunctExtend), ('\u{0FC6}', '\u{0FC6}', Break::ConjunctExtend),
+    ('\u{1000}', '\u{102A}', Break::Consonant), ('\u{102D}', '\u{1030}', Break::ConjunctExtend),
+    ('\u{1031}', '\u{1031}', Break::SpacingMark), ('\u{1032}', '\u{1037}', Break::ConjunctExtend),
+    ('\u{1039}', '\u{1039}', Break::Linker), ('\u{103A}', '\u{103A}', Break::ConjunctExtend),
+    ('\u{103B}', '\u{103C}', Break::SpacingMark), ('\u{103D}', '\u{103E}', Break::ConjunctExtend),
+    ('\u{103F}', '\u{103F}', Break::Consonant), ('\u{1050}', '\u{1055}', Break::Consonant),
+    ('\u{1056}', '\u{1057}', Break::SpacingMark), ('\u{1058}', '\u{1059}', Break::ConjunctExtend),
+    ('\u{105A}', '\u{105D}', Break::Consonant), ('\u{105E}', '\u{1060}', Break::ConjunctExtend),
+    ('\u{1061}', '\u{1061}', Break::Consonant), ('\u{1065}', '\u{1066}', Break::Consonant),
+    ('\u{106E}', '\u{1070}', Break::Consonant), ('\u{1071}', '\u{1074}', Break::ConjunctExtend),
+    ('\u{1075}', '\u{1081}', Break::Consonant), ('\u{1082}', '\u{1082}', Break::ConjunctExtend),
+    ('\u{1084}', '\u{1084}', Break::SpacingMark), ('\u{1085}', '\u{1086}', Break::ConjunctExtend),
+    ('\u{108D}', '\u{108D}', Break::ConjunctExtend), ('\u{108E}', '\u{108E}', Break::Consonant),
+    ('\u{109D}', '\u{109D}', Break::ConjunctExtend), ('\u{1100}', '\u{115F}', Break::L),
+    ('\u{1160}', '\u{11A7}', Break::V), ('\u{11A8}', '\u{11FF}', Break::T),
+    ('\u{135D}', '\u{135F}', Break::ConjunctExtend), ('\u{1712}', '\u{1715}', Break::ConjunctExtend),
+    ('\u{1732}', '\u{1734}', Break::ConjunctExtend), ('\u{1752}', '\u{1753}', Break::ConjunctExtend),
+    ('\u{1772}', '\u{1773}', Break::ConjunctExtend), ('\u{1780}', '\u{17B3}', Break::Consonant),
+    ('\u{17B4}', '\u{17B5}', Break::ConjunctExtend), ('\u{17B6}', '\u{17B6}', Break::SpacingMark),
+    ('\u{17B7}', '\u{17BD}', Break::ConjunctExtend), ('\u{17BE}', '\u{17C5}', Break::SpacingMark),
+    ('\u{17C6}', '\u{17C6}', Break::ConjunctExtend), ('\u{17C7}', '\u{17C8}', Break::SpacingMark),
+    ('\u{17C9}', '\u{17D1}', Break::ConjunctExtend), ('\u{17D2}', '\u{17D2}', Break::Linker),
+    ('\u{17D3}', '\u{17D3}', Break::ConjunctExtend), ('\u{17DD}', '\u{17DD}', Break::ConjunctExtend),
+    ('\u{180B}', '\u{180D}', Break::ConjunctExtend), ('\u{180E}', '\u{180E}', Break::Control),
+    ('\u{180F}', '\u{180F}', Break::ConjunctExtend), ('\u{1885}', '\u{1886}', Break::ConjunctExtend),
+    ('\u{18A9}', '\u{18A9}', Break::ConjunctExtend), ('\u{1920}', '\u{1922}', Break::ConjunctExtend),
+    ('\u{1923}', '\u{1926}', Break::SpacingMark), ('\u{1927}', '\u{1928}', Break::ConjunctExtend),
+    ('\u{1929}', '\u{192B}', Break::SpacingMark), ('\u{1930}', '\u{1931}', Break::SpacingMark),
+    ('\u{1932}', '\u{1932}', Break::ConjunctExtend), ('\u{1933}', '\u{1938}', Break::SpacingMark),
+    ('\u{1939}', '\u{193B}', Break::ConjunctExtend), ('\u{1A17}', '\u{1A18}', Break::ConjunctExtend),
+    ('\u{1A19}', '\u{1A1A}', Break::SpacingMark), ('\u{1A1B}', '\u{1A1B}', Break::ConjunctExtend),
+    ('\u{1A20}', '\u{1A54}', Break::Consonant), ('\u{1A55}', '\u{1A55}', Break::SpacingMark),
+    ('\u{1A56}', '\u{1A56}', Break::ConjunctExtend), ('\u{1A57}', '\u{1A57}', Break::SpacingMark),
+    ('\u{1A58}', '\u{1A5E}', Break::ConjunctExtend), ('\u{1A60}', '\u{1A60}', Break::Linker),
+    ('\u{1A62}', '\u{1A62}', Break::ConjunctExtend), ('\u{1A65}', '\u{1A6C}', Break::ConjunctExtend),
+    ('\u{1A6D}', '\u{1A72}', Break::SpacingMark), ('\u{1A73}', '\u{1A7C}', Break::ConjunctExtend),
+    ('\u{1A7F}', '\u{1A7F}', Break::ConjunctExtend), ('\u{1AB0}', '\u{1ADD}', Break::ConjunctExtend),
+    ('\u{1AE0}', '\u{1AEB}', Break::ConjunctExtend), ('\u{1B00}', '\u{1B03}', Break::ConjunctExtend),
+    ('\u{1B04}', '\u{1B04}', Break::SpacingMark), ('\u{1B0B}', '\u{1B0C}', Break::Consonant),
+    ('\u{1B13}', '\u{1B33}', Break::Consonant), ('\u{1B34}', '\u{1B3D}', Break::ConjunctExtend),
+    ('\u{1B3E}', '\u{1B41}', Break::SpacingMark), ('\u{1B42}', '\u{1B43}', Break::ConjunctExtend),
+    ('\u{1B44}', '\u{1B44}', Break::Linker), ('\u{1B45}', '\u{1B4C}', Break::Consonant),
+    ('\u{1B6B}', '\u{1B73}', Break::ConjunctExtend), ('\u{1B80}', '\u{1B81}', Break::ConjunctExtend),
+    ('\u{1B82}', '\u{1B82}', Break::SpacingMark), ('\u{1B83}', '\u{1BA0}', Break::Consonant),
+    ('\u{1BA1}', '\u{1BA1}', Break::SpacingMark), ('\u{1BA2}', '\u{1BA5}', Break::ConjunctExtend),
+    ('\u{1BA6}', '\u{1BA7}', Break::SpacingMark), ('\u{1BA8}', '\u{1BAA}', Break::ConjunctExtend),
+    ('\u{1BAB}', '\u{1BAB}', Break::Linker), ('\u{1BAC}', '\u{1BAD}', Break::ConjunctExtend),
+    ('\u{1BAE}', '\u{1BAF}', Break::Consonant), ('\u{1BBB}', '\u{1BBD}', Break::Consonant),
+    ('\u{1BE6}', '\u{1BE6}', Break::ConjunctExtend), ('\u{1BE7}', '\u{1BE7}', Break::SpacingMark),
+    ('\u{1BE8}', '\u{1BE9}', Break::ConjunctExtend), ('\u{1BEA}', '\u{1BEC}', Break::SpacingMark),
+    ('\u{1BED}', '\u{1BED}', Break::ConjunctExtend), ('\u{1BEE}', '\u{1BEE}', Break::SpacingMark),
+    ('\u{1BEF}', '\u{1BF3}', Break::ConjunctExtend), ('\u{1C24}', '\u{1C2B}', Break::SpacingMark),
+    ('\u{1C2C}', '\u{1C33}', Break::ConjunctExtend), ('\u{1C34}', '\u{1C35}', Break::SpacingMark),
+    ('\u{1C36}', '\u{1C37}', Break::ConjunctExtend), ('\u{1CD0}', '\u{1CD2}', Break::ConjunctExtend),
+    ('\u{1CD4}', '\u{1CE0}', Break::ConjunctExtend), ('\u{1CE1}', '\u{1CE1}', Break::SpacingMark),
+    ('\u{1CE2}', '\u{1CE8}', Break::ConjunctExtend), ('\u{1CED}', '\u{1CED}', Break::ConjunctExtend),
+    ('\u{1CF4}', '\u{1CF4}', Break::ConjunctExtend), ('\u{1CF7}', '\u{1CF7}', Break::SpacingMark),
+    ('\u{1CF8}', '\u{1CF9}', Break::ConjunctExtend), ('\u{1DC0}', '\u{1DFF}', Break::ConjunctExtend),
+    ('\u{200B}', '\u{200B}', Break::Control), ('\u{200C}', '\u{200C}', Break::Extend),
+    ('\u{200D}', '\u{200D}', Break::Zwj), ('\u{200E}', '\u{200F}', Break::Control),
+    ('\u{2028}', '\u{202E}', Break::Control), ('\u{203C}', '\u{203C}', Break::Pictographic),
+    ('\u{2049}', '\u{2049}', Break::Pictographic), ('\u{2060}', '\u{206F}', Break::Control),
+    ('\u{20D0}', '\u{20F0}', Break::ConjunctExtend), ('\u{2122}', '\u{2122}', Break::Pictographic),
+    ('\u{2139}', '\u{2139}', Break::Pictographic), ('\u{2194}', '\u{2199}', Break::Pictographic),
+    ('\u{21A9}', '\u{21AA}', Break::Pictographic), ('\u{231A}', '\u{231B}', Break::Pictographic),
+    ('\u{2328}', '\u{2328}', Break::Pictographic), ('\u{23CF}', '\u{23CF}', Break::Pictographic),
+    ('\u{23E9}', '\u{23F3}', Break::Pictographic), ('\u{23F8}', '\u{23FA}', Break::Pictographic),
+    ('\u{24C2}', '\u{24C2}', Break::Pictographic), ('\u{25AA}', '\u{25AB}', Break::Pictographic),
+    ('\u{25B6}', '\u{25B6}', Break::Pictographic), ('\u{25C0}', '\u{25C0}', Break::Pictographic),
+    ('\u{25FB}', '\u{25FE}', Break::Pictographic), ('\u{2600}', '\u{2604}', Break::Pictographic),
+    ('\u{260E}', '\u{260E}', Break::Pictographic), ('\u{2611}', '\u{2611}', Break::Pictographic),
+    ('\u{2614}', '\u{2615}', Break::Pictographic), ('\u{2618}', '\u{2618}', Break::Pictographic),
+    ('\u{261D}', '\u{261D}', Break::Pictographic), ('\u{2620}', '\u{2620}', Break::Pictographic),
+    ('\u{2622}', '\u{2623}', Break::Pictographic), ('\u{2626}', '\u{2626}', Break::Pictographic),
+    ('\u{262A}', '\u{262A}', Break::Pictographic), ('\u{262E}', '\u{262F}', Break::Pictographic),
+    ('\u{2638}', '\u{263A}', Break::Pictographic), ('\u{2640}', '\u{2640}', Break::Pictographic),
+    ('\u{2642}', '\u{2642}', Break::Pictographic), ('\u{2648}', '\u{2653}', Break::Pictographic),
+    ('\u{265F}', '\u{2660}', Break::Pictographic), ('\u{2663}', '\u{2663}', Break::Pictographic),
+    ('\u{2665}', '\u{2666}', Break::Pictographic), ('\u{2668}', '\u{2668}', Break::Pictographic),
+    ('\u{267B}', '\u{267B}', Break::Pictographic), ('\u{267E}', '\u{267F}', Break::Pictographic),
+    ('\u{2692}', '\u{2697}', Break::Pictographic), ('\u{2699}', '\u{2699}', Break::Pictographic),
+    ('\u{269B}', '\u{269C}', Break::Pictographic), ('\u{26A0}', '\u{26A1}', Break::Pictographic),
+    ('\u{26A7}', '\u{26A7}', Break::Pictographic), ('\u{26AA}', '\u{26AB}', Break::Pictographic),
+    ('\u{26B0}', '\u{26B1}', Break::Pictographic), ('\u{26BD}', '\u{26BE}', Break::Pictographic),
+    ('\u{26C4}', '\u{26C5}', Break::Pictographic), ('\u{26C8}', '\u{26C8}', Break::Pictographic),
+    ('\u{26CE}', '\u{26CF}', Break::Pictographic), ('\u{26D1}', '\u{26D1}', Break::Pictographic),
+    ('\u{26D3}', '\u{26D4}', Break::Pictographic), ('\u{26E9}', '\u{26EA}', Break::Pictographic),
+    ('\u{26F0}', '\u{26F5}', Break::Pictographic), ('\u{26F7}', '\u{26FA}', Break::Pictographic),
+    ('\u{26FD}', '\u{26FD}', Break::Pictographic), ('\u{2702}', '\u{2702}', Break::Pictographic),
+    ('\u{2705}', '\u{2705}', Break::Pictographic), ('\u{2708}', '\u{270D}', Break::Pictographic),
+    ('\u{270F}', '\u{270F}', Break::Pictographic), ('\u{2712}', '\u{2712}', Break::Pictographic),
+    ('\u{2714}', '\u{2714}', Break::Pictographic), ('\u{2716}', '\u{2716}', Break::Pictographic),
+    ('\u{271D}', '\u{271D}', Break::Pictographic), ('\u{2721}', '\u{2721}', Break::Pictographic),
+    ('\u{2728}', '\u{2728}', Break::Pictographic), ('\u{2733}', '\u{2734}', Break::Pictographic),
+    ('\u{2744}', '\u{2744}', Break::Pictographic), ('\u{2747}', '\u{2747}', Break::Pictographic),
+    ('\u{274C}', '\u{274C}', Break::Pictographic), ('\u{274E}', '\u{274E}', Break::Pictographic),
+    ('\u{2753}', '\u{2755}', Break::Pictographic), ('\u{2757}', '\u{2757}', Break::Pictographic),
+    ('\u{2763}', '\u{2764}', Break::Pictographic), ('\u{2795}', '\u{2797}', Break::Pictographic),
+    ('\u{27A1}', '\u{27A1}', Break::Pictographic), ('\u{27B0}', '\u{27B0}', Break::Pictographic),
+    ('\u{27BF}', '\u{27BF}', Break::Pictographic), ('\u{2934}', '\u{2935}', Break::Pictographic),
+    ('\u{2B05}', '\u{2B07}', Break::Pictographic), ('\u{2B1B}', '\u{2B1C}', Break::Pictographic),
+    ('\u{2B50}', '\u{2B50}', Break::Pictographic), ('\u{2B55}', '\u{2B55}', Break::Pictographic),
+    ('\u{2CEF}', '\u{2CF1}', Break::ConjunctExtend), ('\u{2D7F}', '\u{2D7F}', Break::ConjunctExtend),
+    ('\u{2DE0}', '\u{2DFF}', Break::ConjunctExtend), ('\u{302A}', '\u{302F}', Break::ConjunctExtend),
+    ('\u{3030}', '\u{3030}', Break::Pictographic), ('\u{303D}', '\u{303D}', Break::Pictographic),
+    ('\u{3099}', '\u{309A}', Break::ConjunctExtend), ('\u{3297}', '\u{3297}', Break::Pictographic),
+    ('\u{3299}', '\u{3299}', Break::Pictographic), ('\u{A66F}', '\u{A672}', Break::ConjunctExtend),
+    ('\u{A674}', '\u{A67D}', Break::ConjunctExtend), ('\u{A69E}', '\u{A69F}', Break::ConjunctExtend),
+    ('\u{A6F0}', '\u{A6F1}', Break::ConjunctExtend), ('\u{A802}', '\u{A802}', Break::ConjunctExtend),
+    ('\u{A806}', '\u{A806}', Break::ConjunctExtend), ('\u{A80B}', '\u{A80B}', Break::ConjunctExtend),
+    ('\u{A823}', '\u{A824}', Break::SpacingMark), ('\u{A825}', '\u{A826}', Break::ConjunctExtend),
+    ('\u{A827}', '\u{A827}', Break::SpacingMark), ('\u{A82C}', '\u{A82C}', Break::ConjunctExtend),
+    ('\u{A880}', '\u{A881}', Break::SpacingMark), ('\u{A8B4}', '\u{A8C3}', Break::SpacingMark),
+    ('\u{A8C4}', '\u{A8C5}', Break::ConjunctExtend), ('\u{A8E0}', '\u{A8F1}', Break::ConjunctExtend),
+    ('\u{A8FF}', '\u{A8FF}', Break::ConjunctExtend), ('\u{A926}', '\u{A92D}', Break::ConjunctExtend),
+    ('\u{A947}', '\u{A951}', Break::ConjunctExtend), ('\u{A952}', '\u{A952}', Break::SpacingMark),
+    ('\u{A953}', '\u{A953}', Break::ConjunctExtend), ('\u{A960}', '\u{A97C}', Break::L),
+    ('\u{A980}', '\u{A982}', Break::ConjunctExtend), ('\u{A983}', '\u{A983}', Break::SpacingMark),
+    ('\u{A989}', '\u{A98B}', Break::Consonant), ('\u{A98F}', '\u{A9B2}', Break::Consonant),
+    ('\u{A9B3}', '\u{A9B3}', Break::ConjunctExtend), ('\u{A9B4}', '\u{A9B5}', Break::SpacingMark),
+    ('\u{A9B6}', '\u{A9B9}', Break::ConjunctExtend), ('\u{A9BA}', '\u{A9BB}', Break::SpacingMark),
+    ('\u{A9BC}', '\u{A9BD}', Break::ConjunctExtend), ('\u{A9BE}', '\u{A9BF}', Break::SpacingMark),
+    ('\u{A9C0}', '\u{A9C0}', Break::Linker), ('\u{A9E0}', '\u{A9E4}', Break::Consonant),
+    ('\u{A9E5}', '\u{A9E5}', Break::ConjunctExtend), ('\u{A9E7}', '\u{A9EF}', Break::Consonant),
+    ('\u{A9FA}', '\u{A9FE}', Break::Consonant), ('\u{AA29}', '\u{AA2E}', Break::ConjunctExtend),
+    ('\u{AA2F}', '\u{AA30}', Break::SpacingMark), ('\u{AA31}', '\u{AA32}', Break::ConjunctExtend),
+    ('\u{AA33}', '\u{AA34}', Break::SpacingMark), ('\u{AA35}', '\u{AA36}', Break::ConjunctExtend),
+    ('\u{AA43}', '\u{AA43}', Break::ConjunctExtend), ('\u{AA4C}', '\u{AA4C}', Break::ConjunctExtend),
+    ('\u{AA4D}', '\u{AA4D}', Break::SpacingMark), ('\u{AA60}', '\u{AA6F}', Break::Consonant),
+    ('\u{AA71}', '\u{AA73}', Break::Consonant), ('\u{AA7A}', '\u{AA7A}', Break::Consonant),
+    ('\u{AA7C}', '\u{AA7C}', Break::ConjunctExtend), ('\u{AA7E}', '\u{AA7F}', Break::Consonant),
+    ('\u{AAB0}', '\u{AAB0}', Break::ConjunctExtend), ('\u{AAB2}', '\u{AAB4}', Break::ConjunctExtend),
+    ('\u{AAB7}', '\u{AAB8}', Break::ConjunctExtend), ('\u{AABE}', '\u{AABF}', Break::ConjunctExtend),
+    ('\u{AAC1}', '\u{AAC1}', Break::ConjunctExtend), ('\u{AAE0}', '\u{AAEA}', Break::Consonant),
+    ('\u{AAEB}', '\u{AAEB}', Break::SpacingMark), ('\u{AAEC}', '\u{AAED}', Break::ConjunctExtend),
+    ('\u{AAEE}', '\u{AAEF}', Break::SpacingMark), ('\u{AAF5}', '\u{AAF5}', Break::SpacingMark),
+    ('\u{AAF6}', '\u{AAF6}', Break::Linker), ('\u{ABC0}', '\u{ABDA}', Break::Consonant),
+    ('\u{ABE3}', '\u{ABE4}', Break::SpacingMark), ('\u{ABE5}', '\u{ABE5}', Break::ConjunctExtend),
+    ('\u{ABE6}', '\u{ABE7}', Break::SpacingMark), ('\u{ABE8}', '\u{ABE8}', Break::ConjunctExtend),
+    ('\u{ABE9}', '\u{ABEA}', Break::SpacingMark), ('\u{ABEC}', '\u{ABEC}', Break::SpacingMark),
+    ('\u{ABED}', '\u{ABED}', Break::ConjunctExtend), ('\u{AC00}', '\u{AC00}', Break::Lv),
+    ('\u{AC01}', '\u{AC1B}', Break::Lvt), ('\u{AC1C}', '\u{AC1C}', Break::Lv),
+    ('\u{AC1D}', '\u{AC37}', Break::Lvt), ('\u{AC38}', '\u{AC38}', Break::Lv),
+    ('\u{AC39}', '\u{AC53}', Break::Lvt), ('\u{AC54}', '\u{AC54}', Break::Lv),
+    ('\u{AC55}', '\u{AC6F}', Break::Lvt), ('\u{AC70}', '\u{AC70}', Break::Lv),
+    ('\u{AC71}', '\u{AC8B}', Break::Lvt), ('\u{AC8C}', '\u{AC8C}', Break::Lv),
+    ('\u{AC8D}', '\u{ACA7}', Break::Lvt), ('\u{ACA8}', '\u{ACA8}', Break::Lv),
+    ('\u{ACA9}', '\u{ACC3}', Break::Lvt), ('\u{ACC4}', '\u{ACC4}', Break::Lv),
+    ('\u{ACC5}', '\u{ACDF}', Break::Lvt), ('\u{ACE0}', '\u{ACE0}', Break::Lv),
+    ('\u{ACE1}', '\u{ACFB}', Break::Lvt), ('\u{ACFC}', '\u{ACFC}', Break::Lv),
+    ('\u{ACFD}', '\u{AD17}', Break::Lvt), ('\u{AD18}', '\u{AD18}', Break::Lv),
+    ('\u{AD19}', '\u{AD33}', Break::Lvt), ('\u{AD34}', '\u{AD34}', Break::Lv),
+    ('\u{AD35}', '\u{AD4F}', Break::Lvt), ('\u{AD50}', '\u{AD50}', Break::Lv),
+    ('\u{AD51}', '\u{AD6B}', Break::Lvt), ('\u{AD6C}', '\u{AD6C}', Break::Lv),
+    ('\u{AD6D}', '\u{AD87}', Break::Lvt), ('\u{AD88}', '\u{AD88}', Break::Lv),
+    ('\u{AD89}', '\u{ADA3}', Break::Lvt), ('\u{ADA4}', '\u{ADA4}', Break::Lv),
+    ('\u{ADA5}', '\u{ADBF}', Break::Lvt), ('\u{ADC0}', '\u{ADC0}', Break::Lv),
+    ('\u{ADC1}', '\u{ADDB}', Break::Lvt), ('\u{ADDC}', '\u{ADDC}', Break::Lv),
+    ('\u{ADDD}', '\u{ADF7}', Break::Lvt), ('\u{ADF8}', '\u{ADF8}', Break::Lv),
+    ('\u{ADF9}', '\u{AE13}', Break::Lvt), ('\u{AE14}', '\u{AE14}', Break::Lv),
+    ('\u{AE15}', '\u{AE2F}', Break::Lvt), ('\u{AE30}', '\u{AE30}', Break::Lv),
+    ('\u{AE31}', '\u{AE4B}', Break::Lvt), ('\u{AE4C}', '\u{AE4C}', Break::Lv),
+    ('\u{AE4D}', '\u{AE67}', Break::Lvt), ('\u{AE68}', '\u{AE68}', Break::Lv),
+    ('\u{AE69}', '\u{AE83}', Break::Lvt), ('\u{AE84}', '\u{AE84}', Break::Lv),
+    ('\u{AE85}', '\u{AE9F}', Break::Lvt), ('\u{AEA0}', '\u{AEA0}', Break::Lv),
+    ('\u{AEA1}', '\u{AEBB}', Break::Lvt), ('\u{AEBC}', '\u{AEBC}', Break::Lv),
+    ('\u{AEBD}', '\u{AED7}', Break::Lvt), ('\u{AED8}', '\u{AED8}', Break::Lv),
+    ('\u{AED9}', '\u{AEF3}', Break::Lvt), ('\u{AEF4}', '\u{AEF4}', Break::Lv),
+    ('\u{AEF5}', '\u{AF0F}', Break::Lvt), ('\u{AF10}', '\u{AF10}', Break::Lv),
+    ('\u{AF11}', '\u{AF2B}', Break::Lvt), ('\u{AF2C}', '\u{AF2C}', Break::Lv),
+    ('\u{AF2D}', '\u{AF47}', Break::Lvt), ('\u{AF48}', '\u{AF48}', Break::Lv),
+    ('\u{AF49}', '\u{AF63}', Break::Lvt), ('\u{AF64}', '\u{AF64}', Break::Lv),
+    ('\u{AF65}', '\u{AF7F}', Break::Lvt), ('\u{AF80}', '\u{AF80}', Break::Lv),
+    ('\u{AF81}', '\u{AF9B}', Break::Lvt), ('\u{AF9C}', '\u{AF9C}', Break::Lv),
+    ('\u{AF9D}', '\u{AFB7}', Break::Lvt), ('\u{AFB8}', '\u{AFB8}', Break::Lv),
+    ('\u{AFB9}', '\u{AFD3}', Break::Lvt), ('\u{AFD4}', '\u{AFD4}', Break::Lv),
+    ('\u{AFD5}', '\u{AFEF}', Break::Lvt), ('\u{AFF0}', '\u{AFF0}', Break::Lv),
+    ('\u{AFF1}', '\u{B00B}', Break::Lvt), ('\u{B00C}', '\u{B00C}', Break::Lv),
+    ('\u{B00D}', '\u{B027}', Break::Lvt), ('\u{B028}', '\u{B028}', Break::Lv),
+    ('\u{B029}', '\u{B043}', Break::Lvt), ('\u{B044}', '\u{B044}', Break::Lv),
+    ('\u{B045}', '\u{B05F}', Break::Lvt), ('\u{B060}', '\u{B060}', Break::Lv),
+    ('\u{B061}', '\u{B07B}', Break::Lvt), ('\u{B07C}', '\u{B07C}', Break::Lv),
+    ('\u{B07D}', '\u{B097}', Break::Lvt), ('\u{B098}', '\u{B098}', Break::Lv),
+    ('\u{B099}', '\u{B0B3}', Break::Lvt), ('\u{B0B4}', '\u{B0B4}', Break::Lv),
+    ('\u{B0B5}', '\u{B0CF}', Break::Lvt), ('\u{B0D0}', '\u{B0D0}', Break::Lv),
+    ('\u{B0D1}', '\u{B0EB}', Break::Lvt), ('\u{B0EC}', '\u{B0EC}', Break::Lv),
+    ('\u{B0ED}', '\u{B107}', Break::Lvt), ('\u{B108}', '\u{B108}', Break::Lv),
+    ('\u{B109}', '\u{B123}', Break::Lvt), ('\u{B124}', '\u{B124}', Break::Lv),
+    ('\u{B125}', '\u{B13F}', Break::Lvt), ('\u{B140}', '\u{B140}', Break::Lv),
+    ('\u{B141}', '\u{B15B}', Break::Lvt), ('\u{B15C}', '\u{B15C}', Break::Lv),
+    ('\u{B15D}', '\u{B177}', Break::Lvt), ('\u{B178}', '\u{B178}', Break::Lv),
+    ('\u{B179}', '\u{B193}', Break::Lvt), ('\u{B194}', '\u{B194}', Break::Lv),
+    ('\u{B195}', '\u{B1AF}', Break::Lvt), ('\u{B1B0}', '\u{B1B0}', Break::Lv),
+    ('\u{B1B1}', '\u{B1CB}', Break::Lvt), ('\u{B1CC}', '\u{B1CC}', Break::Lv),
+    ('\u{B1CD}', '\u{B1E7}', Break::Lvt), ('\u{B1E8}', '\u{B1E8}', Break::Lv),
+    ('\u{B1E9}', '\u{B203}', Break::Lvt), ('\u{B204}', '\u{B204}', Break::Lv),
+    ('\u{B205}', '\u{B21F}', Break::Lvt), ('\u{B220}', '\u{B220}', Break::Lv),
+    ('\u{B221}', '\u{B23B}', Break::Lvt), ('\u{B23C}', '\u{B23C}', Break::Lv),
+    ('\u{B23D}', '\u{B257}', Break::Lvt), ('\u{B258}', '\u{B258}', Break::Lv),
+    ('\u{B259}', '\u{B273}', Break::Lvt), ('\u{B274}', '\u{B274}', Break::Lv),
+    ('\u{B275}', '\u{B28F}', Break::Lvt), ('\u{B290}', '\u{B290}', Break::Lv),
+    ('\u{B291}', '\u{B2AB}', Break::Lvt), ('\u{B2AC}', '\u{B2AC}', Break::Lv),
+    ('\u{B2AD}', '\u{B2C7}', Break::Lvt), ('\u{B2C8}', '\u{B2C8}', Break::Lv),
+    ('\u{B2C9}', '\u{B2E3}', Break::Lvt), ('\u{B2E4}', '\u{B2E4}', Break::Lv),
+    ('\u{B2E5}', '\u{B2FF}', Break::Lvt), ('\u{B300}', '\u{B300}', Break::Lv),
+    ('\u{B301}', '\u{B31B}', Break::Lvt), ('\u{B31C}', '\u{B31C}', Break::Lv),
+    ('\u{B31D}', '\u{B337}', Break::Lvt), ('\u{B338}', '\u{B338}', Break::Lv),
+    ('\u{B339}', '\u{B353}', Break::Lvt), ('\u{B354}', '\u{B354}', Break::Lv),
+    ('\u{B355}', '\u{B36F}', Break::Lvt), ('\u{B370}', '\u{B370}', Break::Lv),
+    ('\u{B371}', '\u{B38B}', Break::Lvt), ('\u{B38C}', '\u{B38C}', Break::Lv),
+    ('\u{B38D}', '\u{B3A7}', Break::Lvt), ('\u{B3A8}', '\u{B3A8}', Break::Lv),
+    ('\u{B3A9}', '\u{B3C3}', Break::Lvt), ('\u{B3C4}', '\u{B3C4}', Break::Lv),
+    ('\u{B3C5}', '\u{B3DF}', Break::Lvt), ('\u{B3E0}', '\u{B3E0}', Break::Lv),
+    ('\u{B3E1}', '\u{B3FB}', Break::Lvt), ('\u{B3FC}', '\u{B3FC}', Break::Lv),
+    ('\u{B3FD}', '\u{B417}', Break::Lvt), ('\u{B418}', '\u{B418}', Break::Lv),
+    ('\u{B419}', '\u{B433}', Break::Lvt), ('\u{B434}', '\u{B434}', Break::Lv),
+    ('\u{B435}', '\u{B44F}', Break::Lvt), ('\u{B450}', '\u{B450}', Break::Lv),
+    ('\u{B451}', '\u{B46B}', Break::Lvt), ('\u{B46C}', '\u{B46C}', Break::Lv),
+    ('\u{B46D}', '\u{B487}', Break::Lvt), ('\u{B488}', '\u{B488}', Break::Lv),
+    ('\u{B489}', '\u{B4A3}', Break::Lvt), ('\u{B4A4}', '\u{B4A4}', Break::Lv),
+    ('\u{B4A5}', '\u{B4BF}', Break::Lvt), ('\u{B4C0}', '\u{B4C0}', Break::Lv),
+    ('\u{B4C1}', '\u{B4DB}', Break::Lvt), ('\u{B4DC}', '\u{B4DC}', Break::Lv),
+    ('\u{B4DD}', '\u{B4F7}', Break::Lvt), ('\u{B4F8}', '\u{B4F8}', Break::Lv),
+    ('\u{B4F9}', '\u{B513}', Break::Lvt), ('\u{B514}', '\u{B514}', Break::Lv),
+    ('\u{B515}', '\u{B52F}', Break::Lvt), ('\u{B530}', '\u{B530}', Break::Lv),
+    ('\u{B531}', '\u{B54B}', Break::Lvt), ('\u{B54C}', '\u{B54C}', Break::Lv),
+    ('\u{B54D}', '\u{B567}', Break::Lvt), ('\u{B568}', '\u{B568}', Break::Lv),
+    ('\u{B569}', '\u{B583}', Break::Lvt), ('\u{B584}', '\u{B584}', Break::Lv),
+    ('\u{B585}', '\u{B59F}', Break::Lvt), ('\u{B5A0}', '\u{B5A0}', Break::Lv),
+    ('\u{B5A1}', '\u{B5BB}', Break::Lvt), ('\u{B5BC}', '\u{B5BC}', Break::Lv),
+    ('\u{B5BD}', '\u{B5D7}', Break::Lvt), ('\u{B5D8}', '\u{B5D8}', Break::Lv),
+    ('\u{B5D9}', '\u{B5F3}', Break::Lvt), ('\u{B5F4}', '\u{B5F4}', Break::Lv),
+    ('\u{B5F5}', '\u{B60F}', Break::Lvt), ('\u{B610}', '\u{B610}', Break::Lv),
+    ('\u{B611}', '\u{B62B}', Break::Lvt), ('\u{B62C}', '\u{B62C}', Break::Lv),
+    ('\u{B62D}', '\u{B647}', Break::Lvt), ('\u{B648}', '\u{B648}', Break::Lv),
+    ('\u{B649}', '\u{B663}', Break::Lvt), ('\u{B664}', '\u{B664}', Break::Lv),
+    ('\u{B665}', '\u{B67F}', Break::Lvt), ('\u{B680}', '\u{B680}', Break::Lv),
+    ('\u{B681}', '\u{B69B}', Break::Lvt), ('\u{B69C}', '\u{B69C}', Break::Lv),
+    ('\u{B69D}', '\u{B6B7}', Break::Lvt), ('\u{B6B8}', '\u{B6B8}', Break::Lv),
+    ('\u{B6B9}', '\u{B6D3}', Break::Lvt), ('\u{B6D4}', '\u{B6D4}', Break::Lv),
+    ('\u{B6D5}', '\u{B6EF}', Break::Lvt), ('\u{B6F0}', '\u{B6F0}', Break::Lv),
+    ('\u{B6F1}', '\u{B70B}', Break::Lvt), ('\u{B70C}', '\u{B70C}', Break::Lv),
+    ('\u{B70D}', '\u{B727}', Break::Lvt), ('\u{B728}', '\u{B728}', Break::Lv),
+    ('\u{B729}', '\u{B743}', Break::Lvt), ('\u{B744}', '\u{B744}', Break::Lv),
+    ('\u{B745}', '\u{B75F}', Break::Lvt), ('\u{B760}', '\u{B760}', Break::Lv),
+    ('\u{B761}', '\u{B77B}', Break::Lvt), ('\u{B77C}', '\u{B77C}', Break::Lv),
+    ('\u{B77D}', '\u{B797}', Break::Lvt), ('\u{B798}', '\u{B798}', Break::Lv),
+    ('\u{B799}', '\u{B7B3}', Break::Lvt), ('\u{B7B4}', '\u{B7B4}', Break::Lv),
+    ('\u{B7B5}', '\u{B7CF}', Break::Lvt), ('\u{B7D0}', '\u{B7D0}', Break::Lv),
+    ('\u{B7D1}', '\u{B7EB}', Break::Lvt), ('\u{B7EC}', '\u{B7EC}', Break::Lv),
+    ('\u{B7ED}', '\u{B807}', Break::Lvt), ('\u{B808}', '\u{B808}', Break::Lv),
+    ('\u{B809}', '\u{B823}', Break::Lvt), ('\u{B824}', '\u{B824}', Break::Lv),
+    ('\u{B825}', '\u{B83F}', Break::Lvt), ('\u{B840}', '\u{B840}', Break::Lv),
+    ('\u{B841}', '\u{B85B}', Break::Lvt), ('\u{B85C}', '\u{B85C}', Break::Lv),
+    ('\u{B85D}', '\u{B877}', Break::Lvt), ('\u{B878}', '\u{B878}', Break::Lv),
+    ('\u{B879}', '\u{B893}', Break::Lvt), ('\u{B894}', '\u{B894}', Break::Lv),
+    ('\u{B895}', '\u{B8AF}', Break::Lvt), ('\u{B8B0}', '\u{B8B0}', Break::Lv),
+    ('\u{B8B1}', '\u{B8CB}', Break::Lvt), ('\u{B8CC}', '\u{B8CC}', Break::Lv),
+    ('\u{B8CD}', '\u{B8E7}', Break::Lvt), ('\u{B8E8}', '\u{B8E8}', Break::Lv),
+    ('\u{B8E9}', '\u{B903}', Break::Lvt), ('\u{B904}', '\u{B904}', Break::Lv),
+    ('\u{B905}', '\u{B91F}', Break::Lvt), ('\u{B920}', '\u{B920}', Break::Lv),
+    ('\u{B921}', '\u{B93B}', Break::Lvt), ('\u{B93C}', '\u{B93C}', Break::Lv),
+    ('\u{B93D}', '\u{B957}', Break::Lvt), ('\u{B958}', '\u{B958}', Break::Lv),
+    ('\u{B959}', '\u{B973}', Break::Lvt), ('\u{B974}', '\u{B974}', Break::Lv),
+    ('\u{B975}', '\u{B98F}', Break::Lvt), ('\u{B990}', '\u{B990}', Break::Lv),
+    ('\u{B991}', '\u{B9AB}', Break::Lvt), ('\u{B9AC}', '\u{B9AC}', Break::Lv),
+    ('\u{B9AD}', '\u{B9C7}', Break::Lvt), ('\u{B9C8}', '\u{B9C8}', Break::Lv),
+    ('\u{B9C9}', '\u{B9E3}', Break::Lvt), ('\u{B9E4}', '\u{B9E4}', Break::Lv),
+    ('\u{B9E5}', '\u{B9FF}', Break::Lvt), ('\u{BA00}', '\u{BA00}', Break::Lv),
+    ('\u{BA01}', '\u{BA1B}', Break::Lvt), ('\u{BA1C}', '\u{BA1C}', Break::Lv),
+    ('\u{BA1D}', '\u{BA37}', Break::Lvt), ('\u{BA38}', '\u{BA38}', Break::Lv),
+    ('\u{BA39}', '\u{BA53}', Break::Lvt), ('\u{BA54}', '\u{BA54}', Break::Lv),
+    ('\u{BA55}', '\u{BA6F}', Break::Lvt), ('\u{BA70}', '\u{BA70}', Break::Lv),
+    ('\u{BA71}', '\u{BA8B}', Break::Lvt), ('\u{BA8C}', '\u{BA8C}', Break::Lv),
+    ('\u{BA8D}', '\u{BAA7}', Break::Lvt), ('\u{BAA8}', '\u{BAA8}', Break::Lv),
+    ('\u{BAA9}', '\u{BAC3}', Break::Lvt), ('\u{BAC4}', '\u{BAC4}', Break::Lv),
+    ('\u{BAC5}', '\u{BADF}', Break::Lvt), ('\u{BAE0}', '\u{BAE0}', Break::Lv),
+    ('\u{BAE1}', '\u{BAFB}', Break::Lvt), ('\u{BAFC}', '\u{BAFC}', Break::Lv),
+    ('\u{BAFD}', '\u{BB17}', Break::Lvt), ('\u{BB18}', '\u{BB18}', Break::Lv),
+    ('\u{BB19}', '\u{BB33}', Break::Lvt), ('\u{BB34}', '\u{BB34}', Break::Lv),
+    ('\u{BB35}', '\u{BB4F}', Break::Lvt), ('\u{BB50}', '\u{BB50}', Break::Lv),
+    ('\u{BB51}', '\u{BB6B}', Break::Lvt), ('\u{BB6C}', '\u{BB6C}', Break::Lv),
+    ('\u{BB6D}', '\u{BB87}', Break::Lvt), ('\u{BB88}', '\u{BB88}', Break::Lv),
+    ('\u{BB89}', '\u{BBA3}', Break::Lvt), ('\u{BBA4}', '\u{BBA4}', Break::Lv),
+    ('\u{BBA5}', '\u{BBBF}', Break::Lvt), ('\u{BBC0}', '\u{BBC0}', Break::Lv),
+    ('\u{BBC1}', '\u{BBDB}', Break::Lvt), ('\u{BBDC}', '\u{BBDC}', Break::Lv),
+    ('\u{BBDD}', '\u{BBF7}', Break::Lvt), ('\u{BBF8}', '\u{BBF8}', Break::Lv),
+    ('\u{BBF9}', '\u{BC13}', Break::Lvt), ('\u{BC14}', '\u{BC14}', Break::Lv),
+    ('\u{BC15}', '\u{BC2F}', Break::Lvt), ('\u{BC30}', '\u{BC30}', Break::Lv),
+    ('\u{BC31}', '\u{BC4B}', Break::Lvt), ('\u{BC4C}', '\u{BC4C}', Break::Lv),
+    ('\u{BC4D}', '\u{BC67}', Break::Lvt), ('\u{BC68}', '\u{BC68}', Break::Lv),
+    ('\u{BC69}', '\u{BC83}', Break::Lvt), ('\u{BC84}', '\u{BC84}', Break::Lv),
+    ('\u{BC85}', '\u{BC9F}', Break::Lvt), ('\u{BCA0}', '\u{BCA0}', Break::Lv),
+    ('\u{BCA1}', '\u{BCBB}', Break::Lvt), ('\u{BCBC}', '\u{BCBC}', Break::Lv),
+    ('\u{BCBD}', '\u{BCD7}', Break::Lvt), ('\u{BCD8}', '\u{BCD8}', Break::Lv),
+    ('\u{BCD9}', '\u{BCF3}', Break::Lvt), ('\u{BCF4}', '\u{BCF4}', Break::Lv),
+    ('\u{BCF5}', '\u{BD0F}', Break::Lvt), ('\u{BD10}', '\u{BD10}', Break::Lv),
+    ('\u{BD11}', '\u{BD2B}', Break::Lvt), ('\u{BD2C}', '\u{BD2C}', Break::Lv),
+    ('\u{BD2D}', '\u{BD47}', Break::Lvt), ('\u{BD48}', '\u{BD48}', Break::Lv),
+    ('\u{BD49}', '\u{BD63}', Break::Lvt), ('\u{BD64}', '\u{BD64}', Break::Lv),
+    ('\u{BD65}', '\u{BD7F}', Break::Lvt), ('\u{BD80}', '\u{BD80}', Break::Lv),
+    ('\u{BD81}', '\u{BD9B}', Break::Lvt), ('\u{BD9C}', '\u{BD9C}', Break::Lv),
+    ('\u{BD9D}', '\u{BDB7}', Break::Lvt), ('\u{BDB8}', '\u{BDB8}', Break::Lv),
+    ('\u{BDB9}', '\u{BDD3}', Break::Lvt), ('\u{BDD4}', '\u{BDD4}', Break::Lv),
+    ('\u{BDD5}', '\u{BDEF}', Break::Lvt), ('\u{BDF0}', '\u{BDF0}', Break::Lv),
+    ('\u{BDF1}', '\u{BE0B}', Break::Lvt), ('\u{BE0C}', '\u{BE0C}', Break::Lv),
+    ('\u{BE0D}', '\u{BE27}', Break::Lvt), ('\u{BE28}', '\u{BE28}', Break::Lv),
+    ('\u{BE29}', '\u{BE43}', Break::Lvt), ('\u{BE44}', '\u{BE44}', Break::Lv),
+    ('\u{BE45}', '\u{BE5F}', Break::Lvt), ('\u{BE60}', '\u{BE60}', Break::Lv),
+    ('\u{BE61}', '\u{BE7B}', Break::Lvt), ('\u{BE7C}', '\u{BE7C}', Break::Lv),
+    ('\u{BE7D}', '\u{BE97}', Break::Lvt), ('\u{BE98}', '\u{BE98}', Break::Lv),
+    ('\u{BE99}', '\u{BEB3}', Break::Lvt), ('\u{BEB4}', '\u{BEB4}', Break::Lv),
+    ('\u{BEB5}', '\u{BECF}', Break::Lvt), ('\u{BED0}', '\u{BED0}', Break::Lv),
+    ('\u{BED1}', '\u{BEEB}', Break::Lvt), ('\u{BEEC}', '\u{BEEC}', Break::Lv),
+    ('\u{BEED}', '\u{BF07}', Break::Lvt), ('\u{BF08}', '\u{BF08}', Break::Lv),
+    ('\u{BF09}', '\u{BF23}', Break::Lvt), ('\u{BF24}', '\u{BF24}', Break::Lv),
+    ('\u{BF25}', '\u{BF3F}', Break::Lvt), ('\u{BF40}', '\u{BF40}', Break::Lv),
+    ('\u{BF41}', '\u{BF5B}', Break::Lvt), ('\u{BF5C}', '\u{BF5C}', Break::Lv),
+    ('\u{BF5D}', '\u{BF77}', Break::Lvt), ('\u{BF78}', '\u{BF78}', Break::Lv),
+    ('\u{BF79}', '\u{BF93}', Break::Lvt), ('\u{BF94}', '\u{BF94}', Break::Lv),
+    ('\u{BF95}', '\u{BFAF}', Break::Lvt), ('\u{BFB0}', '\u{BFB0}', Break::Lv),
+    ('\u{BFB1}', '\u{BFCB}', Break::Lvt), ('\u{BFCC}', '\u{BFCC}', Break::Lv),
+    ('\u{BFCD}', '\u{BFE7}', Break::Lvt), ('\u{BFE8}', '\u{BFE8}', Break::Lv),
+    ('\u{BFE9}', '\u{C003}', Break::Lvt), ('\u{C004}', '\u{C004}', Break::Lv),
+    ('\u{C005}', '\u{C01F}', Break::Lvt), ('\u{C020}', '\u{C020}', Break::Lv),
+    ('\u{C021}', '\u{C03B}', Break::Lvt), ('\u{C03C}', '\u{C03C}', Break::Lv),
+    ('\u{C03D}', '\u{C057}', Break::Lvt), ('\u{C058}', '\u{C058}', Break::Lv),
+    ('\u{C059}', '\u{C073}', Break::Lvt), ('\u{C074}', '\u{C074}', Break::Lv),
+    ('\u{C075}', '\u{C08F}', Break::Lvt), ('\u{C090}', '\u{C090}', Break::Lv),
+    ('\u{C091}', '\u{C0AB}', Break::Lvt), ('\u{C0AC}', '\u{C0AC}', Break::Lv),
+    ('\u{C0AD}', '\u{C0C7}', Break::Lvt), ('\u{C0C8}', '\u{C0C8}', Break::Lv),
+    ('\u{C0C9}', '\u{C0E3}', Break::Lvt), ('\u{C0E4}', '\u{C0E4}', Break::Lv),
+    ('\u{C0E5}', '\u{C0FF}', Break::Lvt), ('\u{C100}', '\u{C100}', Break::Lv),
+    ('\u{C101}', '\u{C11B}', Break::Lvt), ('\u{C11C}', '\u{C11C}', Break::Lv),
+    ('\u{C11D}', '\u{C137}', Break::Lvt), ('\u{C138}', '\u{C138}', Break::Lv),
+    ('\u{C139}', '\u{C153}', Break::Lvt), ('\u{C154}', '\u{C154}', Break::Lv),
+    ('\u{C155}', '\u{C16F}', Break::Lvt), ('\u{C170}', '\u{C170}', Break::Lv),
+    ('\u{C171}', '\u{C18B}', Break::Lvt), ('\u{C18C}', '\u{C18C}', Break::Lv),
+    ('\u{C18D}', '\u{C1A7}', Break::Lvt), ('\u{C1A8}', '\u{C1A8}', Break::Lv),
+    ('\u{C1A9}', '\u{C1C3}', Break::Lvt), ('\u{C1C4}', '\u{C1C4}', Break::Lv),
+    ('\u{C1C5}', '\u{C1DF}', Break::Lvt), ('\u{C1E0}', '\u{C1E0}', Break::Lv),
+    ('\u{C1E1}', '\u{C1FB}', Break::Lvt), ('\u{C1FC}', '\u{C1FC}', Break::Lv),
+    ('\u{C1FD}', '\u{C217}', Break::Lvt), ('\u{C218}', '\u{C218}', Break::Lv),
+    ('\u{C219}', '\u{C233}', Break::Lvt), ('\u{C234}', '\u{C234}', Break::Lv),
+    ('\u{C235}', '\u{C24F}', Break::Lvt), ('\u{C250}', '\u{C250}', Break::Lv),
+    ('\u{C251}', '\u{C26B}', Break::Lvt), ('\u{C26C}', '\u{C26C}', Break::Lv),
+    ('\u{C26D}', '\u{C287}', Break::Lvt), ('\u{C288}', '\u{C288}', Break::Lv),
+    ('\u{C289}', '\u{C2A3}', Break::Lvt), ('\u{C2A4}', '\u{C2A4}', Break::Lv),
+    ('\u{C2A5}', '\u{C2BF}', Break::Lvt), ('\u{C2C0}', '\u{C2C0}', Break::Lv),
+    ('\u{C2C1}', '\u{C2DB}', Break::Lvt), ('\u{C2DC}', '\u{C2DC}', Break::Lv),
+    ('\u{C2DD}', '\u{C2F7}', Break::Lvt), ('\u{C2F8}', '\u{C2F8}', Break::Lv),
+    ('\u{C2F9}', '\u{C313}', Break::Lvt), ('\u{C314}', '\u{C314}', Break::Lv),
+    ('\u{C315}', '\u{C32F}', Break::Lvt), ('\u{C330}', '\u{C330}', Break::Lv),
+    ('\u{C331}', '\u{C34B}', Break::Lvt), ('\u{C34C}', '\u{C34C}', Break::Lv),
+    ('\u{C34D}', '\u{C367}', Break::Lvt), ('\u{C368}', '\u{C368}', Break::Lv),
+    ('\u{C369}', '\u{C383}', Break::Lvt), ('\u{C384}', '\u{C384}', Break::Lv),
+    ('\u{C385}', '\u{C39F}', Break::Lvt), ('\u{C3A0}', '\u{C3A0}', Break::Lv),
+    ('\u{C3A1}', '\u{C3BB}', Break::Lvt), ('\u{C3BC}', '\u{C3BC}', Break::Lv),
+    ('\u{C3BD}', '\u{C3D7}', Break::Lvt), ('\u{C3D8}', '\u{C3D8}', Break::Lv),
+    ('\u{C3D9}', '\u{C3F3}', Break::Lvt), ('\u{C3F4}', '\u{C3F4}', Break::Lv),
+    ('\u{C3F5}', '\u{C40F}', Break::Lvt), ('\u{C410}', '\u{C410}', Break::Lv),
+    ('\u{C411}', '\u{C42B}', Break::Lvt), ('\u{C42C}', '\u{C42C}', Break::Lv),
+    ('\u{C42D}', '\u{C447}', Break::Lvt), ('\u{C448}', '\u{C448}', Break::Lv),
+    ('\u{C449}', '\u{C463}', Break::Lvt), ('\u{C464}', '\u{C464}', Break::Lv),
+    ('\u{C465}', '\u{C47F}', Break::Lvt), ('\u{C480}', '\u{C480}', Break::Lv),
+    ('\u{C481}', '\u{C49B}', Break::Lvt), ('\u{C49C}', '\u{C49C}', Break::Lv),
+    ('\u{C49D}', '\u{C4B7}', Break::Lvt), ('\u{C4B8}', '\u{C4B8}', Break::Lv),
+    ('\u{C4B9}', '\u{C4D3}', Break::Lvt), ('\u{C4D4}', '\u{C4D4}', Break::Lv),
+    ('\u{C4D5}', '\u{C4EF}', Break::Lvt), ('\u{C4F0}', '\u{C4F0}', Break::Lv),
+    ('\u{C4F1}', '\u{C50B}', Break::Lvt), ('\u{C50C}', '\u{C50C}', Break::Lv),
+    ('\u{C50D}', '\u{C527}', Break::Lvt), ('\u{C528}', '\u{C528}', Break::Lv),
+    ('\u{C529}', '\u{C543}', Break::Lvt), ('\u{C544}', '\u{C544}', Break::Lv),
+    ('\u{C545}', '\u{C55F}', Break::Lvt), ('\u{C560}', '\u{C560}', Break::Lv),
+    ('\u{C561}', '\u{C57B}', Break::Lvt), ('\u{C57C}', '\u{C57C}', Break::Lv),
+    ('\u{C57D}', '\u{C597}', Break::Lvt), ('\u{C598}', '\u{C598}', Break::Lv),
+    ('\u{C599}', '\u{C5B3}', Break::Lvt), ('\u{C5B4}', '\u{C5B4}', Break::Lv),
+    ('\u{C5B5}', '\u{C5CF}', Break::Lvt), ('\u{C5D0}', '\u{C5D0}', Break::Lv),
+    ('\u{C5D1}', '\u{C5EB}', Break::Lvt), ('\u{C5EC}', '\u{C5EC}', Break::Lv),
+    ('\u{C5ED}', '\u{C607}', Break::Lvt), ('\u{C608}', '\u{C608}', Break::Lv),
+    ('\u{C609}', '\u{C623}', Break::Lvt), ('\u{C624}', '\u{C624}', Break::Lv),
+    ('\u{C625}', '\u{C63F}', Break::Lvt), ('\u{C640}', '\u{C640}', Break::Lv),
+    ('\u{C641}', '\u{C65B}', Break::Lvt), ('\u{C65C}', '\u{C65C}', Break::Lv),
+    ('\u{C65D}', '\u{C677}', Break::Lvt), ('\u{C678}', '\u{C678}', Break::Lv),
+    ('\u{C679}', '\u{C693}', Break::Lvt), ('\u{C694}', '\u{C694}', Break::Lv),
+    ('\u{C695}', '\u{C6AF}', Break::Lvt), ('\u{C6B0}', '\u{C6B0}', Break::Lv),
+    ('\u{C6B1}', '\u{C6CB}', Break::Lvt), ('\u{C6CC}', '\u{C6CC}', Break::Lv),
+    ('\u{C6CD}', '\u{C6E7}', Break::Lvt), ('\u{C6E8}', '\u{C6E8}', Break::Lv),
+    ('\u{C6E9}', '\u{C703}', Break::Lvt), ('\u{C704}', '\u{C704}', Break::Lv),
+    ('\u{C705}', '\u{C71F}', Break::Lvt), ('\u{C720}', '\u{C720}', Break::Lv),
+    ('\u{C721}', '\u{C73B}', Break::Lvt), ('\u{C73C}', '\u{C73C}', Break::Lv),
+    ('\u{C73D}', '\u{C757}', Break::Lvt), ('\u{C758}', '\u{C758}', Break::Lv),
+    ('\u{C759}', '\u{C773}', Break::Lvt), ('\u{C774}', '\u{C774}', Break::Lv),
+    ('\u{C775}', '\u{C78F}', Break::Lvt), ('\u{C790}', '\u{C790}', Break::Lv),
+    ('\u{C791}', '\u{C7AB}', Break::Lvt), ('\u{C7AC}', '\u{C7AC}', Break::Lv),
+    ('\u{C7AD}', '\u{C7C7}', Break::Lvt), ('\u{C7C8}', '\u{C7C8}', Break::Lv),
+    ('\u{C7C9}', '\u{C7E3}', Break::Lvt), ('\u{C7E4}', '\u{C7E4}', Break::Lv),
+    ('\u{C7E5}', '\u{C7FF}', Break::Lvt), ('\u{C800}', '\u{C800}', Break::Lv),
+    ('\u{C801}', '\u{C81B}', Break::Lvt), ('\u{C81C}', '\u{C81C}', Break::Lv),
+    ('\u{C81D}', '\u{C837}', Break::Lvt), ('\u{C838}', '\u{C838}', Break::Lv),
+    ('\u{C839}', '\u{C853}', Break::Lvt), ('\u{C854}', '\u{C854}', Break::Lv),
+    ('\u{C855}', '\u{C86F}', Break::Lvt), ('\u{C870}', '\u{C870}', Break::Lv),
+    ('\u{C871}', '\u{C88B}', Break::Lvt), ('\u{C88C}', '\u{C88C}', Break::Lv),
+    ('\u{C88D}', '\u{C8A7}', Break::Lvt), ('\u{C8A8}', '\u{C8A8}', Break::Lv),
+    ('\u{C8A9}', '\u{C8C3}', Break::Lvt), ('\u{C8C4}', '\u{C8C4}', Break::Lv),
+    ('\u{C8C5}', '\u{C8DF}', Break::Lvt), ('\u{C8E0}', '\u{C8E0}', Break::Lv),
+    ('\u{C8E1}', '\u{C8FB}', Break::Lvt), ('\u{C8FC}', '\u{C8FC}', Break::Lv),
+    ('\u{C8FD}', '\u{C917}', Break::Lvt), ('\u{C918}', '\u{C918}', Break::Lv),
+    ('\u{C919}', '\u{C933}', Break::Lvt), ('\u{C934}', '\u{C934}', Break::Lv),
+    ('\u{C935}', '\u{C94F}', Break::Lvt), ('\u{C950}', '\u{C950}', Break::Lv),
+    ('\u{C951}', '\u{C96B}', Break::Lvt), ('\u{C96C}', '\u{C96C}', Break::Lv),
+    ('\u{C96D}', '\u{C987}', Break::Lvt), ('\u{C988}', '\u{C988}', Break::Lv),
+    ('\u{C989}', '\u{C9A3}', Break::Lvt), ('\u{C9A4}', '\u{C9A4}', Break::Lv),
+    ('\u{C9A5}', '\u{C9BF}', Break::Lvt), ('\u{C9C0}', '\u{C9C0}', Break::Lv),
+    ('\u{C9C1}', '\u{C9DB}', Break::Lvt), ('\u{C9DC}', '\u{C9DC}', Break::Lv),
+    ('\u{C9DD}', '\u{C9F7}', Break::Lvt), ('\u{C9F8}', '\u{C9F8}', Break::Lv),
+    ('\u{C9F9}', '\u{CA13}', Break::Lvt), ('\u{CA14}', '\u{CA14}', Break::Lv),
+    ('\u{CA15}', '\u{CA2F}', Break::Lvt), ('\u{CA30}', '\u{CA30}', Break::Lv),
+    ('\u{CA31}', '\u{CA4B}', Break::Lvt), ('\u{CA4C}', '\u{CA4C}', Break::Lv),
+    ('\u{CA4D}', '\u{CA67}', Break::Lvt), ('\u{CA68}', '\u{CA68}', Break::Lv),
+    ('\u{CA69}', '\u{CA83}', Break::Lvt), ('\u{CA84}', '\u{CA84}', Break::Lv),
+    ('\u{CA85}', '\u{CA9F}', Break::Lvt), ('\u{CAA0}', '\u{CAA0}', Break::Lv),
+    ('\u{CAA1}', '\u{CABB}', Break::Lvt), ('\u{CABC}', '\u{CABC}', Break::Lv),
+    ('\u{CABD}', '\u{CAD7}', Break::Lvt), ('\u{CAD8}', '\u{CAD8}', Break::Lv),
+    ('\u{CAD9}', '\u{CAF3}', Break::Lvt), ('\u{CAF4}', '\u{CAF4}', Break::Lv),
+    ('\u{CAF5}', '\u{CB0F}', Break::Lvt), ('\u{CB10}', '\u{CB10}', Break::Lv),
+    ('\u{CB11}', '\u{CB2B}', Break::Lvt), ('\u{CB2C}', '\u{CB2C}', Break::Lv),
+    ('\u{CB2D}', '\u{CB47}', Break::Lvt), ('\u{CB48}', '\u{CB48}', Break::Lv),
+    ('\u{CB49}', '\u{CB63}', Break::Lvt), ('\u{CB64}', '\u{CB64}', Break::Lv),
+    ('\u{CB65}', '\u{CB7F}', Break::Lvt), ('\u{CB80}', '\u{CB80}', Break::Lv),
+    ('\u{CB81}', '\u{CB9B}', Break::Lvt), ('\u{CB9C}', '\u{CB9C}', Break::Lv),
+    ('\u{CB9D}', '\u{CBB7}', Break::Lvt), ('\u{CBB8}', '\u{CBB8}', Break::Lv),
+    ('\u{CBB9}', '\u{CBD3}', Break::Lvt), ('\u{CBD4}', '\u{CBD4}', Break::Lv),
+    ('\u{CBD5}', '\u{CBEF}', Break::Lvt), ('\u{CBF0}', '\u{CBF0}', Break::Lv),
+    ('\u{CBF1}', '\u{CC0B}', Break::Lvt), ('\u{CC0C}', '\u{CC0C}', Break::Lv),
+    ('\u{CC0D}', '\u{CC27}', Break::Lvt), ('\u{CC28}', '\u{CC28}', Break::Lv),
+    ('\u{CC29}', '\u{CC43}', Break::Lvt), ('\u{CC44}', '\u{CC44}', Break::Lv),
+    ('\u{CC45}', '\u{CC5F}', Break::Lvt), ('\u{CC60}', '\u{CC60}', Break::Lv),
+    ('\u{CC61}', '\u{CC7B}', Break::Lvt), ('\u{CC7C}', '\u{CC7C}', Break::Lv),
+    ('\u{CC7D}', '\u{CC97}', Break::Lvt), ('\u{CC98}', '\u{CC98}', Break::Lv),
+    ('\u{CC99}', '\u{CCB3}', Break::Lvt), ('\u{CCB4}', '\u{CCB4}', Break::Lv),
+    ('\u{CCB5}', '\u{CCCF}', Break::Lvt), ('\u{CCD0}', '\u{CCD0}', Break::Lv),
+    ('\u{CCD1}', '\u{CCEB}', Break::Lvt), ('\u{CCEC}', '\u{CCEC}', Break::Lv),
+    ('\u{CCED}', '\u{CD07}', Break::Lvt), ('\u{CD08}', '\u{CD08}', Break::Lv),
+    ('\u{CD09}', '\u{CD23}', Break::Lvt), ('\u{CD24}', '\u{CD24}', Break::Lv),
+    ('\u{CD25}', '\u{CD3F}', Break::Lvt), ('\u{CD40}', '\u{CD40}', Break::Lv),
+    ('\u{CD41}', '\u{CD5B}', Break::Lvt), ('\u{CD5C}', '\u{CD5C}', Break::Lv),
+    ('\u{CD5D}', '\u{CD77}', Break::Lvt), ('\u{CD78}', '\u{CD78}', Break::Lv),
+    ('\u{CD79}', '\u{CD93}', Break::Lvt), ('\u{CD94}', '\u{CD94}', Break::Lv),
+    ('\u{CD95}', '\u{CDAF}', Break::Lvt), ('\u{CDB0}', '\u{CDB0}', Break::Lv),
+    ('\u{CDB1}', '\u{CDCB}', Break::Lvt), ('\u{CDCC}', '\u{CDCC}', Break::Lv),
+    ('\u{CDCD}', '\u{CDE7}', Break::Lvt), ('\u{CDE8}', '\u{CDE8}', Break::Lv),
+    ('\u{CDE9}', '\u{CE03}', Break::Lvt), ('\u{CE04}', '\u{CE04}', Break::Lv),
+    ('\u{CE05}', '\u{CE1F}', Break::Lvt), ('\u{CE20}', '\u{CE20}', Break::Lv),
+    ('\u{CE21}', '\u{CE3B}', Break::Lvt), ('\u{CE3C}', '\u{CE3C}', Break::Lv),
+    ('\u{CE3D}', '\u{CE57}', Break::Lvt), ('\u{CE58}', '\u{CE58}', Break::Lv),
+    ('\u{CE59}', '\u{CE73}', Break::Lvt), ('\u{CE74}', '\u{CE74}', Break::Lv),
+    ('\u{CE75}', '\u{CE8F}', Break::Lvt), ('\u{CE90}', '\u{CE90}', Break::Lv),
+    ('\u{CE91}', '\u{CEAB}', Break::Lvt), ('\u{CEAC}', '\u{CEAC}', Break::Lv),
+    ('\u{CEAD}', '\u{CEC7}', Break::Lvt), ('\u{CEC8}', '\u{CEC8}', Break::Lv),
+    ('\u{CEC9}', '\u{CEE3}', Break::Lvt), ('\u{CEE4}', '\u{CEE4}', Break::Lv),
+    ('\u{CEE5}', '\u{CEFF}', Break::Lvt), ('\u{CF00}', '\u{CF00}', Break::Lv),
+    ('\u{CF01}', '\u{CF1B}', Break::Lvt), ('\u{CF1C}', '\u{CF1C}', Break::Lv),
+    ('\u{CF1D}', '\u{CF37}', Break::Lvt), ('\u{CF38}', '\u{CF38}', Break::Lv),
+    ('\u{CF39}', '\u{CF53}', Break::Lvt), ('\u{CF54}', '\u{CF54}', Break::Lv),
+    ('\u{CF55}', '\u{CF6F}', Break::Lvt), ('\u{CF70}', '\u{CF70}', Break::Lv),
+    ('\u{CF71}', '\u{CF8B}', Break::Lvt), ('\u{CF8C}', '\u{CF8C}', Break::Lv),
+    ('\u{CF8D}', '\u{CFA7}', Break::Lvt), ('\u{CFA8}', '\u{CFA8}', Break::Lv),
+    ('\u{CFA9}', '\u{CFC3}', Break::Lvt), ('\u{CFC4}', '\u{CFC4}', Break::Lv),
+    ('\u{CFC5}', '\u{CFDF}', Break::Lvt), ('\u{CFE0}', '\u{CFE0}', Break::Lv),
+    ('\u{CFE1}', '\u{CFFB}', Break::Lvt), ('\u{CFFC}', '\u{CFFC}', Break::Lv),
+    ('\u{CFFD}', '\u{D017}', Break::Lvt), ('\u{D018}', '\u{D018}', Break::Lv),
+    ('\u{D019}', '\u{D033}', Break::Lvt), ('\u{D034}', '\u{D034}', Break::Lv),
+    ('\u{D035}', '\u{D04F}', Break::Lvt), ('\u{D050}', '\u{D050}', Break::Lv),
+    ('\u{D051}', '\u{D06B}', Break::Lvt), ('\u{D06C}', '\u{D06C}', Break::Lv),
+    ('\u{D06D}', '\u{D087}', Break::Lvt), ('\u{D088}', '\u{D088}', Break::Lv),
+    ('\u{D089}', '\u{D0A3}', Break::Lvt), ('\u{D0A4}', '\u{D0A4}', Break::Lv),
+    ('\u{D0A5}', '\u{D0BF}', Break::Lvt), ('\u{D0C0}', '\u{D0C0}', Break::Lv),
+    ('\u{D0C1}', '\u{D0DB}', Break::Lvt), ('\u{D0DC}', '\u{D0DC}', Break::Lv),
+    ('\u{D0DD}', '\u{D0F7}', Break::Lvt), ('\u{D0F8}', '\u{D0F8}', Break::Lv),
+    ('\u{D0F9}', '\u{D113}', Break::Lvt), ('\u{D114}', '\u{D114}', Break::Lv),
+    ('\u{D115}', '\u{D12F}', Break::Lvt), ('\u{D130}', '\u{D130}', Break::Lv),
+    ('\u{D131}', '\u{D14B}', Break::Lvt), ('\u{D14C}', '\u{D14C}', Break::Lv),
+    ('\u{D14D}', '\u{D167}', Break::Lvt), ('\u{D168}', '\u{D168}', Break::Lv),
+    ('\u{D169}', '\u{D183}', Break::Lvt), ('\u{D184}', '\u{D184}', Break::Lv),
+    ('\u{D185}', '\u{D19F}', Break::Lvt), ('\u{D1A0}', '\u{D1A0}', Break::Lv),
+    ('\u{D1A1}', '\u{D1BB}', Break::Lvt), ('\u{D1BC}', '\u{D1BC}', Break::Lv),
+    ('\u{D1BD}', '\u{D1D7}', Break::Lvt), ('\u{D1D8}', '\u{D1D8}', Break::Lv),
+    ('\u{D1D9}', '\u{D1F3}', Break::Lvt), ('\u{D1F4}', '\u{D1F4}', Break::Lv),
+    ('\u{D1F5}', '\u{D20F}', Break::Lvt), ('\u{D210}', '\u{D210}', Break::Lv),
+    ('\u{D211}', '\u{D22B}', Break::Lvt), ('\u{D22C}', '\u{D22C}', Break::Lv),
+    ('\u{D22D}', '\u{D247}', Break::Lvt), ('\u{D248}', '\u{D248}', Break::Lv),
+    ('\u{D249}', '\u{D263}', Break::Lvt), ('\u{D264}', '\u{D264}', Break::Lv),
+    ('\u{D265}', '\u{D27F}', Break::Lvt), ('\u{D280}', '\u{D280}', Break::Lv),
+    ('\u{D281}', '\u{D29B}', Break::Lvt), ('\u{D29C}', '\u{D29C}', Break::Lv),
+    ('\u{D29D}', '\u{D2B7}', Break::Lvt), ('\u{D2B8}', '\u{D2B8}', Break::Lv),
+    ('\u{D2B9}', '\u{D2D3}', Break::Lvt), ('\u{D2D4}', '\u{D2D4}', Break::Lv),
+    ('\u{D2D5}', '\u{D2EF}', Break::Lvt), ('\u{D2F0}', '\u{D2F0}', Break::Lv),
+    ('\u{D2F1}', '\u{D30B}', Break::Lvt), ('\u{D30C}', '\u{D30C}', Break::Lv),
+    ('\u{D30D}', '\u{D327}', Break::Lvt), ('\u{D328}', '\u{D328}', Break::Lv),
+    ('\u{D329}', '\u{D343}', Break::Lvt), ('\u{D344}', '\u{D344}', Break::Lv),
+    ('\u{D345}', '\u{D35F}', Break::Lvt), ('\u{D360}', '\u{D360}', Break::Lv),
+    ('\u{D361}', '\u{D37B}', Break::Lvt), ('\u{D37C}', '\u{D37C}', Break::Lv),
+    ('\u{D37D}', '\u{D397}', Break::Lvt), ('\u{D398}', '\u{D398}', Break::Lv),
+    ('\u{D399}', '\u{D3B3}', Break::Lvt), ('\u{D3B4}', '\u{D3B4}', Break::Lv),
+    ('\u{D3B5}', '\u{D3CF}', Break::Lvt), ('\u{D3D0}', '\u{D3D0}', Break::Lv),
+    ('\u{D3D1}', '\u{D3EB}', Break::Lvt), ('\u{D3EC}', '\u{D3EC}', Break::Lv),
+    ('\u{D3ED}', '\u{D407}', Break::Lvt), ('\u{D408}', '\u{D408}', Break::Lv),
+    ('\u{D409}', '\u{D423}', Break::Lvt), ('\u{D424}', '\u{D424}', Break::Lv),
+    ('\u{D425}', '\u{D43F}', Break::Lvt), ('\u{D440}', '\u{D440}', Break::Lv),
+    ('\u{D441}', '\u{D45B}', Break::Lvt), ('\u{D45C}', '\u{D45C}', Break::Lv),
+    ('\u{D45D}', '\u{D477}', Break::Lvt), ('\u{D478}', '\u{D478}', Break::Lv),
+    ('\u{D479}', '\u{D493}', Break::Lvt), ('\u{D494}', '\u{D494}', Break::Lv),
+    ('\u{D495}', '\u{D4AF}', Break::Lvt), ('\u{D4B0}', '\u{D4B0}', Break::Lv),
+    ('\u{D4B1}', '\u{D4CB}', Break::Lvt), ('\u{D4CC}', '\u{D4CC}', Break::Lv),
+    ('\u{D4CD}', '\u{D4E7}', Break::Lvt), ('\u{D4E8}', '\u{D4E8}', Break::Lv),
+    ('\u{D4E9}', '\u{D503}', Break::Lvt), ('\u{D504}', '\u{D504}', Break::Lv),
+    ('\u{D505}', '\u{D51F}', Break::Lvt), ('\u{D520}', '\u{D520}', Break::Lv),
+    ('\u{D521}', '\u{D53B}', Break::Lvt), ('\u{D53C}', '\u{D53C}', Break::Lv),
+    ('\u{D53D}', '\u{D557}', Break::Lvt), ('\u{D558}', '\u{D558}', Break::Lv),
+    ('\u{D559}', '\u{D573}', Break::Lvt), ('\u{D574}', '\u{D574}', Break::Lv),
+    ('\u{D575}', '\u{D58F}', Break::Lvt), ('\u{D590}', '\u{D590}', Break::Lv),
+    ('\u{D591}', '\u{D5AB}', Break::Lvt), ('\u{D5AC}', '\u{D5AC}', Break::Lv),
+    ('\u{D5AD}', '\u{D5C7}', Break::Lvt), ('\u{D5C8}', '\u{D5C8}', Break::Lv),
+    ('\u{D5C9}', '\u{D5E3}', Break::Lvt), ('\u{D5E4}', '\u{D5E4}', Break::Lv),
+    ('\u{D5E5}', '\u{D5FF}', Break::Lvt), ('\u{D600}', '\u{D600}', Break::Lv),
+    ('\u{D601}', '\u{D61B}', Break::Lvt), ('\u{D61C}', '\u{D61C}', Break::Lv),
+    ('\u{D61D}', '\u{D637}', Break::Lvt), ('\u{D638}', '\u{D638}', Break::Lv),
+    ('\u{D639}', '\u{D653}', Break::Lvt), ('\u{D654}', '\u{D654}', Break::Lv),
+    ('\u{D655}', '\u{D66F}', Break::Lvt), ('\u{D670}', '\u{D670}', Break::Lv),
+    ('\u{D671}', '\u{D68B}', Break::Lvt), ('\u{D68C}', '\u{D68C}', Break::Lv),
+    ('\u{D68D}', '\u{D6A7}', Break::Lvt), ('\u{D6A8}', '\u{D6A8}', Break::Lv),
+    ('\u{D6A9}', '\u{D6C3}', Break::Lvt), ('\u{D6C4}', '\u{D6C4}', Break::Lv),
+    ('\u{D6C5}', '\u{D6DF}', Break::Lvt), ('\u{D6E0}', '\u{D6E0}', Break::Lv),
+    ('\u{D6E1}', '\u{D6FB}', Break::Lvt), ('\u{D6FC}', '\u{D6FC}', Break::Lv),
+    ('\u{D6FD}', '\u{D717}', Break::Lvt), ('\u{D718}', '\u{D718}', Break::Lv),
+    ('\u{D719}', '\u{D733}', Break::Lvt), ('\u{D734}', '\u{D734}', Break::Lv),
+    ('\u{D735}', '\u{D74F}', Break::Lvt), ('\u{D750}', '\u{D750}', Break::Lv),
+    ('\u{D751}', '\u{D76B}', Break::Lvt), ('\u{D76C}', '\u{D76C}', Break::Lv),
+    ('\u{D76D}', '\u{D787}', Break::Lvt), ('\u{D788}', '\u{D788}', Break::Lv),
+    ('\u{D789}', '\u{D7A3}', Break::Lvt), ('\u{D7B0}', '\u{D7C6}', Break::V),
+    ('\u{D7CB}', '\u{D7FB}', Break::T), ('\u{FB1E}', '\u{FB1E}', Break::ConjunctExtend),
+    ('\u{FE00}', '\u{FE0F}', Break::ConjunctExtend), ('\u{FE20}', '\u{FE2F}', Break::ConjunctExtend),
+    ('\u{FEFF}', '\u{FEFF}', Break::Control), ('\u{FF9E}', '\u{FF9F}', Break::ConjunctExtend),
+    ('\u{FFF0}', '\u{FFFB}', Break::Control), ('\u{101FD}', '\u{101FD}', Break::ConjunctExtend),
+    ('\u{102E0}', '\u{102E0}', Break::ConjunctExtend), ('\u{10376}', '\u{1037A}', Break::ConjunctExtend),
+    ('\u{10A00}', '\u{10A00}', Break::Consonant), ('\u{10A01}', '\u{10A03}', Break::ConjunctExtend),
+    ('\u{10A05}', '\u{10A06}', Break::ConjunctExtend), ('\u{10A0C}', '\u{10A0F}', Break::ConjunctExtend),
+    ('\u{10A10}', '\u{10A13}', Break::Consonant), ('\u{10A15}', '\u{10A17}', Break::Consonant),
+    ('\u{10A19}', '\u{10A35}', Break::Consonant), ('\u{10A38}', '\u{10A3A}', Break::ConjunctExtend),
+    ('\u{10A3F}', '\u{10A3F}', Break::Linker), ('\u{10AE5}', '\u{10AE6}', Break::ConjunctExtend),
+    ('\u{10D24}', '\u{10D27}', Break::ConjunctExtend), ('\u{10D69}', '\u{10D6D}', Break::ConjunctExtend),
+    ('\u{10EAB}', '\u{10EAC}', Break::ConjunctExtend), ('\u{10EFA}', '\u{10EFF}', Break::ConjunctExtend),
+    ('\u{10F46}', '\u{10F50}', Break::ConjunctExtend), ('\u{10F82}', '\u{10F85}', Break::ConjunctExtend),
+    ('\u{11000}', '\u{11000}', Break::SpacingMark), ('\u{11001}', '\u{11001}', Break::ConjunctExtend),
+    ('\u{11002}', '\u{11002}', Break::SpacingMark), ('\u{11038}', '\u{11046}', Break::ConjunctExtend),
+    ('\u{11070}', '\u{11070}', Break::ConjunctExtend), ('\u{11073}', '\u{11074}', Break::ConjunctExtend),
+    ('\u{1107F}', '\u{11081}', Break::ConjunctExtend), ('\u{11082}', '\u{11082}', Break::SpacingMark),
+    ('\u{110B0}', '\u{110B2}', Break::SpacingMark), ('\u{110B3}', '\u{110B6}', Break::ConjunctExtend),
+    ('\u{110B7}', '\u{110B8}', Break::SpacingMark), ('\u{110B9}', '\u{110BA}', Break::ConjunctExtend),
+    ('\u{110BD}', '\u{110BD}', Break::Prepend), ('\u{110C2}', '\u{110C2}', Break::ConjunctExtend),
+    ('\u{110CD}', '\u{110CD}', Break::Prepend), ('\u{11100}', '\u{11102}', Break::ConjunctExtend),
+    ('\u{11103}', '\u{11126}', Break::Consonant), ('\u{11127}', '\u{1112B}', Break::ConjunctExtend),
+    ('\u{1112C}', '\u{1112C}', Break::SpacingMark), ('\u{1112D}', '\u{11132}', Break::ConjunctExtend),
+    ('\u{11133}', '\u{11133}', Break::Linker), ('\u{11134}', '\u{11134}', Break::ConjunctExtend),
+    ('\u{11144}', '\u{11144}', Break::Consonant), ('\u{11145}', '\u{11146}', Break::SpacingMark),
+    ('\u{11147}', '\u{11147}', Break::Consonant), ('\u{11173}', '\u{11173}', Break::ConjunctExtend),
+    ('\u{11180}', '\u{11181}', Break::ConjunctExtend), ('\u{11182}', '\u{11182}', Break::SpacingMark),
+    ('\u{111B3}', '\u{111B5}', Break::SpacingMark), ('\u{111B6}', '\u{111BE}', Break::ConjunctExtend),
+    ('\u{111BF}', '\u{111BF}', Break::SpacingMark), ('\u{111C0}', '\u{111C0}', Break::ConjunctExtend),
+    ('\u{111C2}', '\u{111C3}', Break::Prepend), ('\u{111C9}', '\u{111CC}', Break::ConjunctExtend),
+    ('\u{111CE}', '\u{111CE}', Break::SpacingMark), ('\u{111CF}', '\u{111CF}', Break::ConjunctExtend),
+    ('\u{1122C}', '\u{1122E}', Break::SpacingMark), ('\u{1122F}', '\u{11231}', Break::ConjunctExtend),
+    ('\u{11232}', '\u{11233}', Break::SpacingMark), ('\u{11234}', '\u{11237}', Break::ConjunctExtend),
+    ('\u{1123E}', '\u{1123E}', Break::ConjunctExtend), ('\u{11241}', '\u{11241}', Break::ConjunctExtend),
+    ('\u{112DF}', '\u{112DF}', Break::ConjunctExtend), ('\u{112E0}', '\u{112E2}', Break::SpacingMark),
+    ('\u{112E3}', '\u{112EA}', Break::ConjunctExtend), ('\u{11300}', '\u{11301}', Break::ConjunctExtend),
+    ('\u{11302}', '\u{11303}', Break::SpacingMark), ('\u{1133B}', '\u{1133C}', Break::ConjunctExtend),
+    ('\u{1133E}', '\u{1133E}', Break::ConjunctExtend), ('\u{1133F}', '\u{1133F}', Break::SpacingMark),
+    ('\u{11340}', '\u{11340}', Break::ConjunctExtend), ('\u{11341}', '\u{11344}', Break::SpacingMark),
+    ('\u{11347}', '\u{11348}', Break::SpacingMark), ('\u{1134B}', '\u{1134C}', Break::SpacingMark),
+    ('\u{1134D}', '\u{1134D}', Break::ConjunctExtend), ('\u{11357}', '\u{11357}', Break::ConjunctExtend),
+    ('\u{11362}', '\u{11363}', Break::SpacingMark), ('\u{11366}', '\u{1136C}', Break::ConjunctExtend),
+    ('\u{11370}', '\u{11374}', Break::ConjunctExtend), ('\u{11380}', '\u{11389}', Break::Consonant),
+    ('\u{1138B}', '\u{1138B}', Break::Consonant), ('\u{1138E}', '\u{1138E}', Break::Consonant),
+    ('\u{11390}', '\u{113B5}', Break::Consonant), ('\u{113B8}', '\u{113B8}', Break::ConjunctExtend),
+    ('\u{113B9}', '\u{113BA}', Break::SpacingMark), ('\u{113BB}', '\u{113C0}', Break::ConjunctExtend),
+    ('\u{113C2}', '\u{113C2}', Break::ConjunctExtend), ('\u{113C5}', '\u{113C5}', Break::ConjunctExtend),
+    ('\u{113C7}', '\u{113C9}', Break::ConjunctExtend), ('\u{113CA}', '\u{113CA}', Break::SpacingMark),
+    ('\u{113CC}', '\u{113CD}', Break::SpacingMark), ('\u{113CE}', '\u{113CF}', Break::ConjunctExtend),
+    ('\u{113D0}', '\u{113D0}', Break::Linker), ('\u{113D1}', '\u{113D1}', Break::Prepend),
+    ('\u{113D2}', '\u{113D2}', Break::ConjunctExtend), ('\u{113E1}', '\u{113E2}', Break::ConjunctExtend),
+    ('\u{11435}', '\u{11437}', Break::SpacingMark), ('\u{11438}', '\u{1143F}', Break::ConjunctExtend),
+    ('\u{11440}', '\u{11441}', Break::SpacingMark), ('\u{11442}', '\u{11444}', Break::ConjunctExtend),
+    ('\u{11445}', '\u{11445}', Break::SpacingMark), ('\u{11446}', '\u{11446}', Break::ConjunctExtend),
+    ('\u{1145E}', '\u{1145E}', Break::ConjunctExtend), ('\u{114B0}', '\u{114B0}', Break::ConjunctExtend),
+    ('\u{114B1}', '\u{114B2}', Break::SpacingMark), ('\u{114B3}', '\u{114B8}', Break::ConjunctExtend),
+    ('\u{114B9}', '\u{114B9}', Break::SpacingMark), ('\u{114BA}', '\u{114BA}', Break::ConjunctExtend),
+    ('\u{114BB}', '\u{114BC}', Break::SpacingMark), ('\u{114BD}', '\u{114BD}', Break::ConjunctExtend),
+    ('\u{114BE}', '\u{114BE}', Break::SpacingMark), ('\u{114BF}', '\u{114C0}', Break::ConjunctExtend),
+    ('\u{114C1}', '\u{114C1}', Break::SpacingMark), ('\u{114C2}', '\u{114C3}', Break::ConjunctExtend),
+    ('\u{115AF}', '\u{115AF}', Break::ConjunctExtend), ('\u{115B0}', '\u{115B1}', Break::SpacingMark),
+    ('\u{115B2}', '\u{115B5}', Break::ConjunctExtend), ('\u{115B8}', '\u{115BB}', Break::SpacingMark),
+    ('\u{115BC}', '\u{115BD}', Break::ConjunctExtend), ('\u{115BE}', '\u{115BE}', Break::SpacingMark),
+    ('\u{115BF}', '\u{115C0}', Break::ConjunctExtend), ('\u{115DC}', '\u{115DD}', Break::ConjunctExtend),
+    ('\u{11630}', '\u{11632}', Break::SpacingMark), ('\u{11633}', '\u{1163A}', Break::ConjunctExtend),
+    ('\u{1163B}', '\u{1163C}', Break::SpacingMark), ('\u{1163D}', '\u{1163D}', Break::ConjunctExtend),
+    ('\u{1163E}', '\u{1163E}', Break::SpacingMark), ('\u{1163F}', '\u{11640}', Break::ConjunctExtend),
+    ('\u{116AB}', '\u{116AB}', Break::ConjunctExtend), ('\u{116AC}', '\u{116AC}', Break::SpacingMark),
+    ('\u{116AD}', '\u{116AD}', Break::ConjunctExtend), ('\u{116AE}', '\u{116AF}', Break::SpacingMark),
+    ('\u{116B0}', '\u{116B7}', Break::ConjunctExtend), ('\u{1171D}', '\u{1171D}', Break::ConjunctExtend),
+    ('\u{1171E}', '\u{1171E}', Break::SpacingMark), ('\u{1171F}', '\u{1171F}', Break::ConjunctExtend),
+    ('\u{11722}', '\u{11725}', Break::ConjunctExtend), ('\u{11726}', '\u{11726}', Break::SpacingMark),
+    ('\u{11727}', '\u{1172B}', Break::ConjunctExtend), ('\u{1182C}', '\u{1182E}', Break::SpacingMark),
+    ('\u{1182F}', '\u{11837}', Break::ConjunctExtend), ('\u{11838}', '\u{11838}', Break::SpacingMark),
+    ('\u{11839}', '\u{1183A}', Break::ConjunctExtend), ('\u{11900}', '\u{11906}', Break::Consonant),
+    ('\u{11909}', '\u{11909}', Break::Consonant), ('\u{1190C}', '\u{11913}', Break::Consonant),
+    ('\u{11915}', '\u{11916}', Break::Consonant), ('\u{11918}', '\u{1192F}', Break::Consonant),
+    ('\u{11930}', '\u{11930}', Break::ConjunctExtend), ('\u{11931}', '\u{11935}', Break::SpacingMark),
+    ('\u{11937}', '\u{11938}', Break::SpacingMark), ('\u{1193B}', '\u{1193D}', Break::ConjunctExtend),
+    ('\u{1193E}', '\u{1193E}', Break::Linker), ('\u{1193F}', '\u{1193F}', Break::Prepend),
+    ('\u{11940}', '\u{11940}', Break::SpacingMark), ('\u{11941}', '\u{11941}', Break::Prepend),
+    ('\u{11942}', '\u{11942}', Break::SpacingMark), ('\u{11943}', '\u{11943}', Break::ConjunctExtend),
+    ('\u{119D1}', '\u{119D3}', Break::SpacingMark), ('\u{119D4}', '\u{119D7}', Break::ConjunctExtend),
+    ('\u{119DA}', '\u{119DB}', Break::ConjunctExtend), ('\u{119DC}', '\u{119DF}', Break::SpacingMark),
+    ('\u{119E0}', '\u{119E0}', Break::ConjunctExtend), ('\u{119E4}', '\u{119E4}', Break::SpacingMark),
+    ('\u{11A00}', '\u{11A00}', Break::Consonant), ('\u{11A01}', '\u{11A0A}', Break::ConjunctExtend),
+    ('\u{11A0B}', '\u{11A32}', Break::Consonant), ('\u{11A33}', '\u{11A38}', Break::ConjunctExtend),
+    ('\u{11A39}', '\u{11A39}', Break::SpacingMark), ('\u{11A3B}', '\u{11A3E}', Break::ConjunctExtend),
+    ('\u{11A47}', '\u{11A47}', Break::Linker), ('\u{11A50}', '\u{11A50}', Break::Consonant),
+    ('\u{11A51}', '\u{11A56}', Break::ConjunctExtend), ('\u{11A57}', '\u{11A58}', Break::SpacingMark),
+    ('\u{11A59}', '\u{11A5B}', Break::ConjunctExtend), ('\u{11A5C}', '\u{11A83}', Break::Consonant),
+    ('\u{11A84}', '\u{11A89}', Break::Prepend), ('\u{11A8A}', '\u{11A96}', Break::ConjunctExtend),
+    ('\u{11A97}', '\u{11A97}', Break::SpacingMark), ('\u{11A98}', '\u{11A98}', Break::ConjunctExtend),
+    ('\u{11A99}', '\u{11A99}', Break::Linker), ('\u{11B60}', '\u{11B60}', Break::ConjunctExtend),
+    ('\u{11B61}', '\u{11B61}', Break::SpacingMark), ('\u{11B62}', '\u{11B64}', Break::ConjunctExtend),
+    ('\u{11B65}', '\u{11B65}', Break::SpacingMark), ('\u{11B66}', '\u{11B66}', Break::ConjunctExtend),
+    ('\u{11B67}', '\u{11B67}', Break::SpacingMark), ('\u{11C2F}', '\u{11C2F}', Break::SpacingMark),
+    ('\u{11C30}', '\u{11C36}', Break::ConjunctExtend), ('\u{11C38}', '\u{11C3D}', Break::ConjunctExtend),
+    ('\u{11C3E}', '\u{11C3E}', Break::SpacingMark), ('\u{11C3F}', '\u{11C3F}', Break::ConjunctExtend),
+    ('\u{11C92}', '\u{11CA7}', Break::ConjunctExtend), ('\u{11CA9}', '\u{11CA9}', Break::SpacingMark),
+    ('\u{11CAA}', '\u{11CB0}', Break::ConjunctExtend), ('\u{11CB1}', '\u{11CB1}', Break::SpacingMark),
+    ('\u{11CB2}', '\u{11CB3}', Break::ConjunctExtend), ('\u{11CB4}', '\u{11CB4}', Break::SpacingMark),
+    ('\u{11CB5}', '\u{11CB6}', Break::ConjunctExtend), ('\u{11D31}', '\u{11D36}', Break::ConjunctExtend),
+    ('\u{11D3A}', '\u{11D3A}', Break::ConjunctExtend), ('\u{11D3C}', '\u{11D3D}', Break::ConjunctExtend),
+    ('\u{11D3F}', '\u{11D45}', Break::ConjunctExtend), ('\u{11D46}', '\u{11D46}', Break::Prepend),
+    ('\u{11D47}', '\u{11D47}', Break::ConjunctExtend), ('\u{11D8A}', '\u{11D8E}', Break::SpacingMark),
+    ('\u{11D90}', '\u{11D91}', Break::ConjunctExtend), ('\u{11D93}', '\u{11D94}', Break::SpacingMark),
+    ('\u{11D95}', '\u{11D95}', Break::ConjunctExtend), ('\u{11D96}', '\u{11D96}', Break::SpacingMark),
+    ('\u{11D97}', '\u{11D97}', Break::ConjunctExtend), ('\u{11EF3}', '\u{11EF4}', Break::ConjunctExtend),
+    ('\u{11EF5}', '\u{11EF6}', Break::SpacingMark), ('\u{11F00}', '\u{11F01}', Break::ConjunctExtend),
+    ('\u{11F02}', '\u{11F02}', Break::Prepend), ('\u{11F03}', '\u{11F03}', Break::SpacingMark),
+    ('\u{11F04}', '\u{11F10}', Break::Consonant), ('\u{11F12}', '\u{11F33}', Break::Consonant),
+    ('\u{11F34}', '\u{11F35}', Break::SpacingMark), ('\u{11F36}', '\u{11F3A}', Break::ConjunctExtend),
+    ('\u{11F3E}', '\u{11F3F}', Break::SpacingMark), ('\u{11F40}', '\u{11F41}', Break::ConjunctExtend),
+    ('\u{11F42}', '\u{11F42}', Break::Linker), ('\u{11F5A}', '\u{11F5A}', Break::ConjunctExtend),
+    ('\u{13430}', '\u{1343F}', Break::Control), ('\u{13440}', '\u{13440}', Break::ConjunctExtend),
+    ('\u{13447}', '\u{13455}', Break::ConjunctExtend), ('\u{1611E}', '\u{16129}', Break::ConjunctExtend),
+    ('\u{1612A}', '\u{1612C}', Break::SpacingMark), ('\u{1612D}', '\u{1612F}', Break::ConjunctExtend),
+    ('\u{16AF0}', '\u{16AF4}', Break::ConjunctExtend), ('\u{16B30}', '\u{16B36}', Break::ConjunctExtend),
+    ('\u{16D63}', '\u{16D63}', Break::V), ('\u{16D67}', '\u{16D6A}', Break::V),
+    ('\u{16F4F}', '\u{16F4F}', Break::ConjunctExtend), ('\u{16F51}', '\u{16F87}', Break::SpacingMark),
+    ('\u{16F8F}', '\u{16F92}', Break::ConjunctExtend), ('\u{16FE4}', '\u{16FE4}', Break::ConjunctExtend),
+    ('\u{16FF0}', '\u{16FF1}', Break::ConjunctExtend), ('\u{1BC9D}', '\u{1BC9E}', Break::ConjunctExtend),
+    ('\u{1BCA0}', '\u{1BCA3}', Break::Control), ('\u{1CF00}', '\u{1CF2D}', Break::ConjunctExtend),
+    ('\u{1CF30}', '\u{1CF46}', Break::ConjunctExtend), ('\u{1D165}', '\u{1D169}', Break::ConjunctExtend),
+    ('\u{1D16D}', '\u{1D172}', Break::ConjunctExtend), ('\u{1D173}', '\u{1D17A}', Break::Control),
+    ('\u{1D17B}', '\u{1D182}', Break::ConjunctExtend), ('\u{1D185}', '\u{1D18B}', Break::ConjunctExtend),
+    ('\u{1D1AA}', '\u{1D1AD}', Break::ConjunctExtend), ('\u{1D242}', '\u{1D244}', Break::ConjunctExtend),
+    ('\u{1DA00}', '\u{1DA36}', Break::ConjunctExtend), ('\u{1DA3B}', '\u{1DA6C}', Break::ConjunctExtend),
+    ('\u{1DA75}', '\u{1DA75}', Break::ConjunctExtend), ('\u{1DA84}', '\u{1DA84}', Break::ConjunctExtend),
+    ('\u{1DA9B}', '\u{1DA9F}', Break::ConjunctExtend), ('\u{1DAA1}', '\u{1DAAF}', Break::ConjunctExtend),
+    ('\u{1E000}', '\u{1E006}', Break::ConjunctExtend), ('\u{1E008}', '\u{1E018}', Break::ConjunctExtend),
+    ('\u{1E01B}', '\u{1E021}', Break::ConjunctExtend), ('\u{1E023}', '\u{1E024}', Break::ConjunctExtend),
+    ('\u{1E026}', '\u{1E02A}', Break::ConjunctExtend), ('\u{1E08F}', '\u{1E08F}', Break::ConjunctExtend),
+    ('\u{1E130}', '\u{1E136}', Break::ConjunctExtend), ('\u{1E2AE}', '\u{1E2AE}', Break::ConjunctExtend),
+    ('\u{1E2EC}', '\u{1E2EF}', Break::ConjunctExtend), ('\u{1E4EC}', '\u{1E4EF}', Break::ConjunctExtend),
+    ('\u{1E5EE}', '\u{1E5EF}', Break::ConjunctExtend), ('\u{1E6E3}', '\u{1E6E3}', Break::ConjunctExtend),
+    ('\u{1E6E6}', '\u{1E6E6}', Break::ConjunctExtend), ('\u{1E6EE}', '\u{1E6EF}', Break::ConjunctExtend),
+    ('\u{1E6F5}', '\u{1E6F5}', Break::ConjunctExtend), ('\u{1E8D0}', '\u{1E8D6}', Break::ConjunctExtend),
+    ('\u{1E944}', '\u{1E94A}', Break::ConjunctExtend), ('\u{1F004}', '\u{1F004}', Break::Pictographic),
+    ('\u{1F02C}', '\u{1F02F}', Break::Pictographic), ('\u{1F094}', '\u{1F09F}', Break::Pictographic),
+    ('\u{1F0AF}', '\u{1F0B0}', Break::Pictographic), ('\u{1F0C0}', '\u{1F0C0}', Break::Pictographic),
+    ('\u{1F0CF}', '\u{1F0D0}', Break::Pictographic), ('\u{1F0F6}', '\u{1F0FF}', Break::Pictographic),
+    ('\u{1F170}', '\u{1F171}', Break::Pictographic), ('\u{1F17E}', '\u{1F17F}', Break::Pictographic),
+    ('\u{1F18E}', '\u{1F18E}', Break::Pictographic), ('\u{1F191}', '\u{1F19A}', Break::Pictographic),
+    ('\u{1F1AE}', '\u{1F1E5}', Break::Pictographic), ('\u{1F1E6}', '\u{1F1FF}', Break::RegionalIndicator),
+    ('\u{1F201}', '\u{1F20F}', Break::Pictographic), ('\u{1F21A}', '\u{1F21A}', Break::Pictographic),
+    ('\u{1F22F}', '\u{1F22F}', Break::Pictographic), ('\u{1F232}', '\u{1F23A}', Break::Pictographic),
+    ('\u{1F23C}', '\u{1F23F}', Break::Pictographic), ('\u{1F249}', '\u{1F25F}', Break::Pictographic),
+    ('\u{1F266}', '\u{1F321}', Break::Pictographic), ('\u{1F324}', '\u{1F393}', Break::Pictographic),
+    ('\u{1F396}', '\u{1F397}', Break::Pictographic), ('\u{1F399}', '\u{1F39B}', Break::Pictographic),
+    ('\u{1F39E}', '\u{1F3F0}', Break::Pictographic), ('\u{1F3F3}', '\u{1F3F5}', Break::Pictographic),
+    ('\u{1F3F7}', '\u{1F3FA}', Break::Pictographic), ('\u{1F3FB}', '\u{1F3FF}', Break::ConjunctExtend),
+    ('\u{1F400}', '\u{1F4FD}', Break::Pictographic), ('\u{1F4FF}', '\u{1F53D}', Break::Pictographic),
+    ('\u{1F549}', '\u{1F54E}', Break::Pictographic), ('\u{1F550}', '\u{1F567}', Break::Pictographic),
+    ('\u{1F56F}', '\u{1F570}', Break::Pictographic), ('\u{1F573}', '\u{1F57A}', Break::Pictographic),
+    ('\u{1F587}', '\u{1F587}', Break::Pictographic), ('\u{1F58A}', '\u{1F58D}', Break::Pictographic),
+    ('\u{1F590}', '\u{1F590}', Break::Pictographic), ('\u{1F595}', '\u{1F596}', Break::Pictographic),
+    ('\u{1F5A4}', '\u{1F5A5}', Break::Pictographic), ('\u{1F5A8}', '\u{1F5A8}', Break::Pictographic),
+    ('\u{1F5B1}', '\u{1F5B2}', Break::Pictographic), ('\u{1F5BC}', '\u{1F5BC}', Break::Pictographic),
+    ('\u{1F5C2}', '\u{1F5C4}', Break::Pictographic), ('\u{1F5D1}', '\u{1F5D3}', Break::Pictographic),
+    ('\u{1F5DC}', '\u{1F5DE}', Break::Pictographic), ('\u{1F5E1}', '\u{1F5E1}', Break::Pictographic),
+    ('\u{1F5E3}', '\u{1F5E3}', Break::Pictographic), ('\u{1F5E8}', '\u{1F5E8}', Break::Pictographic),
+    ('\u{1F5EF}', '\u{1F5EF}', Break::Pictographic), ('\u{1F5F3}', '\u{1F5F3}', Break::Pictographic),
+    ('\u{1F5FA}', '\u{1F64F}', Break::Pictographic), ('\u{1F680}', '\u{1F6C5}', Break::Pictographic),
+    ('\u{1F6CB}', '\u{1F6D2}', Break::Pictographic), ('\u{1F6D5}', '\u{1F6E5}', Break::Pictographic),
+    ('\u{1F6E9}', '\u{1F6E9}', Break::Pictographic), ('\u{1F6EB}', '\u{1F6F0}', Break::Pictographic),
+    ('\u{1F6F3}', '\u{1F6FF}', Break::Pictographic), ('\u{1F7DA}', '\u{1F7FF}', Break::Pictographic),
+    ('\u{1F80C}', '\u{1F80F}', Break::Pictographic), ('\u{1F848}', '\u{1F84F}', Break::Pictographic),
+    ('\u{1F85A}', '\u{1F85F}', Break::Pictographic), ('\u{1F888}', '\u{1F88F}', Break::Pictographic),
+    ('\u{1F8AE}', '\u{1F8AF}', Break::Pictographic), ('\u{1F8BC}', '\u{1F8BF}', Break::Pictographic),
+    ('\u{1F8C2}', '\u{1F8CF}', Break::Pictographic), ('\u{1F8D9}', '\u{1F8FF}', Break::Pictographic),
+    ('\u{1F90C}', '\u{1F93A}', Break::Pictographic), ('\u{1F93C}', '\u{1F945}', Break::Pictographic),
+    ('\u{1F947}', '\u{1F9FF}', Break::Pictographic), ('\u{1FA58}', '\u{1FA5F}', Break::Pictographic),
+    ('\u{1FA6E}', '\u{1FAFF}', Break::Pictographic), ('\u{1FC00}', '\u{1FFFD}', Break::Pictographic),
+    ('\u{E0000}', '\u{E001F}', Break::Control), ('\u{E0020}', '\u{E007F}', Break::ConjunctExtend),
+    ('\u{E0080}', '\u{E00FF}', Break::Control), ('\u{E0100}', '\u{E01EF}', Break::ConjunctExtend),
+    ('\u{E01F0}', '\u{E0FFF}', Break::Control),
+];
@@ -0,0 +1,253 @@
+use crate::parser::{
+    ast::{AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind},
+    simplify, Ast, ClassSet, Error, ErrorKind,
+};
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests;
+
+// the tree every engine is built from, lowered from the simplified ast.
+// what the ast leaves open is decided here once:
+// - sets are sorted, non-overlapping intervals with the negation applied
+// - every repetition is min to max, a possessive one is an atomic greedy one
+// - positions are look-assertions, comments and plain groups are gone
+// case folding and the other flags are applied by the parser already.
+// a tree made in code may hold nodes no pattern parses to, they are errors here
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Ir {
+    Concat(Vec<Ir>),         // empty matches nothing and always succeeds
+    Alt(Vec<Ir>),            // empty always fails
+    Capture(usize, Box<Ir>), // group 0 is the whole match
+    Atomic(Box<Ir>),
+    Conditional(usize, Box<Ir>, Box<Ir>), // group id, then yes and no
+    Call(usize),
+    Repeat(Repeat),
+    Char(char),
+    Class(Vec<(char, char)>),
+    Any,
+    Grapheme,
+    Look(Look),
+    ResetStart,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Repeat {
+    pub min: u32,
+    pub max: Option<u32>, // None has no upper bound
+    pub greedy: bool,
+    pub item: Box<Ir>,
+    pub item_can_be_empty: bool, // kept so no pass looks into the item again
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Look {
+    SoL, // '^'
+    EoL, // '$'
+    SoS, // '\G'
+}
+
+impl Ir {
+    pub fn from_ast(ast: &Ast) -> Result<Ir, Error> {
+        let simple = simplify(ast)?;
+        let ir = lower(&simple);
+        simple.teardown();
+        ir
+    }
+
+    pub fn can_be_empty(&self) -> bool {
+        // a repeat knows it for its item, nothing below one is visited
+        fn children(ir: &Ir) -> Vec<&Ir> {
+            match ir {
+                Ir::Repeat(_) => vec![],
+                _ => ir.children(),
+            }
+        }
+        self.fold(children, |ir, children: Vec<bool>| match ir {
+            Ir::Concat(_) => children.iter().all(|empty| *empty),
+            Ir::Alt(_) | Ir::Conditional(_, _, _) => children.iter().any(|empty| *empty),
+            Ir::Capture(_, _) | Ir::Atomic(_) => children[0],
+            Ir::Call(_) => true, // the callee is unknown here, assume the worst
+            Ir::Repeat(repeat) => repeat.min == 0 || repeat.item_can_be_empty,
+            Ir::Char(_) | Ir::Class(_) | Ir::Any | Ir::Grapheme => false,
+            Ir::Look(_) | Ir::ResetStart => true,
+        })
+    }
+
+    // the first in depth first order
+    pub fn find_group(&self, capture_id: usize) -> Option<&Ir> {
+        let mut stack = vec![self];
+        while let Some(ir) = stack.pop() {
+            if let Ir::Capture(id, _) = ir {
+                if *id == capture_id {
+                    return Some(ir);
+                }
+            }
+            stack.extend(ir.children().into_iter().rev());
+        }
+        None
+    }
+
+    pub fn children(&self) -> Vec<&Ir> {
+        match self {
+            Ir::Concat(items) | Ir::Alt(items) => items.iter().collect(),
+            Ir::Capture(_, item) | Ir::Atomic(item) => vec![item],
+            Ir::Conditional(_, yes, no) => vec![yes, no],
+            Ir::Repeat(repeat) => vec![&repeat.item],
+            _ => vec![],
+        }
+    }
+
+    // bottom up, f gets each node with what it returned for the children, which
+    // are given by children. the tree may be deeper than the stack
+    pub fn fold<'a, T>(
+        &'a self,
+        children: impl Fn(&'a Ir) -> Vec<&'a Ir>,
+        mut f: impl FnMut(&'a Ir, Vec<T>) -> T,
+    ) -> T {
+        let mut stack = vec![(self, None)];
+        let mut results = vec![];
+        while let Some((ir, count)) = stack.pop() {
+            if let Some(count) = count {
+                let values = results.split_off(results.len() - count);
+                results.push(f(ir, values));
+                continue;
+            }
+
+            let items = children(ir);
+            stack.push((ir, Some(items.len())));
+            stack.extend(items.into_iter().rev().map(|item| (item, None)));
+        }
+        results.pop().unwrap()
+    }
+
+    fn take_children(&mut self) -> Vec<Ir> {
+        let take = |item: &mut Box<Ir>| std::mem::replace(&mut **item, Ir::Concat(vec![]));
+        match self {
+            Ir::Concat(items) | Ir::Alt(items) => std::mem::take(items),
+            Ir::Capture(_, item) | Ir::Atomic(item) => vec![take(item)],
+            Ir::Conditional(_, yes, no) => vec![take(yes), take(no)],
+            Ir::Repeat(repeat) => vec![take(&mut repeat.item)],
+            _ => vec![],
+        }
+    }
+}
+
+// a tree made in code may be deeper than the stack, dropping one is a loop
+impl Drop for Ir {
+    fn drop(&mut self) {
+        let mut stack = self.take_children();
+        while let Some(mut ir) = stack.pop() {
+            stack.append(&mut ir.take_children());
+        }
+    }
+}
+
+fn lower(ast: &Ast) -> Result<Ir, Error> {
+    // bottom up, the tree may be deeper than the stack
+    ast.fold(|ast, children: Vec<Result<Ir, Error>>| lower_node(ast, children))
+}
+
+fn lower_node(ast: &Ast, children: Vec<Result<Ir, Error>>) -> Result<Ir, Error> {
+    let ir = match &ast.kind {
+        AstKind::NonCaptureGroup => lower_concat(children)?,
+        AstKind::CaptureGroup(cap_id) => Ir::Capture(*cap_id, Box::new(lower_concat(children)?)),
+        AstKind::AtomicGroup => Ir::Atomic(Box::new(lower_concat(children)?)),
+        AstKind::Conditional(cap_id) => {
+            let [yes, no] = take::<2>(ast, children)?;
+            Ir::Conditional(*cap_id, Box::new(yes?), Box::new(no?))
+        }
+        AstKind::Call(cap_id) => Ir::Call(*cap_id),
+        AstKind::Union => Ir::Alt(children.into_iter().collect::<Result<_, _>>()?),
+        AstKind::IncludeSet => lower_class(ClassSet::from_asts(&ast.children)?),
+        AstKind::ExcludeSet => lower_class(ClassSet::from_asts(&ast.children)?.negate()),
+        AstKind::Star(greedy) => lower_repeat(ast, children, 0, None, greedy)?,
+        AstKind::Plus(greedy) => lower_repeat(ast, children, 1, None, greedy)?,
+        AstKind::Option(greedy) => lower_repeat(ast, children, 0, Some(1), greedy)?,
+        AstKind::Repeat(RepeatKind::Num(n), m, greedy) => {
+            let max = match m {
+                RepeatKind::Num(m) => Some(*m),
+                RepeatKind::Infinity => None,
+            };
+            lower_repeat(ast, children, *n, max, greedy)?
+        }
+        AstKind::Repeat(RepeatKind::Infinity, _, _) => {
+            let what = "repeat min is infinity".to_string();
+            return Err(Error::new(ErrorKind::InvalidNode(what), ast.span));
+        }
+        AstKind::Match(MatchKind::Any) => Ir::Any,
+        AstKind::Match(MatchKind::Grapheme) => Ir::Grapheme,
+        AstKind::Match(MatchKind::Char(c)) => Ir::Char(*c),
+        AstKind::Match(MatchKind::Range(_, _)) => {
+            lower_class(ClassSet::from_asts(std::slice::from_ref(ast))?)
+        }
+        AstKind::Position(PositionKind::SoL) => Ir::Look(Look::SoL),
+        AstKind::Position(PositionKind::EoL) => Ir::Look(Look::EoL),
+        AstKind::Position(PositionKind::SoS) => Ir::Look(Look::SoS),
+        AstKind::Comment(_) => Ir::Concat(vec![]),
+        AstKind::ResetStart => Ir::ResetStart,
+    };
+    Ok(ir)
+}
+
+fn lower_concat(children: Vec<Result<Ir, Error>>) -> Result<Ir, Error> {
+    let mut items = vec![];
+    for item in children {
+        let mut item = item?;
+        match &mut item {
+            Ir::Concat(inner) => items.append(inner),
+            _ => items.push(item),
+        }
+    }
+
+    if items.len() == 1 {
+        return Ok(items.pop().unwrap());
+    }
+    Ok(Ir::Concat(items))
+}
+
+fn lower_class(set: ClassSet) -> Ir {
+    match set.ranges()[..] {
+        [(a, b)] if a == b => Ir::Char(a),
+        ref ranges => Ir::Class(ranges.to_vec()),
+    }
+}
+
+fn lower_repeat(
+    ast: &Ast,
+    children: Vec<Result<Ir, Error>>,
+    min: u32,
+    max: Option<u32>,
+    greedy: &GreedyKind,
+) -> Result<Ir, Error> {
+    let [item] = take::<1>(ast, children)?;
+    let item = item?;
+    let repeat = Ir::Repeat(Repeat {
+        min,
+        max,
+        greedy: !matches!(greedy, GreedyKind::NonGreedy),
+        item_can_be_empty: item.can_be_empty(),
+        item: Box::new(item),
+    });
+
+    match greedy {
+        GreedyKind::Possessive => Ok(Ir::Atomic(Box::new(repeat))),
+        _ => Ok(repeat),
+    }
+}
+
+// the children a node takes, one for a quantifier and two for a conditional
+fn take<const N: usize>(
+    ast: &Ast,
+    children: Vec<Result<Ir, Error>>,
+) -> Result<[Result<Ir, Error>; N], Error> {
+    children.try_into().map_err(|_| {
+        let what = format!(
+            "{:?} wants {} children, has {}",
+            ast.kind,
+            N,
+            ast.children.len()
+        );
+        Error::new(ErrorKind::InvalidNode(what), ast.span)
+    })
+}
@@ -0,0 +1,205 @@
+use super::*;
+use crate::RegexBuilder;
+
+fn run(pattern: &str) -> Ir {
+    Ir::from_ast(&RegexBuilder::new(pattern).parse().unwrap()).unwrap()
+}
+
+fn top(item: Ir) -> Ir {
+    Ir::Capture(0, Box::new(item))
+}
+
+fn repeat(item: Ir, min: u32, max: Option<u32>, greedy: bool) -> Ir {
+    Ir::Repeat(Repeat {
+        min,
+        max,
+        greedy,
+        item_can_be_empty: item.can_be_empty(),
+        item: Box::new(item),
+    })
+}
+
+#[test]
+fn class() {
+    assert_eq!(run("[a-cb-d]"), top(Ir::Class(vec![('a', 'd')])));
+    assert_eq!(run("[a]"), top(Ir::Char('a')));
+    assert_eq!(
+        run("[a-z&&[^b-y]]"),
+        top(Ir::Class(vec![('a', 'a'), ('z', 'z')]))
+    );
+    assert_eq!(
+        run("[^b-y]"),
+        top(Ir::Class(vec![
+            ('\0', 'a'),
+            ('z', '\u{D7FF}'),
+            ('\u{E000}', char::MAX)
+        ]))
+    );
+    assert_eq!(
+        run("(?i)k"),
+        top(Ir::Class(vec![
+            ('K', 'K'),
+            ('k', 'k'),
+            ('\u{212A}', '\u{212A}')
+        ]))
+    );
+}
+
+#[test]
+fn repeat_bounds() {
+    let a = || Ir::Char('a');
+
+    assert_eq!(run("a*"), top(repeat(a(), 0, None, true)));
+    assert_eq!(run("a+?"), top(repeat(a(), 1, None, false)));
+    assert_eq!(run("a?"), top(repeat(a(), 0, Some(1), true)));
+    assert_eq!(run("a{2,}?"), top(repeat(a(), 2, None, false)));
+    assert_eq!(run("a{,3}"), top(repeat(a(), 0, Some(3), true)));
+    assert_eq!(
+        run("a{2,3}+"),
+        top(Ir::Atomic(Box::new(repeat(a(), 2, Some(3), true))))
+    );
+}
+
+#[test]
+fn look() {
+    assert_eq!(
+        run(r"^(?#note)\Ga$"),
+        top(Ir::Concat(vec![
+            Ir::Look(Look::SoL),
+            Ir::Look(Look::SoS),
+            Ir::Char('a'),
+            Ir::Look(Look::EoL),
+        ]))
+    );
+}
+
+#[test]
+fn groups() {
+    let ir = run("(a(?:b(c)))(?1)|(?>d)");
+    let group1 = Ir::Capture(
+        1,
+        Box::new(Ir::Concat(vec![
+            Ir::Char('a'),
+            Ir::Char('b'),
+            Ir::Capture(2, Box::new(Ir::Char('c'))),
+        ])),
+    );
+    let expect = top(Ir::Alt(vec![
+        Ir::Concat(vec![group1.clone(), Ir::Call(1)]),
+        Ir::Atomic(Box::new(Ir::Char('d'))),
+    ]));
+
+    assert_eq!(ir, expect);
+    assert_eq!(ir.find_group(1), Some(&group1));
+    assert_eq!(
+        ir.find_group(2),
+        Some(&Ir::Capture(2, Box::new(Ir::Char('c'))))
+    );
+    assert_eq!(ir.find_group(3), None);
+}
+
+#[test]
+fn can_be_empty() {
+    for (src, empty) in [
+        ("a*", true),
+        ("a+", false),
+        ("(?:a*)+", true),
+        ("a|", true),
+        ("(a|b)", false),
+        ("a{0}", true),
+        ("^$", true),
+        ("[^a]", false),
+    ] {
+        assert_eq!(run(src).can_be_empty(), empty, "{}", src);
+    }
+}
+
+#[test]
+fn invalid() {
+    use crate::ast::{self, AstKind, GreedyKind, RepeatKind};
+
+    let make = |kind: AstKind, children: Vec<Ast>| Ast {
+        kind,
+        children,
+        span: Default::default(),
+    };
+    let is_invalid = |ast: &Ast| {
+        matches!(
+            Ir::from_ast(ast).map_err(|err| err.kind),
+            Err(ErrorKind::InvalidNode(_))
+        )
+    };
+
+    let star = make(AstKind::Star(GreedyKind::Greedy), vec![ast::literal("a")]);
+    assert_eq!(is_invalid(&make(AstKind::IncludeSet, vec![star])), true);
+    assert_eq!(
+        is_invalid(&make(AstKind::Star(GreedyKind::Greedy), vec![])),
+        true
+    );
+    assert_eq!(
+        is_invalid(&make(
+            AstKind::Repeat(
+                RepeatKind::Infinity,
+                RepeatKind::Infinity,
+                GreedyKind::Greedy
+            ),
+            vec![ast::literal("a")]
+        )),
+        true
+    );
+    assert_eq!(
+        is_invalid(&make(AstKind::Conditional(1), vec![ast::literal("a")])),
+        true
+    );
+    assert_eq!(
+        Ir::from_ast(&ast::class(&[('z', 'a')])).map_err(|err| err.kind),
+        Err(ErrorKind::InvalidRange('z', 'a'))
+    );
+}
+
+#[test]
+fn deep() {
+    use crate::ast::{self, AstKind, GreedyKind};
+
+    let make = |kind: AstKind, children: Vec<Ast>| Ast {
+        kind,
+        children,
+        span: Default::default(),
+    };
+
+    // made in code a tree can be deeper than any stack, simplify and lower loop and
+    // the trees are torn down, dropping them would recurse
+    let mut ast = ast::literal("a");
+    for id in 1..=100_000 {
+        ast = ast::group(id, ast);
+    }
+    let ast = ast::group(0, ast);
+    let ir = Ir::from_ast(&ast).unwrap();
+    assert_eq!(ir.can_be_empty(), false);
+    assert_eq!(
+        ir.find_group(1),
+        Some(&Ir::Capture(1, Box::new(Ir::Char('a'))))
+    );
+    ast.teardown();
+
+    let mut ast = ast::literal("a");
+    for _ in 0..100_000 {
+        ast = make(AstKind::Plus(GreedyKind::Greedy), vec![ast]);
+    }
+    assert_eq!(Ir::from_ast(&ast).unwrap().can_be_empty(), false);
+    ast.teardown();
+
+    let mut ast = ast::class(&[('a', 'a')]);
+    for _ in 0..100_000 {
+        ast = make(AstKind::ExcludeSet, vec![ast]);
+    }
+    assert_eq!(Ir::from_ast(&ast), Ok(Ir::Char('a')));
+    ast.teardown();
+
+    let mut ast = ast::literal("a");
+    for _ in 0..50_000 {
+        ast = ast::alt(vec![ast::literal("b"), ast]);
+    }
+    assert_eq!(Ir::from_ast(&ast).is_ok(), true);
+    ast.teardown();
+}
@@ -1,6 +1,16 @@
+pub mod ast;
+mod builder;
+mod grapheme;
+mod ir;
 mod parser;
 mod sfa;
 mod vm;
 
+pub use builder::{RegexBuilder, Syntax};
+pub use parser::{escape, Error, ErrorKind};
 pub use sfa::{Dfa, Nfa};
 pub use vm::Vm;
+
+// the Unicode release the case folding and grapheme cluster tables are generated from,
+// see scripts/unicode_tables.py
+pub const UNICODE_VERSION: (u32, u32, u32) = (17, 0, 0);
@@ -1,11 +1,18 @@
 // syntax (like BNF)
 //
 // root      = concat
-// concat    = ( group | set | repeat | union | position | matcher )+
-// group     = '(' concat ')' | '(' '?' ':' concat ')'
-// set       = '[' set-items ']'
-// set-items = ( char | char '-' char )+
-// repeat    = repeat_g | repeat_ng
+// concat    = ( group | set | repeat | union | position | reset | matcher | comment )*
+// group     = '(' concat ')' | '(' '?' ':' concat ')' | '(' '?' '>' concat ')'
+//           | '(' '?' '<' name '>' concat ')' | '(' '?' 'P' '<' name '>' concat ')'
+//           | '(' '?' '|' concat ')'                              (branch reset)
+//           | '(' '?' flags ':' concat ')' | '(' '?' flags ')'  (flags last to the group end)
+//           | '(' '?' '(' ( number | '<' name '>' ) ')' concat ( '|' concat )? ')'
+//           | '(' '?' ( 'R' | number | '&' name ) ')'          (subroutine call)
+// set       = '[' set-expr ']' | '[' '^' set-expr ']'
+// set-expr  = set-items ( set-op set-items )*
+// set-items = ( char | char '-' char | set )+   (']' first, '-' at either end and '^' not first are chars)
+// set-op    = '&&' | '--' | '~~'             (intersection, difference, symmetric difference)
+// repeat    = repeat_g | repeat_ng | repeat_p
 // repeat_g  = term '{' number '}'              == term{n, n}
 //           | term '{' number ',' '}           == term{n, inf}
 //           | term '{' ',' number '}'          == term{0, n}
@@ -14,15 +21,37 @@
 //           | term '+'                         == term{1, inf}
 //           | term '?'                         == term{0, 1}
 // repeat_ng = repeat_g '?'
+// repeat_p  = repeat_g '+'
 // union     = concat '|' concat
-// position  = '^' | '$'
-// matcher   = '\' meta-char | char
+// position  = '^' | '$' | '\' 'G'                ('\' 'G' is where the search started)
+// reset     = '\' 'K'                         (the reported match starts here)
+// matcher   = '\' meta-char | char | quote | '.' | '\' 'X'   ('\' 'X' is one grapheme cluster)
+// flags     = ( 'i' | '-' )+                   ('i' case insensitive, '-' turns off the rest)
+// comment   = '(' '?' '#' char* ')'           (kept in the tree, matches nothing)
+// quote     = '\' 'Q' char* ( '\' 'E' | EOL )   (all chars are literal, '\' 'E' alone is ignored)
 
 pub(crate) mod ast;
+mod casefold;
+mod class;
+mod error;
+mod glob;
+mod like;
+#[allow(clippy::module_inception, clippy::needless_return)]
 mod parser;
+mod posix;
+mod simplify;
+mod stream;
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests;
 
 pub(crate) use ast::Ast;
+pub(crate) use class::ClassSet;
+pub use error::{Error, ErrorKind};
+pub(crate) use glob::GlobParser;
+pub(crate) use like::LikeParser;
+pub use parser::escape;
 pub(crate) use parser::Parser;
+pub(crate) use posix::PosixParser;
+pub(crate) use simplify::simplify;
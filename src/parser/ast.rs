@@ -1,13 +1,139 @@
-#[derive(Debug, PartialEq)]
+use super::error::{Error, ErrorKind};
+
+#[derive(Debug, Clone)]
 pub struct Ast {
     pub kind: AstKind,
     pub children: Vec<Ast>,
+    pub span: Span, // where the node was written in the pattern
+}
+
+// byte range of the pattern, nodes made up by the parser get the span they came from
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+// two trees are equal when they have the same shape, wherever they were written
+impl PartialEq for Ast {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.children == other.children
+    }
+}
+
+// a tree may be as deep as the nest limit allows, or deeper when made in code, so nothing
+// here recurses
+impl Ast {
+    // dropping a tree recurses, the trees the crate is done with are torn down in a loop
+    pub(crate) fn teardown(mut self) {
+        let mut stack = std::mem::take(&mut self.children);
+        while let Some(mut ast) = stack.pop() {
+            stack.append(&mut ast.children);
+        }
+    }
+
+    pub fn can_be_empty(&self) -> bool {
+        self.fold(|ast, children: Vec<bool>| match &ast.kind {
+            AstKind::NonCaptureGroup | AstKind::CaptureGroup(_) | AstKind::AtomicGroup => {
+                children.iter().all(|empty| *empty)
+            }
+            AstKind::Union | AstKind::Conditional(_) => children.iter().any(|empty| *empty),
+            AstKind::IncludeSet | AstKind::ExcludeSet => false,
+            AstKind::Star(_) | AstKind::Option(_) => true,
+            AstKind::Repeat(RepeatKind::Num(0), _, _) => true,
+            AstKind::Plus(_) | AstKind::Repeat(_, _, _) => children.iter().all(|empty| *empty),
+            AstKind::Match(_) => false,
+            AstKind::Position(_) => true,
+            AstKind::Comment(_) => true,
+            AstKind::ResetStart => true,
+            AstKind::Call(_) => true, // the callee is unknown here, assume the worst
+        })
+    }
+
+    // depth first, the visitor sees each node before and after its children
+    pub fn walk<V: Visitor>(&self, visitor: &mut V) {
+        let mut stack = vec![(self, false)];
+        while let Some((ast, is_visited)) = stack.pop() {
+            if is_visited {
+                visitor.visit_post(ast);
+                continue;
+            }
+
+            visitor.visit_pre(ast);
+            stack.push((ast, true));
+            stack.extend(ast.children.iter().rev().map(|child| (child, false)));
+        }
+    }
+
+    // bottom up, f gets each node with what it returned for the children
+    pub(crate) fn fold<T>(&self, mut f: impl FnMut(&Ast, Vec<T>) -> T) -> T {
+        let mut stack = vec![(self, false)];
+        let mut results = vec![];
+        while let Some((ast, is_visited)) = stack.pop() {
+            if is_visited {
+                let children = results.split_off(results.len() - ast.children.len());
+                results.push(f(ast, children));
+                continue;
+            }
+
+            stack.push((ast, true));
+            stack.extend(ast.children.iter().rev().map(|child| (child, false)));
+        }
+        results.pop().unwrap()
+    }
+
+    // every group, set, union and quantifier below the root is a level
+    pub(crate) fn check_nest(&self, limit: usize) -> Result<(), Error> {
+        let mut stack: Vec<(&Ast, usize)> = self.children.iter().rev().map(|c| (c, 1)).collect();
+        while let Some((ast, depth)) = stack.pop() {
+            if ast.is_leaf() {
+                continue;
+            }
+            if depth > limit {
+                return Err(Error::new(ErrorKind::NestTooDeep(limit), ast.span));
+            }
+            stack.extend(ast.children.iter().rev().map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    fn is_leaf(&self) -> bool {
+        matches!(
+            self.kind,
+            AstKind::Call(_)
+                | AstKind::Match(_)
+                | AstKind::Position(_)
+                | AstKind::Comment(_)
+                | AstKind::ResetStart
+        )
+    }
+
+    // the first in depth first order
+    pub fn find_group(&self, capture_id: usize) -> Option<&Ast> {
+        let mut stack = vec![self];
+        while let Some(ast) = stack.pop() {
+            if ast.kind == AstKind::CaptureGroup(capture_id) {
+                return Some(ast);
+            }
+            stack.extend(ast.children.iter().rev());
+        }
+        None
+    }
 }
 
-#[derive(Debug, PartialEq)]
+// callbacks for Ast::walk, both do nothing unless overridden
+pub trait Visitor {
+    fn visit_pre(&mut self, _ast: &Ast) {}
+    fn visit_post(&mut self, _ast: &Ast) {}
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum AstKind {
     NonCaptureGroup,
     CaptureGroup(usize),
+    AtomicGroup,
+    Conditional(usize),
+    Call(usize),
     Union,
     IncludeSet,
     ExcludeSet,
@@ -17,12 +143,15 @@ pub enum AstKind {
     Repeat(RepeatKind, RepeatKind, GreedyKind),
     Match(MatchKind),
     Position(PositionKind),
+    Comment(String),
+    ResetStart, // '\K'
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum GreedyKind {
     Greedy,
     NonGreedy,
+    Possessive,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -31,15 +160,17 @@ pub enum RepeatKind {
     Infinity,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum MatchKind {
     Any,               // '.'
+    Grapheme,          // '\X'
     Char(char),        // a
     Range(char, char), // a - z
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PositionKind {
     SoL, // '^'
     EoL, // '$'
+    SoS, // '\G', where the search started
 }
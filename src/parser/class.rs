@@ -0,0 +1,199 @@
+use super::{
+    ast::{AstKind, MatchKind, Span},
+    casefold::fold_range,
+    error::{Error, ErrorKind},
+    Ast,
+};
+
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATE_END: u32 = 0xDFFF;
+
+// sorted, non-overlapping ranges of code points
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ClassSet {
+    ranges: Vec<(u32, u32)>,
+}
+
+impl ClassSet {
+    pub fn new(ranges: Vec<(char, char)>) -> Self {
+        Self::merge(
+            ranges
+                .into_iter()
+                .map(|(a, b)| (a as u32, b as u32))
+                .collect(),
+        )
+    }
+
+    fn merge(mut ranges: Vec<(u32, u32)>) -> Self {
+        ranges.sort();
+
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (a, b) in ranges {
+            if let Some(last) = merged.last_mut() {
+                let is_adjacent = a <= last.1.saturating_add(1)
+                    || (last.1 == SURROGATE_START - 1 && a == SURROGATE_END + 1);
+                if is_adjacent {
+                    last.1 = last.1.max(b);
+                    continue;
+                }
+            }
+            merged.push((a, b));
+        }
+
+        ClassSet { ranges: merged }
+    }
+
+    pub fn full() -> Self {
+        ClassSet {
+            ranges: vec![
+                (0, SURROGATE_START - 1),
+                (SURROGATE_END + 1, char::MAX as u32),
+            ],
+        }
+    }
+
+    // the parser puts only chars, ranges and sets in a set, a tree made in code may not
+    pub fn from_asts(asts: &[Ast]) -> Result<Self, Error> {
+        let mut ranges = vec![];
+        for ast in asts.iter() {
+            ranges.extend(Self::from_ast(ast)?.ranges);
+        }
+        Ok(Self::merge(ranges))
+    }
+
+    // bottom up, a set made in code may nest deeper than the stack
+    fn from_ast(ast: &Ast) -> Result<Self, Error> {
+        ast.fold(|ast, children: Vec<Result<Self, Error>>| match &ast.kind {
+            AstKind::Match(MatchKind::Char(c)) => Ok(Self::new(vec![(*c, *c)])),
+            AstKind::Match(MatchKind::Range(a, b)) if a <= b => Ok(Self::new(vec![(*a, *b)])),
+            AstKind::Match(MatchKind::Range(a, b)) => {
+                Err(Error::new(ErrorKind::InvalidRange(*a, *b), ast.span))
+            }
+            AstKind::IncludeSet | AstKind::ExcludeSet => {
+                let mut ranges = vec![];
+                for child in children {
+                    ranges.extend(child?.ranges);
+                }
+                let set = Self::merge(ranges);
+                match ast.kind {
+                    AstKind::ExcludeSet => Ok(set.negate()),
+                    _ => Ok(set),
+                }
+            }
+            _ => {
+                let what = "a set holds only chars, char-ranges and sets".to_string();
+                Err(Error::new(ErrorKind::InvalidNode(what), ast.span))
+            }
+        })
+    }
+
+    // the ranges don't map back to the pattern, they all get the span of the set
+    pub fn to_asts(&self, span: Span) -> Vec<Ast> {
+        self.ranges()
+            .into_iter()
+            .map(|(a, b)| Ast {
+                kind: AstKind::Match(MatchKind::Range(a, b)),
+                children: vec![],
+                span,
+            })
+            .collect()
+    }
+
+    pub fn ranges(&self) -> Vec<(char, char)> {
+        self.ranges
+            .iter()
+            .map(|(a, b)| (char::from_u32(*a).unwrap(), char::from_u32(*b).unwrap()))
+            .collect()
+    }
+
+    pub fn union(&self, other: &ClassSet) -> Self {
+        let mut ranges = self.ranges();
+        ranges.extend(other.ranges());
+        Self::new(ranges)
+    }
+
+    pub fn intersect(&self, other: &ClassSet) -> Self {
+        let mut ranges = vec![];
+
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = self.ranges[i];
+            let (c, d) = other.ranges[j];
+
+            let start = a.max(c);
+            let end = b.min(d);
+            if start <= end {
+                ranges.push((start, end));
+            }
+
+            if b < d {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        ClassSet { ranges }
+    }
+
+    pub fn difference(&self, other: &ClassSet) -> Self {
+        let mut ranges = vec![];
+
+        for (a, b) in self.ranges.iter().copied() {
+            let mut start = a;
+
+            for (c, d) in other.ranges.iter().copied() {
+                if d < start {
+                    continue;
+                }
+                if b < c {
+                    break;
+                }
+                if start < c {
+                    ranges.push((start, prev_point(c)));
+                }
+                start = next_point(d);
+                if b < start {
+                    break;
+                }
+            }
+
+            if start <= b {
+                ranges.push((start, b));
+            }
+        }
+
+        ClassSet { ranges }
+    }
+
+    pub fn symmetric_difference(&self, other: &ClassSet) -> Self {
+        self.union(other).difference(&self.intersect(other))
+    }
+
+    pub fn case_fold(&self) -> Self {
+        let mut ranges = vec![];
+        for (a, b) in self.ranges() {
+            ranges.extend(fold_range(a, b));
+        }
+        Self::new(ranges)
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::full().difference(self)
+    }
+}
+
+// the code points next to c, stepping over the surrogates which are no chars
+fn prev_point(c: u32) -> u32 {
+    if c == SURROGATE_END + 1 {
+        return SURROGATE_START - 1;
+    }
+    c - 1
+}
+
+fn next_point(c: u32) -> u32 {
+    if c == SURROGATE_START - 1 {
+        return SURROGATE_END + 1;
+    }
+    c + 1
+}
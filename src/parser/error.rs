@@ -0,0 +1,151 @@
+use std::fmt;
+
+use super::ast::Span;
+
+// why a pattern failed to parse
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    Unexpected(char),            // a char no rule takes, like a stray ')'
+    UnexpectedEol(&'static str), // the pattern ended before the named thing
+    Want(&'static str),          // the named token is missing
+    Unclosed(&'static str),      // the close token of the named group, set, .. is missing
+    EmptyTarget(&'static str),   // the named quantifier has nothing to repeat
+    InvalidNumber(String),
+    InvalidRange(char, char),
+    InvalidRangeStart,
+    InvalidRepeat(u32, u32),
+    Unsupported(String),
+    UndefinedGroup(String),
+    DuplicateName(String),
+    EmptyName,
+    UnknownClass(String),
+    TooManyBranches,
+    ConditionOnZero,
+    InvalidNode(String), // a tree made in code holds a node no pattern parses to
+    NestTooDeep(usize),  // groups, sets, .. nest deeper than the limit
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub span: Span,           // the part of the pattern that failed
+    pub opened: Option<Span>, // the open token of an unclosed group, set, ..
+}
+
+impl Error {
+    pub(crate) fn new(kind: ErrorKind, span: Span) -> Self {
+        Error {
+            kind,
+            span,
+            opened: None,
+        }
+    }
+
+    pub(crate) fn opened(mut self, span: Span) -> Self {
+        self.opened = Some(span);
+        self
+    }
+
+    // a short suggestion for the fix, if there is a likely one
+    pub fn hint(&self, pattern: &str) -> Option<String> {
+        let text = pattern.get(self.span.start..self.span.end).unwrap_or("");
+
+        match &self.kind {
+            ErrorKind::Unexpected(c) if *c != '\\' => Some(format!("did you mean `\\{}`?", c)),
+            ErrorKind::UnexpectedEol(_) if text == "\\" => Some("did you mean `\\\\`?".to_string()),
+            ErrorKind::Unclosed(what) if self.opened.is_some() => {
+                Some(format!("unclosed {} opened here", what))
+            }
+            ErrorKind::EmptyTarget(_) if !text.is_empty() => {
+                Some(format!("did you mean `\\{}`?", text))
+            }
+            ErrorKind::InvalidRange(a, b) => Some(format!("did you mean `{}-{}`?", b, a)),
+            ErrorKind::InvalidRepeat(n, m) => Some(format!("did you mean `{{{},{}}}`?", m, n)),
+            _ => None,
+        }
+    }
+
+    // the message, then the pattern with carets under the failing part,
+    // and a '|' under the open token when something was left unclosed:
+    //
+    //   ERROR: want group close token
+    //     a(bc
+    //      |  ^
+    //      unclosed group opened here
+    pub fn render(&self, pattern: &str) -> String {
+        let column = |pos: usize| pattern.get(..pos).map_or(0, |s| s.chars().count());
+        let start = column(self.span.start);
+        let width = (column(self.span.end) - start).max(1);
+
+        let mut marks = vec![' '; start + width];
+        marks[start..].fill('^');
+        if let Some(mark) = self
+            .opened
+            .and_then(|opened| marks.get_mut(column(opened.start)))
+        {
+            *mark = '|';
+        }
+
+        // keep the carets in line with the chars above them
+        let line: String = pattern
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+
+        let mut text = format!("{}\n  {}\n  {}", self, line, String::from_iter(marks));
+        match (self.hint(pattern), self.opened) {
+            (Some(hint), Some(opened)) => {
+                let indent = " ".repeat(column(opened.start));
+                text += &format!("\n  {}{}", indent, hint);
+            }
+            (Some(hint), None) => text += &format!("\n  hint: {}", hint),
+            (None, _) => {}
+        }
+        text
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Unexpected(c) => write!(f, "ERROR: unexpected '{}'", c),
+            ErrorKind::UnexpectedEol(what) => write!(f, "ERROR: want {}, get EOL", what),
+            ErrorKind::Want(what) => write!(f, "ERROR: want {}", what),
+            ErrorKind::Unclosed(what) => write!(f, "ERROR: want {} close token", what),
+            ErrorKind::EmptyTarget(what) => write!(f, "ERROR: {} target is empty", what),
+            ErrorKind::InvalidNumber(err) => write!(f, "ERROR: invalid number, {}", err),
+            ErrorKind::InvalidRange(a, b) => write!(f, "ERROR: char-range is invalid {}-{}", a, b),
+            ErrorKind::InvalidRangeStart => write!(f, "ERROR: char-range start is not a char"),
+            ErrorKind::InvalidRepeat(n, m) => {
+                write!(f, "ERROR: repeat range invalid {{{},{}}}", n, m)
+            }
+            ErrorKind::Unsupported(what) => write!(f, "ERROR: unsupport {}", what),
+            ErrorKind::UndefinedGroup(name) => {
+                write!(f, "ERROR: reference to undefined group {}", name)
+            }
+            ErrorKind::DuplicateName(name) => write!(f, "ERROR: duplicate group name '{}'", name),
+            ErrorKind::EmptyName => write!(f, "ERROR: group name is empty"),
+            ErrorKind::UnknownClass(name) => write!(f, "ERROR: unknown char-class '{}'", name),
+            ErrorKind::TooManyBranches => {
+                write!(f, "ERROR: conditional group has too many branches")
+            }
+            ErrorKind::ConditionOnZero => write!(f, "ERROR: condition on group 0 is not supported"),
+            ErrorKind::InvalidNode(what) => write!(f, "ERROR: invalid node, {}", what),
+            ErrorKind::NestTooDeep(limit) => {
+                write!(f, "ERROR: pattern nests deeper than {} levels", limit)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> String {
+        err.to_string()
+    }
+}
@@ -0,0 +1,322 @@
+use super::{
+    ast::{AstKind, GreedyKind, MatchKind, PositionKind, Span},
+    error::{Error, ErrorKind},
+    stream::CharStream,
+    Ast,
+};
+
+// shell glob syntax, the whole text must match
+//
+// glob     = item*
+// item     = '*'                        any chars (no '/' with literal separator)
+//          | '**' '/'?                  any chars, also across '/'
+//          | '?'                        any char (not '/' with literal separator)
+//          | '[' ( '!' | '^' )? ']'? ( char | char '-' char )* ']'
+//          | '{' glob ( ',' glob )* '}'
+//          | '\' char | char
+
+pub(crate) struct GlobParser {
+    stream: CharStream,
+    literal_separator: bool,
+    depth: usize, // braces open around the current position
+    nest_limit: usize,
+}
+
+// braces being parsed, the braces around them wait on a stack in parse_sequence
+struct BraceFrame {
+    start: usize,
+    outer: Vec<Ast>, // the sequence the braces are in
+    branches: Vec<Ast>,
+    branch_start: usize,
+}
+
+impl BraceFrame {
+    fn end_branch(&mut self, children: Vec<Ast>, end: usize) {
+        self.branches.push(Ast {
+            kind: AstKind::NonCaptureGroup,
+            children,
+            span: Span {
+                start: self.branch_start,
+                end,
+            },
+        });
+    }
+}
+
+impl GlobParser {
+    pub fn parse(pattern: &str, literal_separator: bool, nest_limit: usize) -> Result<Ast, Error> {
+        let mut parser = GlobParser {
+            stream: CharStream::new(pattern),
+            literal_separator,
+            depth: 0,
+            nest_limit,
+        };
+
+        let mut children = vec![make_position(PositionKind::SoL, parser.span_from(0))];
+        children.extend(parser.parse_sequence()?);
+        let end = parser.stream.pos();
+        children.push(make_position(PositionKind::EoL, parser.span_from(end)));
+
+        match parser.stream.peek() {
+            Some(c) => Err(parser.error(ErrorKind::Unexpected(*c))),
+            None => Ok(Ast {
+                kind: AstKind::CaptureGroup(0),
+                children,
+                span: parser.span_from(0),
+            }),
+        }
+    }
+
+    // braces open and close here rather than recurse, so deep braces can't overflow
+    // the stack
+    fn parse_sequence(&mut self) -> Result<Vec<Ast>, Error> {
+        let mut braces: Vec<BraceFrame> = vec![];
+        let mut children = vec![];
+
+        loop {
+            let start = self.stream.pos();
+            match self.stream.peek() {
+                Some(',') if !braces.is_empty() => {
+                    let brace = braces.last_mut().unwrap();
+                    brace.end_branch(std::mem::take(&mut children), start);
+                    self.stream.next();
+                    brace.branch_start = self.stream.pos();
+                }
+                Some('}') if !braces.is_empty() => {
+                    let mut brace = braces.pop().unwrap();
+                    brace.end_branch(std::mem::take(&mut children), start);
+                    self.stream.next();
+                    self.depth -= 1;
+
+                    children = brace.outer;
+                    let span = self.span_from(brace.start);
+                    children.push(Ast {
+                        kind: AstKind::NonCaptureGroup,
+                        children: vec![Ast {
+                            kind: AstKind::Union,
+                            children: brace.branches,
+                            span,
+                        }],
+                        span,
+                    });
+                }
+                Some('*') => {
+                    self.stream.next();
+                    children.push(self.parse_star(start));
+                }
+                Some('?') => {
+                    self.stream.next();
+                    children.push(self.make_any(self.span_from(start)));
+                }
+                Some('[') => {
+                    children.push(self.parse_bracket()?);
+                }
+                Some('{') => {
+                    let brace = self.parse_brace_open(std::mem::take(&mut children))?;
+                    braces.push(brace);
+                }
+                Some('\\') => {
+                    self.stream.next();
+                    match self.stream.next() {
+                        Some(c) => children.push(make_char(c, self.span_from(start))),
+                        None => {
+                            let kind = ErrorKind::UnexpectedEol("escaped char");
+                            return Err(Error::new(kind, self.span_from(start)));
+                        }
+                    }
+                }
+                Some(_) => {
+                    let c = self.stream.next().unwrap();
+                    children.push(make_char(c, self.span_from(start)));
+                }
+                None => {
+                    if let Some(brace) = braces.last() {
+                        return Err(self.unclosed("brace", brace.start, "{"));
+                    }
+                    break; // EOL, end loop
+                }
+            }
+        }
+
+        Ok(children)
+    }
+
+    fn parse_star(&mut self, start: usize) -> Ast {
+        if self.stream.next_if_eq(&'*').is_none() {
+            let span = self.span_from(start);
+            return make_star(self.make_any(span), span);
+        }
+
+        let span = self.span_from(start);
+        let any = make_star(
+            Ast {
+                kind: AstKind::Match(MatchKind::Any),
+                children: vec![],
+                span,
+            },
+            span,
+        );
+        let slash_start = self.stream.pos();
+        if self.stream.next_if_eq(&'/').is_none() {
+            return any;
+        }
+
+        // '**/' matches zero or more whole directories
+        let span = self.span_from(start);
+        Ast {
+            kind: AstKind::Option(GreedyKind::Greedy),
+            children: vec![Ast {
+                kind: AstKind::NonCaptureGroup,
+                children: vec![any, make_char('/', self.span_from(slash_start))],
+                span,
+            }],
+            span,
+        }
+    }
+
+    fn parse_bracket(&mut self) -> Result<Ast, Error> {
+        let bracket_start = self.stream.pos();
+        if self.stream.next_if_eq(&'[').is_none() {
+            return Err(self.error(ErrorKind::Want("set open token")));
+        }
+
+        let is_positive = self.stream.next_if(|c| *c == '!' || *c == '^').is_none();
+
+        let mut children = vec![];
+        let item_start = self.stream.pos();
+        if let Some(c) = self.stream.next_if_eq(&']') {
+            children.push(make_char(c, self.span_from(item_start))); // leading ']' is a literal
+        }
+
+        loop {
+            let item_start = self.stream.pos();
+            let start = match self.stream.next() {
+                Some(']') => break, // end loop
+                Some('\\') => self.stream.next(),
+                c => c,
+            };
+            let start = match start {
+                Some(c) => c,
+                None => return Err(self.unclosed("set", bracket_start, "[")),
+            };
+
+            let is_range = {
+                let mut stream = self.stream.clone();
+                stream.next() == Some('-') && !matches!(stream.next(), Some(']') | None)
+            };
+            if is_range {
+                self.stream.next();
+                let end = self.stream.next().unwrap();
+                if start > end {
+                    let kind = ErrorKind::InvalidRange(start, end);
+                    return Err(Error::new(kind, self.span_from(item_start)));
+                }
+                children.push(Ast {
+                    kind: AstKind::Match(MatchKind::Range(start, end)),
+                    children: vec![],
+                    span: self.span_from(item_start),
+                });
+            } else {
+                children.push(make_char(start, self.span_from(item_start)));
+            }
+        }
+        let span = self.span_from(bracket_start);
+
+        if is_positive {
+            return Ok(Ast {
+                kind: AstKind::IncludeSet,
+                children,
+                span,
+            });
+        }
+
+        if self.literal_separator {
+            children.push(make_char('/', span));
+        }
+        Ok(Ast {
+            kind: AstKind::ExcludeSet,
+            children,
+            span,
+        })
+    }
+
+    fn parse_brace_open(&mut self, outer: Vec<Ast>) -> Result<BraceFrame, Error> {
+        let start = self.stream.pos();
+        if self.stream.next_if_eq(&'{').is_none() {
+            return Err(self.error(ErrorKind::Want("brace open token")));
+        }
+        self.depth += 1;
+        if self.depth > self.nest_limit {
+            let kind = ErrorKind::NestTooDeep(self.nest_limit);
+            return Err(Error::new(kind, self.span_from(start)));
+        }
+
+        Ok(BraceFrame {
+            start,
+            outer,
+            branches: vec![],
+            branch_start: self.stream.pos(),
+        })
+    }
+
+    fn make_any(&self, span: Span) -> Ast {
+        if self.literal_separator {
+            return Ast {
+                kind: AstKind::ExcludeSet,
+                children: vec![make_char('/', span)],
+                span,
+            };
+        }
+
+        Ast {
+            kind: AstKind::Match(MatchKind::Any),
+            children: vec![],
+            span,
+        }
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span {
+            start,
+            end: self.stream.pos(),
+        }
+    }
+
+    // at the next char, or at EOL
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(kind, self.stream.next_span())
+    }
+
+    // the close token of what was opened at start is missing
+    fn unclosed(&self, what: &'static str, start: usize, open: &str) -> Error {
+        let opened = Span {
+            start,
+            end: start + open.len(),
+        };
+        self.error(ErrorKind::Unclosed(what)).opened(opened)
+    }
+}
+
+fn make_char(c: char, span: Span) -> Ast {
+    Ast {
+        kind: AstKind::Match(MatchKind::Char(c)),
+        children: vec![],
+        span,
+    }
+}
+
+fn make_star(ast: Ast, span: Span) -> Ast {
+    Ast {
+        kind: AstKind::Star(GreedyKind::Greedy),
+        children: vec![ast],
+        span,
+    }
+}
+
+fn make_position(pos: PositionKind, span: Span) -> Ast {
+    Ast {
+        kind: AstKind::Position(pos),
+        children: vec![],
+        span,
+    }
+}
@@ -0,0 +1,82 @@
+use super::{
+    ast::{AstKind, GreedyKind, MatchKind, PositionKind, Span},
+    error::{Error, ErrorKind},
+    Ast,
+};
+
+// SQL LIKE syntax, the whole text must match
+//
+// like = ( '%' | '_' | '\' char | char )*
+//        ('%' is any chars, '_' is any char)
+
+pub(crate) struct LikeParser;
+
+impl LikeParser {
+    pub fn parse(pattern: &str) -> Result<Ast, Error> {
+        let mut children = vec![Ast {
+            kind: AstKind::Position(PositionKind::SoL),
+            children: vec![],
+            span: Span { start: 0, end: 0 },
+        }];
+
+        let mut stream = pattern.char_indices();
+        while let Some((start, c)) = stream.next() {
+            let ast = match c {
+                '%' => Ast {
+                    kind: AstKind::Star(GreedyKind::Greedy),
+                    children: vec![Ast {
+                        kind: AstKind::Match(MatchKind::Any),
+                        children: vec![],
+                        span: Self::span(start, c),
+                    }],
+                    span: Self::span(start, c),
+                },
+                '_' => Ast {
+                    kind: AstKind::Match(MatchKind::Any),
+                    children: vec![],
+                    span: Self::span(start, c),
+                },
+                '\\' => match stream.next() {
+                    Some((pos, c)) => Ast {
+                        kind: AstKind::Match(MatchKind::Char(c)),
+                        children: vec![],
+                        span: Span {
+                            start,
+                            end: pos + c.len_utf8(),
+                        },
+                    },
+                    None => {
+                        let kind = ErrorKind::UnexpectedEol("escaped char");
+                        return Err(Error::new(kind, Self::span(start, c)));
+                    }
+                },
+                c => Ast {
+                    kind: AstKind::Match(MatchKind::Char(c)),
+                    children: vec![],
+                    span: Self::span(start, c),
+                },
+            };
+            children.push(ast);
+        }
+
+        let end = pattern.len();
+        children.push(Ast {
+            kind: AstKind::Position(PositionKind::EoL),
+            children: vec![],
+            span: Span { start: end, end },
+        });
+
+        Ok(Ast {
+            kind: AstKind::CaptureGroup(0),
+            children,
+            span: Span { start: 0, end },
+        })
+    }
+
+    fn span(start: usize, c: char) -> Span {
+        Span {
+            start,
+            end: start + c.len_utf8(),
+        }
+    }
+}
@@ -1,16 +1,19 @@
-use std::iter::Peekable;
+use std::collections::HashMap;
 use std::num::ParseIntError;
-use std::vec::IntoIter;
 
 use super::{
-    ast::{AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind},
+    ast::{AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind, Span},
+    class::ClassSet,
+    error::{Error, ErrorKind},
+    stream::CharStream,
     Ast,
 };
+use crate::RegexBuilder;
 
 const META_CHARS: [char; 15] = [
     '|', // union
     '*', // star
-    '+', // plus
+    '+', // plus or possessive
     '?', // option or non-greedy or parameter
     ',', // repeat range separator
     '-', // set range separator
@@ -22,237 +25,925 @@ const META_CHARS: [char; 15] = [
     '[', ']', // set brackets
 ];
 
+const SET_OPERATORS: [&str; 3] = [
+    "&&", // intersection
+    "--", // difference
+    "~~", // symmetric difference
+];
+
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if META_CHARS.contains(&c) || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 pub(crate) struct Parser {
-    stream: Peekable<IntoIter<char>>,
+    stream: CharStream,
     capture_id: usize,
+    capture_names: HashMap<String, usize>,
+    references: Vec<(usize, Span)>,
+    named_references: Vec<(String, usize, Span)>, // name, start of the call or conditional
+    case_insensitive: bool,
+    grapheme_dot: bool,
+    depth: usize, // groups and sets open around the current position
+    nest_limit: usize,
+    recovering: bool,   // keep going after the errors it can step over
+    errors: Vec<Error>, // the errors stepped over
 }
 
-impl Parser {
-    pub fn parse(pattern: &str) -> Result<Ast, String> {
-        let mut parser = Parser {
-            stream: pattern
-                .chars()
-                .collect::<Vec<char>>()
-                .into_iter()
-                .peekable(),
-            capture_id: 1,
+// a group being parsed. the groups around it wait on a stack in parse_root
+// rather than on the call stack, so a deep pattern can't overflow it
+struct GroupFrame {
+    kind: GroupKind,
+    start: usize,                  // at the '('
+    case_insensitive: bool,        // the flag outside, back at the close
+    reset: Option<(usize, usize)>, // (first, next) capture id of a branch reset
+    content_start: usize,
+    branch_start: usize,
+    branches: Vec<Ast>, // the branches before the current one
+    children: Vec<Ast>,
+    comments: Vec<Ast>,
+    ast: Option<Ast>,   // the last term, which may be quantified
+    quantifiers: usize, // stacked on ast, each is a level
+}
+
+enum GroupKind {
+    Group(AstKind),
+    Conditional(usize),
+}
+
+impl GroupFrame {
+    fn new(
+        kind: GroupKind,
+        start: usize,
+        case_insensitive: bool,
+        reset: Option<(usize, usize)>,
+        content_start: usize,
+    ) -> Self {
+        GroupFrame {
+            kind,
+            start,
+            case_insensitive,
+            reset,
+            content_start,
+            branch_start: content_start,
+            branches: vec![],
+            children: vec![],
+            comments: vec![],
+            ast: None,
+            quantifiers: 0,
+        }
+    }
+
+    fn push_term(&mut self, ast: Ast) {
+        Parser::push_node(
+            &mut self.children,
+            self.ast.replace(ast),
+            &mut self.comments,
+        );
+        self.quantifiers = 0;
+    }
+
+    fn end_branch(&mut self, end: usize) -> Ast {
+        Parser::push_node(&mut self.children, self.ast.take(), &mut self.comments);
+        self.quantifiers = 0;
+        return Ast {
+            kind: AstKind::NonCaptureGroup,
+            children: std::mem::take(&mut self.children),
+            span: Span {
+                start: self.branch_start,
+                end,
+            },
+        };
+    }
+
+    // a union in a group when there was a '|'
+    fn end_content(&mut self, end: usize) -> Ast {
+        let branch = self.end_branch(end);
+        if self.branches.is_empty() {
+            return branch; // started with the content
+        }
+
+        let mut branches = std::mem::take(&mut self.branches);
+        branches.push(branch);
+        let span = Span {
+            start: self.content_start,
+            end,
+        };
+        return Ast {
+            kind: AstKind::NonCaptureGroup,
+            children: vec![Ast {
+                kind: AstKind::Union,
+                children: branches,
+                span,
+            }],
+            span,
         };
+    }
+}
+
+// a set being parsed, the sets around it wait on a stack in parse_set
+struct SetFrame {
+    start: usize,
+    is_positive: bool,
+    lhs: Option<(ClassSet, SetOperator)>, // the items before the last operator
+    children: Vec<Ast>,
+    ast: Option<Ast>, // the last item, which may start a range
+}
+
+type SetOperator = fn(&ClassSet, &ClassSet) -> ClassSet;
+
+impl SetFrame {
+    fn push_item(&mut self, ast: Ast) {
+        if let Some(node) = self.ast.replace(ast) {
+            self.children.push(node);
+        }
+    }
+
+    fn end_items(&mut self) -> Vec<Ast> {
+        if let Some(node) = self.ast.take() {
+            self.children.push(node);
+        }
+        std::mem::take(&mut self.children)
+    }
+}
+
+impl Parser {
+    #[cfg(test)]
+    pub fn parse(pattern: &str) -> Result<Ast, Error> {
+        Self::parse_with(pattern, &RegexBuilder::new(pattern))
+    }
+
+    pub fn parse_with(pattern: &str, options: &RegexBuilder) -> Result<Ast, Error> {
+        Self::new(pattern, options).parse_root()
+    }
+
+    // parse on after the errors it can step over and return them all,
+    // the tree is None when one of them couldn't be stepped over
+    pub fn parse_recover(pattern: &str, options: &RegexBuilder) -> (Option<Ast>, Vec<Error>) {
+        let mut parser = Self::new(pattern, options);
+        parser.recovering = true;
+
+        match parser.parse_root() {
+            Ok(ast) => (Some(ast), parser.errors),
+            Err(err) => {
+                parser.errors.push(err);
+                (None, parser.errors)
+            }
+        }
+    }
 
-        let ast = parser.parse_concat()?;
-        match parser.stream.next() {
-            Some(c) => Err(format!("parse is failed: {}", c)),
-            None => Ok(Ast {
-                kind: AstKind::CaptureGroup(0),
-                children: ast.children,
-            }),
+    fn new(pattern: &str, options: &RegexBuilder) -> Self {
+        Parser {
+            stream: CharStream::new(pattern),
+            capture_id: 1,
+            capture_names: HashMap::new(),
+            references: vec![],
+            named_references: vec![],
+            case_insensitive: options.case_insensitive,
+            grapheme_dot: options.grapheme_dot,
+            depth: 0,
+            nest_limit: options.nest_limit,
+            recovering: false,
+            errors: vec![],
         }
     }
 
-    fn parse_concat(&mut self) -> Result<Ast, String> {
+    fn parse_root(&mut self) -> Result<Ast, Error> {
+        let root = AstKind::CaptureGroup(0);
+        let case_insensitive = self.case_insensitive;
+        let mut groups = vec![GroupFrame::new(
+            GroupKind::Group(root),
+            0,
+            case_insensitive,
+            None,
+            0,
+        )];
         let mut children = vec![];
-        let mut ast = None;
 
+        // groups open and close here, everything else is parsed in parse_term
         loop {
-            match self.stream.peek() {
-                Some('(') => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_group()?);
+            match self.stream.peek().copied() {
+                Some('(') if self.starts_with_group() => {
+                    let group = self.parse_group_open()?;
+                    groups.push(group);
                 }
-                Some('[') => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_set()?);
+                Some('|') => {
+                    let group = groups.last_mut().unwrap();
+                    self.parse_branch(group)?;
                 }
-                Some('{') => {
-                    if ast.is_none() {
-                        return Err(format!("ERROR: repeat target is empty"));
-                    }
-                    ast = Some(self.parse_repeat(ast.unwrap())?);
+                Some(')') | None if groups.len() > 1 => {
+                    let group = groups.pop().unwrap();
+                    let ast = self.parse_group_close(group)?;
+                    groups.last_mut().unwrap().push_term(ast);
                 }
-                Some('|') => {
-                    if ast.is_none() {
-                        return Err(format!("ERROR: union target is empty"));
-                    }
+                Some(c @ ')') => {
+                    // a ')' without its '(', step over it and parse the rest
+                    self.recover(self.error(ErrorKind::Unexpected(c)))?;
+                    let mut ast = groups[0].end_content(self.stream.pos());
+                    children.append(&mut ast.children);
+                    self.stream.next();
 
-                    children.push(ast.unwrap());
-                    let lhs = Ast {
-                        kind: AstKind::NonCaptureGroup,
-                        children,
-                    };
-                    children = vec![];
-
-                    ast = Some(self.parse_union(lhs)?);
-                }
-                Some('*') => {
-                    if ast.is_none() {
-                        return Err(format!("ERROR: star target is empty"));
-                    }
-                    ast = Some(self.parse_star(ast.unwrap())?);
+                    let kind = GroupKind::Group(AstKind::CaptureGroup(0));
+                    let start = self.stream.pos();
+                    groups[0] = GroupFrame::new(kind, 0, self.case_insensitive, None, start);
                 }
-                Some('+') => {
-                    if ast.is_none() {
-                        return Err(format!("ERROR: plus target is empty"));
-                    }
-                    ast = Some(self.parse_plus(ast.unwrap())?);
+                Some(_) => {
+                    let group = groups.last_mut().unwrap();
+                    self.parse_term(group)?;
                 }
-                Some('?') => {
-                    if ast.is_none() {
-                        return Err(format!("ERROR: option target is empty"));
-                    }
-                    ast = Some(self.parse_option(ast.unwrap())?);
+                None => {
+                    break; // EOL, end loop
                 }
-                Some('^') | Some('$') => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_position()?);
+            }
+        }
+
+        let mut ast = groups[0].end_content(self.stream.pos());
+        children.append(&mut ast.children);
+
+        let undefined: Vec<_> = self
+            .references
+            .iter()
+            .filter(|(id, _)| *id >= self.capture_id)
+            .map(|(id, span)| Error::new(ErrorKind::UndefinedGroup(id.to_string()), *span))
+            .collect();
+        for err in undefined {
+            self.recover(err)?;
+        }
+
+        let mut ast = Ast {
+            kind: AstKind::CaptureGroup(0),
+            children,
+            span: self.span_from(0),
+        };
+        self.resolve_names(&mut ast)?;
+
+        return Ok(ast);
+    }
+
+    // a name may be used before its group, the ids are filled in once all groups are known
+    fn resolve_names(&mut self, ast: &mut Ast) -> Result<(), Error> {
+        let mut ids = HashMap::new();
+        for (name, start, span) in std::mem::take(&mut self.named_references) {
+            match self.capture_names.get(&name) {
+                Some(id) => {
+                    ids.insert(start, *id);
                 }
-                Some('\\') => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_metachar()?);
+                None => {
+                    let kind = ErrorKind::UndefinedGroup(format!("'{}'", name));
+                    self.recover(Error::new(kind, span))?;
                 }
-                Some('.') => {
-                    if let Some(node) = ast {
-                        children.push(node);
+            }
+        }
+
+        let mut stack = vec![ast];
+        while let Some(ast) = stack.pop() {
+            match &mut ast.kind {
+                AstKind::Call(id) | AstKind::Conditional(id) => {
+                    if let Some(named_id) = ids.get(&ast.span.start) {
+                        *id = *named_id;
                     }
-                    ast = Some(self.parse_any()?);
                 }
-                Some(c) if META_CHARS.contains(c) => {
-                    break; // end loop
+                _ => {}
+            }
+            stack.extend(ast.children.iter_mut());
+        }
+
+        Ok(())
+    }
+
+    // a comment, flag group, quantifier or term, the group's ast is the last term which
+    // may be quantified
+    fn parse_term(&mut self, group: &mut GroupFrame) -> Result<(), Error> {
+        match self.stream.peek() {
+            Some('(') if self.starts_with("(?#") => {
+                let comment = self.parse_comment()?;
+                if group.ast.is_some() {
+                    group.comments.push(comment); // keep the term quantifiable
+                } else {
+                    group.children.push(comment);
                 }
-                Some(_) => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_char()?);
+            }
+            Some('(') if self.starts_with_flag_group() => {
+                self.parse_flag_group()?; // applies to the rest of the group
+            }
+            Some('{') | Some('*') | Some('+') | Some('?') => {
+                group.quantifiers += 1;
+                if self.depth + group.quantifiers > self.nest_limit {
+                    return Err(self.error(ErrorKind::NestTooDeep(self.nest_limit)));
                 }
-                None => {
-                    break; // EOL, end loop
+                group.ast = Some(self.parse_quantifier(group.ast.take())?);
+            }
+            _ => {
+                for term in self.parse_terms()? {
+                    group.push_term(term); // only the last term is quantified
                 }
             }
         }
+        Ok(())
+    }
 
-        if let Some(node) = ast {
-            children.push(node);
+    fn parse_quantifier(&mut self, ast: Option<Ast>) -> Result<Ast, Error> {
+        match self.stream.peek() {
+            Some('{') => {
+                let lhs = self.quantifier_target(ast, "repeat")?;
+                self.parse_repeat(lhs)
+            }
+            Some('*') => {
+                let lhs = self.quantifier_target(ast, "star")?;
+                self.parse_star(lhs)
+            }
+            Some('+') => {
+                let lhs = self.quantifier_target(ast, "plus")?;
+                self.parse_plus(lhs)
+            }
+            _ => {
+                let lhs = self.quantifier_target(ast, "option")?;
+                self.parse_option(lhs)
+            }
+        }
+    }
+
+    // one term, or none for a lone '\E', or all the chars of a '\Q..\E' quote
+    fn parse_terms(&mut self) -> Result<Vec<Ast>, Error> {
+        let term_start = self.stream.pos();
+        let ast = match self.stream.peek() {
+            Some('(') => self.parse_call()?,
+            Some('[') => self.parse_set()?,
+            Some('^') | Some('$') => self.parse_position()?,
+            Some('\\') => {
+                if self.starts_with("\\Q") {
+                    let quoted = self.parse_quote()?;
+                    return Ok(quoted.into_iter().map(|ast| self.fold_case(ast)).collect());
+                } else if self.next_if_str("\\E") {
+                    return Ok(vec![]); // '\E' without '\Q' is ignored
+                }
+
+                let kind = if self.next_if_str("\\G") {
+                    AstKind::Position(PositionKind::SoS)
+                } else if self.next_if_str("\\K") {
+                    AstKind::ResetStart
+                } else if self.next_if_str("\\X") {
+                    AstKind::Match(MatchKind::Grapheme)
+                } else {
+                    let metachar = self.parse_metachar()?;
+                    return Ok(vec![self.fold_case(metachar)]);
+                };
+                Ast {
+                    kind,
+                    children: vec![],
+                    span: self.span_from(term_start),
+                }
+            }
+            Some('.') => self.parse_any()?,
+            Some(c) if META_CHARS.contains(c) => {
+                // a stray close token or separator, recovery takes it as a literal
+                self.recover(self.error(ErrorKind::Unexpected(*c)))?;
+                let char = self.parse_char()?;
+                self.fold_case(char)
+            }
+            _ => {
+                let char = self.parse_char()?;
+                self.fold_case(char)
+            }
+        };
+        Ok(vec![ast])
+    }
+
+    // recovery repeats an empty group when there is nothing before the quantifier
+    fn quantifier_target(&mut self, ast: Option<Ast>, what: &'static str) -> Result<Ast, Error> {
+        if let Some(ast) = ast {
+            return Ok(ast);
         }
 
+        self.recover(self.error(ErrorKind::EmptyTarget(what)))?;
         return Ok(Ast {
             kind: AstKind::NonCaptureGroup,
-            children,
+            children: vec![],
+            span: self.span_from(self.stream.pos()),
         });
     }
 
-    fn parse_set_items(&mut self) -> Result<Vec<Ast>, String> {
-        let mut children = vec![];
-        let mut ast = None;
+    fn push_node(children: &mut Vec<Ast>, ast: Option<Ast>, comments: &mut Vec<Ast>) {
+        if let Some(node) = ast {
+            children.push(node);
+        }
+        children.append(comments); // comments follow the term they were written after
+    }
 
+    fn parse_comment(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
+        if !self.next_if_str("(?#") {
+            return Err(self.error(ErrorKind::Want("comment open token")));
+        }
+
+        let mut comment = String::new();
         loop {
-            match self.stream.peek() {
-                Some('\\') => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_metachar()?);
-                }
-                Some('-') => {
-                    if ast.is_none() {
-                        return Err(format!("ERROR: char-range start is empty"));
-                    }
-                    children.push(self.parse_char_range(ast.unwrap())?);
-                    ast = None;
+            match self.stream.next() {
+                Some(')') => break,
+                Some(c) => comment.push(c),
+                None => {
+                    self.recover(self.unclosed("comment", start, "(?#"))?;
+                    break; // the comment runs to EOL
                 }
-                Some(c) if META_CHARS.contains(c) => {
-                    break; // end loop
+            }
+        }
+
+        return Ok(Ast {
+            kind: AstKind::Comment(comment),
+            children: vec![],
+            span: self.span_from(start),
+        });
+    }
+
+    // the '(' of a group and what follows up to its content, the group is closed
+    // in parse_group_close
+    fn parse_group_open(&mut self) -> Result<GroupFrame, Error> {
+        let start = self.stream.pos();
+        if self.stream.next_if_eq(&'(').is_none() {
+            return Err(self.error(ErrorKind::Want("group open token")));
+        }
+
+        let case_insensitive = self.case_insensitive;
+        let (kind, reset) = if self.stream.next_if_eq(&'?').is_some() {
+            self.nest(start)?;
+            if self.stream.next_if_eq(&'(').is_some() {
+                let capture_id = self.parse_condition(start)?;
+                (GroupKind::Conditional(capture_id), None)
+            } else {
+                let (kind, is_branch_reset) = self.parse_group_option()?;
+                let reset = is_branch_reset.then_some((self.capture_id, self.capture_id));
+                (GroupKind::Group(kind), reset)
+            }
+        } else {
+            self.nest(start)?;
+            let capture_id = self.capture_id;
+            self.capture_id += 1;
+            (GroupKind::Group(AstKind::CaptureGroup(capture_id)), None)
+        };
+
+        let content_start = self.stream.pos();
+        return Ok(GroupFrame::new(
+            kind,
+            start,
+            case_insensitive,
+            reset,
+            content_start,
+        ));
+    }
+
+    // at the ')' of the group, or at EOL when it's unclosed
+    fn parse_group_close(&mut self, mut group: GroupFrame) -> Result<Ast, Error> {
+        let mut content = group.end_content(self.stream.pos());
+        if let Some((_, next)) = group.reset {
+            // continue after the highest number used by any alternative
+            self.capture_id = self.capture_id.max(next);
+        }
+        self.case_insensitive = group.case_insensitive; // flags end with the group
+        self.depth -= 1;
+
+        if self.stream.next_if_eq(&')').is_none() {
+            self.recover(self.unclosed("group", group.start, "("))?; // closed at EOL
+        }
+
+        let capture_id = match group.kind {
+            GroupKind::Group(kind) => {
+                return Ok(Ast {
+                    kind,
+                    children: std::mem::take(&mut content.children),
+                    span: self.span_from(group.start),
+                });
+            }
+            GroupKind::Conditional(capture_id) => capture_id,
+        };
+
+        let (yes, no) = match content.children.first().map(|child| &child.kind) {
+            Some(AstKind::Union) => {
+                let mut branches = std::mem::take(&mut content.children[0].children);
+                if branches.len() > 2 {
+                    return Err(Error::new(ErrorKind::TooManyBranches, branches[2].span));
                 }
-                Some(_) => {
-                    if let Some(node) = ast {
-                        children.push(node);
-                    }
-                    ast = Some(self.parse_char()?);
+
+                let no = branches.pop().unwrap();
+                let yes = branches.pop().unwrap();
+                (yes, no)
+            }
+            _ => (
+                Ast {
+                    kind: AstKind::NonCaptureGroup,
+                    children: std::mem::take(&mut content.children),
+                    span: content.span,
+                },
+                Ast {
+                    kind: AstKind::NonCaptureGroup,
+                    children: vec![],
+                    span: Span {
+                        start: content.span.end,
+                        end: content.span.end,
+                    },
+                },
+            ),
+        };
+
+        return Ok(Ast {
+            kind: AstKind::Conditional(capture_id),
+            children: vec![yes, no],
+            span: self.span_from(group.start),
+        });
+    }
+
+    // a '|' ends the current branch of the group
+    fn parse_branch(&mut self, group: &mut GroupFrame) -> Result<(), Error> {
+        if self.stream.peek() != Some(&'|') {
+            return Err(self.error(ErrorKind::Want("union token")));
+        }
+
+        let branch = group.end_branch(self.stream.pos());
+        group.branches.push(branch);
+        self.stream.next();
+
+        if let Some((first, next)) = &mut group.reset {
+            // each alternative of a branch reset numbers from the same id
+            *next = (*next).max(self.capture_id);
+            self.capture_id = *first;
+        }
+        group.branch_start = self.stream.pos();
+
+        Ok(())
+    }
+
+    // the part after '(?', the bool is true for a branch reset
+    fn parse_group_option(&mut self) -> Result<(AstKind, bool), Error> {
+        let option_start = self.stream.pos();
+        match self.stream.next() {
+            Some(':') => Ok((AstKind::NonCaptureGroup, false)),
+            Some('>') => Ok((AstKind::AtomicGroup, false)),
+            Some('|') => Ok((AstKind::NonCaptureGroup, true)),
+            Some('<') => Ok((self.parse_named_group()?, false)),
+            Some('P') => {
+                if self.stream.next_if_eq(&'<').is_none() {
+                    return Err(self.error(ErrorKind::Want("'<' after '?P'")));
                 }
-                None => {
-                    break; // EOL, end loop
+                Ok((self.parse_named_group()?, false))
+            }
+            Some(c @ ('i' | '-')) => {
+                self.case_insensitive = self.parse_flags(c);
+                if self.stream.next_if_eq(&':').is_none() {
+                    return Err(self.error(ErrorKind::Want("':' after group flags")));
                 }
+                Ok((AstKind::NonCaptureGroup, false))
             }
+            Some(c) => {
+                let kind = ErrorKind::Unsupported(format!("group option '{}'", c));
+                Err(self.error_from(kind, option_start))
+            }
+            None => Err(self.error(ErrorKind::UnexpectedEol("group option"))),
         }
+    }
 
-        if let Some(node) = ast {
-            children.push(node);
+    // a '(' that opens a group, not a comment, a flag group or a call
+    fn starts_with_group(&self) -> bool {
+        !self.starts_with("(?#") && !self.starts_with_flag_group() && !self.starts_with_call()
+    }
+
+    fn starts_with_call(&self) -> bool {
+        let mut stream = self.stream.clone().skip(2);
+        let is_call =
+            matches!(stream.next(), Some(c) if c == 'R' || c == '&' || c.is_ascii_digit());
+        self.starts_with("(?") && is_call
+    }
+
+    fn starts_with_flag_group(&self) -> bool {
+        let mut stream = self.stream.clone().skip(2);
+        let mut flags = 0;
+        loop {
+            match stream.next() {
+                Some('i') | Some('-') => flags += 1,
+                Some(')') => return self.starts_with("(?") && flags > 0,
+                _ => return false,
+            }
+        }
+    }
+
+    fn parse_flag_group(&mut self) -> Result<(), Error> {
+        let start = self.stream.pos();
+        if !self.next_if_str("(?") {
+            return Err(self.error(ErrorKind::Want("flag group open token")));
         }
 
-        return Ok(children);
+        match self.stream.next() {
+            Some(c @ ('i' | '-')) => self.case_insensitive = self.parse_flags(c),
+            _ => return Err(self.error(ErrorKind::Want("group flags"))),
+        }
+
+        if self.stream.next_if_eq(&')').is_none() {
+            return Err(self.unclosed("group", start, "("));
+        }
+
+        return Ok(());
     }
 
-    fn parse_group(&mut self) -> Result<Ast, String> {
-        if self.stream.next_if_eq(&'(').is_none() {
-            return Err(format!("ERROR: want group open token"));
+    // returns the case_insensitive flag after applying 'i' and '-i'
+    fn parse_flags(&mut self, first: char) -> bool {
+        let mut case_insensitive = self.case_insensitive;
+        let mut is_enable = true;
+
+        let mut flag = Some(first);
+        while let Some(c) = flag {
+            match c {
+                '-' => is_enable = false,
+                _ => case_insensitive = is_enable,
+            }
+            flag = self.stream.next_if(|c| *c == 'i' || *c == '-');
+        }
+
+        case_insensitive
+    }
+
+    fn fold_case(&self, ast: Ast) -> Ast {
+        if !self.case_insensitive {
+            return ast;
         }
 
-        let mut capture_id = 0;
-        if self.stream.next_if_eq(&'?').is_some() {
-            if self.stream.next_if_eq(&':').is_none() {
-                return match self.stream.next() {
-                    Some(c) => Err(format!("ERROR: want ':', get '{}'", c)),
-                    None => Err(format!("ERROR: want ':', get EOL")),
+        if let AstKind::Match(MatchKind::Char(c)) = ast.kind {
+            let set = ClassSet::new(vec![(c, c)]).case_fold();
+            if set.ranges() != [(c, c)] {
+                return Ast {
+                    kind: AstKind::IncludeSet,
+                    children: set.to_asts(ast.span),
+                    span: ast.span,
                 };
             }
-        } else {
-            capture_id = self.capture_id;
-            self.capture_id += 1;
         }
 
-        let ast = self.parse_concat()?;
+        ast
+    }
+
+    fn parse_named_group(&mut self) -> Result<AstKind, Error> {
+        let start = self.stream.pos();
+        let name = self.parse_group_name()?;
+        let capture_id = self.capture_id;
+        if self
+            .capture_names
+            .get(&name)
+            .is_some_and(|id| *id != capture_id)
+        {
+            // a branch reset may give the same name to the same number
+            let span = Span {
+                start,
+                end: start + name.len(),
+            };
+            return Err(Error::new(ErrorKind::DuplicateName(name), span));
+        }
+
+        self.capture_id += 1;
+        self.capture_names.insert(name, capture_id);
+
+        return Ok(AstKind::CaptureGroup(capture_id));
+    }
+
+    fn parse_group_name(&mut self) -> Result<String, Error> {
+        let mut name = String::new();
+        while let Some(c) = self.stream.next_if(|c| c.is_alphanumeric() || *c == '_') {
+            name.push(c);
+        }
+
+        if name.is_empty() {
+            return Err(self.error(ErrorKind::EmptyName));
+        }
+        if self.stream.next_if_eq(&'>').is_none() {
+            return Err(self.error(ErrorKind::Unclosed("group name")));
+        }
+
+        return Ok(name);
+    }
+
+    fn parse_call(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
+        if !self.next_if_str("(?") {
+            return Err(self.error(ErrorKind::Want("call open token")));
+        }
+
+        let capture_id = match self.stream.peek() {
+            Some('R') => {
+                self.stream.next();
+                0
+            }
+            Some('&') => {
+                self.stream.next();
+
+                let name_start = self.stream.pos();
+                let mut name = String::new();
+                while let Some(c) = self.stream.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                let span = self.span_from(name_start);
+                self.named_references.push((name, start, span));
+                usize::MAX // resolved at the end
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let id_start = self.stream.pos();
+                let id = self.parse_number()? as usize;
+                self.references.push((id, self.span_from(id_start)));
+                id
+            }
+            _ => return Err(self.error(ErrorKind::Want("group to call"))),
+        };
 
         if self.stream.next_if_eq(&')').is_none() {
-            return Err(format!("ERROR: want group close token"));
+            return Err(self.unclosed("group", start, "("));
         }
 
-        if capture_id == 0 {
-            return Ok(Ast {
-                kind: AstKind::NonCaptureGroup,
-                children: ast.children,
-            });
+        return Ok(Ast {
+            kind: AstKind::Call(capture_id),
+            children: vec![],
+            span: self.span_from(start),
+        });
+    }
+
+    // the '(..)' after '(?', the group to test
+    fn parse_condition(&mut self, start: usize) -> Result<usize, Error> {
+        let condition_start = self.stream.pos() - 1; // the '(' before the condition
+        let capture_id = if self.stream.next_if_eq(&'<').is_some() {
+            let name_start = self.stream.pos();
+            let name = self.parse_group_name()?;
+            let span = Span {
+                start: name_start,
+                end: name_start + name.len(),
+            };
+            self.named_references.push((name, start, span));
+            usize::MAX // resolved at the end
         } else {
-            return Ok(Ast {
-                kind: AstKind::CaptureGroup(capture_id),
-                children: ast.children,
-            });
+            let id_start = self.stream.pos();
+            let id = self.parse_number()? as usize;
+            if id == 0 {
+                return Err(self.error_from(ErrorKind::ConditionOnZero, id_start));
+            }
+            self.references.push((id, self.span_from(id_start)));
+            id
+        };
+
+        if self.stream.next_if_eq(&')').is_none() {
+            return Err(self.unclosed("condition", condition_start, "("));
+        }
+
+        return Ok(capture_id);
+    }
+
+    // nested sets open and close here
+    fn parse_set(&mut self) -> Result<Ast, Error> {
+        let mut sets = vec![self.parse_set_open()?];
+        loop {
+            let set = sets.last_mut().unwrap();
+            if self.starts_with_set_operator() {
+                self.parse_set_operator(set)?;
+                continue;
+            }
+
+            // '-' without a range start or end is a literal
+            let is_literal_hyphen = set.ast.is_none() || self.starts_with("-]");
+
+            match self.stream.peek() {
+                Some('\\') => {
+                    let item = self.parse_metachar()?;
+                    set.push_item(item);
+                }
+                Some('[') => {
+                    let set = self.parse_set_open()?;
+                    sets.push(set);
+                }
+                Some('-') if is_literal_hyphen => {
+                    let item = self.parse_char()?;
+                    set.push_item(item);
+                }
+                Some('-') => {
+                    if set.ast.as_ref().is_some_and(Self::is_set) {
+                        return Err(self.error(ErrorKind::InvalidRangeStart));
+                    }
+                    let range = self.parse_char_range(set.ast.take().unwrap())?;
+                    set.children.push(range);
+                }
+                Some(']') | None => {
+                    let set = sets.pop().unwrap();
+                    let ast = self.parse_set_close(set)?;
+                    match sets.last_mut() {
+                        Some(outer) => outer.push_item(ast),
+                        None => return Ok(ast),
+                    }
+                }
+                Some(_) => {
+                    let item = self.parse_char()?;
+                    set.push_item(item);
+                }
+            }
         }
     }
 
-    fn parse_set(&mut self) -> Result<Ast, String> {
+    fn parse_set_open(&mut self) -> Result<SetFrame, Error> {
+        let start = self.stream.pos();
         if self.stream.next_if_eq(&'[').is_none() {
-            return Err(format!("ERROR: want set open token"));
+            return Err(self.error(ErrorKind::Want("set open token")));
         }
+        self.nest(start)?;
 
         let is_positive = self.stream.next_if_eq(&'^').is_none();
-        let children = self.parse_set_items()?;
+        let mut ast = None;
+        if self.stream.peek() == Some(&']') {
+            ast = Some(self.parse_char()?); // leading ']' is a literal
+        }
+
+        return Ok(SetFrame {
+            start,
+            is_positive,
+            lhs: None,
+            children: vec![],
+            ast,
+        });
+    }
+
+    // the items so far are the left side of the operator
+    fn parse_set_operator(&mut self, set: &mut SetFrame) -> Result<(), Error> {
+        let mut lhs = ClassSet::from_asts(&set.end_items())?;
+        if let Some((prev, operator)) = set.lhs.take() {
+            lhs = operator(&prev, &lhs);
+        }
+
+        let operator: SetOperator = if self.next_if_str("&&") {
+            ClassSet::intersect
+        } else if self.next_if_str("--") {
+            ClassSet::difference
+        } else if self.next_if_str("~~") {
+            ClassSet::symmetric_difference
+        } else {
+            return Err(self.error(ErrorKind::Want("set operator")));
+        };
+        set.lhs = Some((lhs, operator));
+
+        Ok(())
+    }
+
+    // at the ']' of the set, or at EOL when it's unclosed
+    fn parse_set_close(&mut self, mut set: SetFrame) -> Result<Ast, Error> {
+        let mut is_positive = set.is_positive;
+        let mut children = set.end_items();
+
+        let mut evaluated = None;
+        if let Some((lhs, operator)) = set.lhs.take() {
+            evaluated = Some(operator(&lhs, &ClassSet::from_asts(&children)?));
+        } else if children.iter().any(Self::is_set) {
+            evaluated = Some(ClassSet::from_asts(&children)?);
+        }
+
+        self.depth -= 1;
 
         if self.stream.next_if_eq(&']').is_none() {
-            return Err(format!("ERROR: want set close token"));
+            self.recover(self.unclosed("set", set.start, "["))?; // closed at EOL
+        }
+        let span = self.span_from(set.start);
+
+        if let Some(mut evaluated) = evaluated {
+            // evaluate nested sets and operators into plain ranges. the positive set
+            // is folded before it's negated, a negated set excludes every case
+            if self.case_insensitive {
+                evaluated = evaluated.case_fold();
+            }
+            if !is_positive {
+                evaluated = evaluated.negate();
+                is_positive = true;
+            }
+            children = evaluated.to_asts(span);
+        } else if self.case_insensitive {
+            children = ClassSet::from_asts(&children)?.case_fold().to_asts(span);
         }
 
         if is_positive {
             return Ok(Ast {
                 kind: AstKind::IncludeSet,
                 children,
+                span,
             });
         } else {
             return Ok(Ast {
                 kind: AstKind::ExcludeSet,
                 children,
+                span,
             });
         }
     }
 
-    fn parse_repeat(&mut self, lhs: Ast) -> Result<Ast, String> {
+    fn is_set(ast: &Ast) -> bool {
+        matches!(ast.kind, AstKind::IncludeSet | AstKind::ExcludeSet)
+    }
+
+    fn parse_repeat(&mut self, lhs: Ast) -> Result<Ast, Error> {
+        let start = self.stream.pos();
         if self.stream.next_if_eq(&'{').is_none() {
-            return Err(format!("ERROR: want repeat open token"));
+            return Err(self.error(ErrorKind::Want("repeat open token")));
         }
 
         let mut min = RepeatKind::Num(0);
@@ -278,123 +969,90 @@ impl Parser {
         }
 
         if self.stream.next_if_eq(&'}').is_none() {
-            return Err(format!("ERROR: want repeat close token"));
+            return Err(self.unclosed("repeat", start, "{"));
         }
 
         match (min, max) {
             (RepeatKind::Num(n), RepeatKind::Num(m)) if n > m => {
-                return Err(format!("ERROR: repeat range invalid {{{},{}}}", n, m));
+                return Err(self.error_from(ErrorKind::InvalidRepeat(n, m), start));
             }
             _ => { /* OK */ }
         }
 
-        let greedy = match self.stream.next_if_eq(&'?') {
-            Some(_) => GreedyKind::NonGreedy,
-            None => GreedyKind::Greedy,
-        };
+        let greedy = self.parse_greedy();
 
         return Ok(Ast {
             kind: AstKind::Repeat(min, max, greedy),
+            span: self.span_from(lhs.span.start),
             children: vec![lhs],
         });
     }
 
-    fn parse_union(&mut self, lhs: Ast) -> Result<Ast, String> {
-        if self.stream.next_if_eq(&'|').is_none() {
-            return Err(format!("ERROR: want union token"));
-        }
-
-        let mut rhs = self.parse_concat()?;
-        if rhs.children.is_empty() {
-            return Err(format!("ERROR: union right token is empty"));
-        }
-
-        let ast = match rhs.children[0].kind {
-            AstKind::Union => {
-                assert!(rhs.children.len() == 1);
-
-                let mut children = vec![lhs];
-                children.append(&mut rhs.children[0].children);
-
-                Ast {
-                    kind: AstKind::Union,
-                    children,
-                }
-            }
-            _ => Ast {
-                kind: AstKind::Union,
-                children: vec![lhs, rhs],
-            },
-        };
-
-        return Ok(ast);
-    }
-
-    fn parse_star(&mut self, lhs: Ast) -> Result<Ast, String> {
+    fn parse_star(&mut self, lhs: Ast) -> Result<Ast, Error> {
         if self.stream.next_if_eq(&'*').is_none() {
-            return Err(format!("ERROR: want star token"));
+            return Err(self.error(ErrorKind::Want("star token")));
         }
 
-        let greedy = match self.stream.next_if_eq(&'?') {
-            Some(_) => GreedyKind::NonGreedy,
-            None => GreedyKind::Greedy,
-        };
+        let greedy = self.parse_greedy();
 
         return Ok(Ast {
             kind: AstKind::Star(greedy),
+            span: self.span_from(lhs.span.start),
             children: vec![lhs],
         });
     }
 
-    fn parse_plus(&mut self, lhs: Ast) -> Result<Ast, String> {
+    fn parse_plus(&mut self, lhs: Ast) -> Result<Ast, Error> {
         if self.stream.next_if_eq(&'+').is_none() {
-            return Err(format!("ERROR: want plus token"));
+            return Err(self.error(ErrorKind::Want("plus token")));
         }
 
-        let greedy = match self.stream.next_if_eq(&'?') {
-            Some(_) => GreedyKind::NonGreedy,
-            None => GreedyKind::Greedy,
-        };
+        let greedy = self.parse_greedy();
 
         return Ok(Ast {
             kind: AstKind::Plus(greedy),
+            span: self.span_from(lhs.span.start),
             children: vec![lhs],
         });
     }
 
-    fn parse_option(&mut self, lhs: Ast) -> Result<Ast, String> {
+    fn parse_option(&mut self, lhs: Ast) -> Result<Ast, Error> {
         if self.stream.next_if_eq(&'?').is_none() {
-            return Err(format!("ERROR: want option token"));
+            return Err(self.error(ErrorKind::Want("option token")));
         }
 
-        let greedy = match self.stream.next_if_eq(&'?') {
-            Some(_) => GreedyKind::NonGreedy,
-            None => GreedyKind::Greedy,
-        };
+        let greedy = self.parse_greedy();
 
         return Ok(Ast {
             kind: AstKind::Option(greedy),
+            span: self.span_from(lhs.span.start),
             children: vec![lhs],
         });
     }
 
-    fn parse_position(&mut self) -> Result<Ast, String> {
+    fn parse_position(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
         let pos = match self.stream.next() {
             Some('^') => PositionKind::SoL,
             Some('$') => PositionKind::EoL,
-            Some(c) => return Err(format!("ERROR: unsupport position '{}'", c)),
-            None => return Err(format!("ERROR: want position token, get EOL")),
+            Some(c) => {
+                let kind = ErrorKind::Unsupported(format!("position '{}'", c));
+                return Err(self.error_from(kind, start));
+            }
+            None => return Err(self.error(ErrorKind::UnexpectedEol("position token"))),
         };
 
         return Ok(Ast {
             kind: AstKind::Position(pos),
             children: vec![],
+            span: self.span_from(start),
         });
     }
 
-    fn parse_metachar(&mut self) -> Result<Ast, String> {
+    fn parse_metachar(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
         if self.stream.next_if_eq(&'\\').is_none() {
-            return Err(format!("ERROR: want \\ token"));
+            return Err(self.error(ErrorKind::Want("\\ token")));
         }
 
         match self.stream.next() {
@@ -402,61 +1060,110 @@ impl Parser {
                 return Ok(Ast {
                     kind: AstKind::Match(MatchKind::Char(c)),
                     children: vec![],
+                    span: self.span_from(start),
                 });
             }
-            Some(c) if c == '\\' => {
+            Some('\\') => {
                 return Ok(Ast {
                     kind: AstKind::Match(MatchKind::Char('\\')),
                     children: vec![],
+                    span: self.span_from(start),
                 });
             }
             Some(c) => {
-                return Err(format!("ERROR: unsupport control sequence '\\{}'", c));
+                // recovery takes an unknown escape as the escaped char
+                let kind = ErrorKind::Unsupported(format!("control sequence '\\{}'", c));
+                self.recover(self.error_from(kind, start))?;
+                return Ok(Ast {
+                    kind: AstKind::Match(MatchKind::Char(c)),
+                    children: vec![],
+                    span: self.span_from(start),
+                });
             }
             None => {
-                return Err(format!("ERROR: want control sequence, get EOL"));
+                // and a '\' at EOL as itself
+                let kind = ErrorKind::UnexpectedEol("control sequence");
+                self.recover(self.error_from(kind, start))?;
+                return Ok(Ast {
+                    kind: AstKind::Match(MatchKind::Char('\\')),
+                    children: vec![],
+                    span: self.span_from(start),
+                });
+            }
+        }
+    }
+
+    fn parse_quote(&mut self) -> Result<Vec<Ast>, Error> {
+        if !self.next_if_str("\\Q") {
+            return Err(self.error(ErrorKind::Want("\\Q token")));
+        }
+
+        let mut children = vec![];
+        while !self.next_if_str("\\E") {
+            let start = self.stream.pos();
+            match self.stream.next() {
+                Some(c) => children.push(Ast {
+                    kind: AstKind::Match(MatchKind::Char(c)),
+                    children: vec![],
+                    span: self.span_from(start),
+                }),
+                None => break, // missing '\E' quotes to EOL
             }
         }
+
+        return Ok(children);
     }
 
-    fn parse_any(&mut self) -> Result<Ast, String> {
+    fn parse_any(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
         if self.stream.next_if_eq(&'.').is_none() {
-            return Err(format!("ERROR: want . token"));
+            return Err(self.error(ErrorKind::Want(". token")));
+        }
+
+        if self.grapheme_dot {
+            return Ok(Ast {
+                kind: AstKind::Match(MatchKind::Grapheme),
+                children: vec![],
+                span: self.span_from(start),
+            });
         }
 
         return Ok(Ast {
             kind: AstKind::Match(MatchKind::Any),
             children: vec![],
+            span: self.span_from(start),
         });
     }
 
-    fn parse_char(&mut self) -> Result<Ast, String> {
+    fn parse_char(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
         match self.stream.next() {
             Some(c) => {
                 return Ok(Ast {
                     kind: AstKind::Match(MatchKind::Char(c)),
                     children: vec![],
+                    span: self.span_from(start),
                 });
             }
             None => {
-                return Err(format!("ERROR: want control sequence, get EOL"));
+                return Err(self.error(ErrorKind::UnexpectedEol("char")));
             }
         }
     }
 
-    fn parse_char_range(&mut self, lhs: Ast) -> Result<Ast, String> {
+    fn parse_char_range(&mut self, lhs: Ast) -> Result<Ast, Error> {
         if self.stream.next_if_eq(&'-').is_none() {
-            return Err(format!("ERROR: want char-range '-' token"));
+            return Err(self.error(ErrorKind::Want("char-range '-' token")));
         }
 
         let rhs = match self.stream.peek() {
             Some('\\') => self.parse_metachar()?,
-            Some(c) if META_CHARS.contains(c) => {
-                return Err(format!("ERROR: want char-range end, get {}", c));
+            Some('[') | Some(']') => {
+                return Err(self.error(ErrorKind::Want("char-range end")));
             }
             Some(_) => self.parse_char()?,
             None => {
-                return Err(format!("ERROR: want char-range end, get EOL"));
+                return Err(self.error(ErrorKind::UnexpectedEol("char-range end")));
             }
         };
 
@@ -473,21 +1180,95 @@ impl Parser {
         };
 
         if a > b {
-            return Err(format!("ERROR: invalid char-range [{}-{}]", a, b));
+            return Err(self.error_from(ErrorKind::InvalidRange(a, b), lhs.span.start));
         }
 
         return Ok(Ast {
             kind: AstKind::Match(MatchKind::Range(a, b)),
             children: vec![],
+            span: self.span_from(lhs.span.start),
         });
     }
 
-    fn parse_number(&mut self) -> Result<u32, String> {
+    fn parse_greedy(&mut self) -> GreedyKind {
+        match self.stream.next_if(|c| *c == '?' || *c == '+') {
+            Some('?') => GreedyKind::NonGreedy,
+            Some(_) => GreedyKind::Possessive,
+            None => GreedyKind::Greedy,
+        }
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span {
+            start,
+            end: self.stream.pos(),
+        }
+    }
+
+    fn starts_with(&self, token: &str) -> bool {
+        let mut stream = self.stream.clone();
+        token.chars().all(|c| stream.next_if_eq(&c).is_some())
+    }
+
+    fn starts_with_set_operator(&self) -> bool {
+        SET_OPERATORS.iter().any(|op| self.starts_with(op))
+    }
+
+    fn next_if_str(&mut self, token: &str) -> bool {
+        if !self.starts_with(token) {
+            return false;
+        }
+        for _ in token.chars() {
+            self.stream.next();
+        }
+        true
+    }
+
+    fn parse_number(&mut self) -> Result<u32, Error> {
+        let start = self.stream.pos();
         let mut num = String::new();
         while let Some(c) = self.stream.next_if(|c| c.is_ascii_digit()) {
             num.push(c);
         }
 
-        num.parse().map_err(|err: ParseIntError| err.to_string())
+        num.parse().map_err(|err: ParseIntError| {
+            self.error_from(ErrorKind::InvalidNumber(err.to_string()), start)
+        })
+    }
+
+    // one level deeper, an error past the limit
+    fn nest(&mut self, start: usize) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > self.nest_limit {
+            return Err(self.error_from(ErrorKind::NestTooDeep(self.nest_limit), start));
+        }
+        Ok(())
+    }
+
+    // in recovery mode the error is kept and parsing goes on, otherwise it's returned
+    fn recover(&mut self, err: Error) -> Result<(), Error> {
+        if !self.recovering {
+            return Err(err);
+        }
+        self.errors.push(err);
+        Ok(())
+    }
+
+    // at the next char, or at EOL
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(kind, self.stream.next_span())
+    }
+
+    fn error_from(&self, kind: ErrorKind, start: usize) -> Error {
+        Error::new(kind, self.span_from(start))
+    }
+
+    // the close token of what was opened at start is missing
+    fn unclosed(&self, what: &'static str, start: usize, open: &str) -> Error {
+        let opened = Span {
+            start,
+            end: start + open.len(),
+        };
+        self.error(ErrorKind::Unclosed(what)).opened(opened)
     }
 }
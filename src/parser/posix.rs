@@ -0,0 +1,533 @@
+use std::num::ParseIntError;
+
+use super::{
+    ast::{AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind, Span},
+    error::{Error, ErrorKind},
+    stream::CharStream,
+    Ast,
+};
+
+// POSIX basic (BRE) and extended (ERE) syntax
+//
+// ere       = branch ( '|' branch )*
+// bre       = branch
+// branch    = ( atom dupl* )*
+// atom(ERE) = '(' ere ')' | bracket | '.' | '^' | '$' | '\' char | char
+// atom(BRE) = '\(' bre '\)' | bracket | '.' | '^' | '$' | '\' char | char
+//             ('^' only at the start and '$' only at the end are anchors,
+//              '*' at the start is a char)
+// dupl(ERE) = '*' | '+' | '?' | '{' interval '}'
+// dupl(BRE) = '*' | '\{' interval '\}'
+// interval  = number | number ',' | number ',' number
+// bracket   = '[' '^'? ']'? ( item | item '-' item | '[:' class ':]' )* ']'
+// item      = char | '[.' char '.]' | '[=' char '=]'   ('\' is a char)
+
+const CHAR_CLASSES: [(&str, &[(char, char)]); 12] = [
+    ("alnum", &[('0', '9'), ('A', 'Z'), ('a', 'z')]),
+    ("alpha", &[('A', 'Z'), ('a', 'z')]),
+    ("blank", &[('\t', '\t'), (' ', ' ')]),
+    ("cntrl", &[('\0', '\x1f'), ('\x7f', '\x7f')]),
+    ("digit", &[('0', '9')]),
+    ("graph", &[('!', '~')]),
+    ("lower", &[('a', 'z')]),
+    ("print", &[(' ', '~')]),
+    ("punct", &[('!', '/'), (':', '@'), ('[', '`'), ('{', '~')]),
+    ("space", &[('\t', '\r'), (' ', ' ')]),
+    ("upper", &[('A', 'Z')]),
+    ("xdigit", &[('0', '9'), ('A', 'F'), ('a', 'f')]),
+];
+
+pub(crate) struct PosixParser {
+    stream: CharStream,
+    capture_id: usize,
+    is_extended: bool,
+    depth: usize, // groups open around the current position
+    nest_limit: usize,
+}
+
+// a group being parsed, the groups around it wait on a stack in parse_alternation
+struct GroupFrame {
+    start: usize, // at the '(' or '\('
+    capture_id: usize,
+    content_start: usize,
+    branch_start: usize,
+    branches: Vec<Ast>, // the ERE branches before the current one
+    children: Vec<Ast>,
+}
+
+impl GroupFrame {
+    fn new(start: usize, capture_id: usize, content_start: usize) -> Self {
+        GroupFrame {
+            start,
+            capture_id,
+            content_start,
+            branch_start: content_start,
+            branches: vec![],
+            children: vec![],
+        }
+    }
+}
+
+impl PosixParser {
+    pub fn parse(pattern: &str, is_extended: bool, nest_limit: usize) -> Result<Ast, Error> {
+        let mut parser = PosixParser {
+            stream: CharStream::new(pattern),
+            capture_id: 1,
+            is_extended,
+            depth: 0,
+            nest_limit,
+        };
+
+        let mut ast = parser.parse_alternation()?;
+        match parser.stream.peek() {
+            Some(c) => Err(parser.error(ErrorKind::Unexpected(*c))),
+            None => Ok(Ast {
+                kind: AstKind::CaptureGroup(0),
+                children: std::mem::take(&mut ast.children),
+                span: ast.span,
+            }),
+        }
+    }
+
+    // groups open and close here rather than recurse, so a deep pattern can't
+    // overflow the stack
+    fn parse_alternation(&mut self) -> Result<Ast, Error> {
+        let mut groups = vec![GroupFrame::new(0, 0, self.stream.pos())];
+
+        loop {
+            let is_root = groups.len() == 1;
+            let group = groups.last_mut().unwrap();
+            if self.is_extended && self.stream.peek() == Some(&'|') {
+                let branch = self.end_branch(group);
+                group.branches.push(branch);
+                self.stream.next();
+                group.branch_start = self.stream.pos();
+                continue;
+            }
+
+            let is_end = match self.stream.peek() {
+                None => true,
+                Some(')') => self.is_extended,
+                Some(_) => !self.is_extended && self.starts_with("\\)"),
+            };
+            if is_end && is_root {
+                return Ok(self.end_alternation(group));
+            }
+            if is_end {
+                let group = groups.pop().unwrap();
+                let ast = self.parse_group_close(group)?;
+                let ast = self.parse_duplications(ast)?;
+                groups.last_mut().unwrap().children.push(ast);
+                continue;
+            }
+
+            // '^' is the only thing that may come before a leading BRE '*'
+            let is_start = matches!(
+                group.children.as_slice(),
+                [] | [Ast {
+                    kind: AstKind::Position(PositionKind::SoL),
+                    ..
+                }]
+            );
+
+            if self.starts_with(self.group_open()) {
+                let group = self.parse_group_open()?;
+                groups.push(group);
+            } else if self.is_extended {
+                let atom = self.parse_ere_atom()?;
+                group.children.push(self.parse_duplications(atom)?);
+            } else {
+                let atom = self.parse_bre_atom(is_start, group.children.is_empty())?;
+                if matches!(atom.kind, AstKind::Position(_)) {
+                    group.children.push(atom); // BRE anchors can't be repeated
+                } else {
+                    group.children.push(self.parse_duplications(atom)?);
+                }
+            }
+        }
+    }
+
+    fn parse_group_open(&mut self) -> Result<GroupFrame, Error> {
+        let start = self.stream.pos();
+        if !self.next_if_str(self.group_open()) {
+            return Err(self.error(ErrorKind::Want("group open token")));
+        }
+        self.nest(start)?;
+
+        let capture_id = self.capture_id;
+        self.capture_id += 1;
+        Ok(GroupFrame::new(start, capture_id, self.stream.pos()))
+    }
+
+    fn parse_group_close(&mut self, mut group: GroupFrame) -> Result<Ast, Error> {
+        let mut ast = self.end_alternation(&mut group);
+        self.depth -= 1;
+
+        let close = if self.is_extended { ")" } else { "\\)" };
+        if !self.next_if_str(close) {
+            return Err(self.unclosed("group", group.start, self.group_open()));
+        }
+        Ok(Ast {
+            kind: AstKind::CaptureGroup(group.capture_id),
+            children: std::mem::take(&mut ast.children),
+            span: self.span_from(group.start),
+        })
+    }
+
+    fn group_open(&self) -> &'static str {
+        if self.is_extended {
+            "("
+        } else {
+            "\\("
+        }
+    }
+
+    fn end_branch(&self, group: &mut GroupFrame) -> Ast {
+        Ast {
+            kind: AstKind::NonCaptureGroup,
+            children: std::mem::take(&mut group.children),
+            span: self.span_from(group.branch_start),
+        }
+    }
+
+    // a union when there was a '|'
+    fn end_alternation(&self, group: &mut GroupFrame) -> Ast {
+        let branch = self.end_branch(group);
+        if group.branches.is_empty() {
+            return branch; // started with the content
+        }
+
+        let mut branches = std::mem::take(&mut group.branches);
+        branches.push(branch);
+        let span = self.span_from(group.content_start);
+        Ast {
+            kind: AstKind::NonCaptureGroup,
+            children: vec![Ast {
+                kind: AstKind::Union,
+                children: branches,
+                span,
+            }],
+            span,
+        }
+    }
+
+    fn parse_ere_atom(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
+        let is_interval = self.starts_with_interval("{");
+
+        match self.stream.peek() {
+            Some('*') | Some('+') | Some('?') => Err(self.error(ErrorKind::EmptyTarget("repeat"))),
+            Some('{') if is_interval => Err(self.error(ErrorKind::EmptyTarget("repeat"))),
+            Some('^') => {
+                self.stream.next();
+                Ok(self.make_position(PositionKind::SoL, start))
+            }
+            Some('$') => {
+                self.stream.next();
+                Ok(self.make_position(PositionKind::EoL, start))
+            }
+            _ => self.parse_common_atom(),
+        }
+    }
+
+    fn parse_bre_atom(&mut self, is_start: bool, is_first: bool) -> Result<Ast, Error> {
+        let start = self.stream.pos();
+        if self.starts_with("\\{") {
+            return Err(self.error(ErrorKind::EmptyTarget("repeat")));
+        }
+
+        match self.stream.peek() {
+            Some('*') if is_start => {
+                self.stream.next();
+                Ok(self.make_char('*', start))
+            }
+            Some('^') if is_first => {
+                self.stream.next();
+                Ok(self.make_position(PositionKind::SoL, start))
+            }
+            Some('$') => {
+                self.stream.next();
+                if self.stream.peek().is_none() || self.starts_with("\\)") {
+                    return Ok(self.make_position(PositionKind::EoL, start));
+                }
+                Ok(self.make_char('$', start))
+            }
+            Some('^') => {
+                self.stream.next();
+                Ok(self.make_char('^', start))
+            }
+            _ => self.parse_common_atom(),
+        }
+    }
+
+    fn parse_common_atom(&mut self) -> Result<Ast, Error> {
+        let start = self.stream.pos();
+        match self.stream.next() {
+            Some('[') => self.parse_bracket(start),
+            Some('.') => Ok(Ast {
+                kind: AstKind::Match(MatchKind::Any),
+                children: vec![],
+                span: self.span_from(start),
+            }),
+            Some('\\') => match self.stream.next() {
+                Some(c) if c.is_ascii_digit() => {
+                    let kind = ErrorKind::Unsupported(format!("back-reference '\\{}'", c));
+                    Err(self.error_from(kind, start))
+                }
+                Some(c) => Ok(self.make_char(c, start)),
+                None => Err(self.error_from(ErrorKind::UnexpectedEol("control sequence"), start)),
+            },
+            Some(c) => Ok(self.make_char(c, start)),
+            None => Err(self.error(ErrorKind::UnexpectedEol("atom"))),
+        }
+    }
+
+    fn parse_duplications(&mut self, atom: Ast) -> Result<Ast, Error> {
+        let mut ast = atom;
+        let mut count = 0;
+
+        loop {
+            let start = self.stream.pos();
+            let kind = if self.stream.next_if_eq(&'*').is_some() {
+                AstKind::Star(GreedyKind::Greedy)
+            } else if self.is_extended && self.stream.next_if_eq(&'+').is_some() {
+                AstKind::Plus(GreedyKind::Greedy)
+            } else if self.is_extended && self.stream.next_if_eq(&'?').is_some() {
+                AstKind::Option(GreedyKind::Greedy)
+            } else if self.is_extended && self.starts_with_interval("{") {
+                self.parse_interval("{", "}")?
+            } else if !self.is_extended && self.starts_with_interval("\\{") {
+                self.parse_interval("\\{", "\\}")?
+            } else {
+                break; // end loop
+            };
+
+            // each one is a level of the tree
+            count += 1;
+            if self.depth + count > self.nest_limit {
+                return Err(self.error_from(ErrorKind::NestTooDeep(self.nest_limit), start));
+            }
+
+            ast = Ast {
+                kind,
+                span: self.span_from(ast.span.start),
+                children: vec![ast],
+            };
+        }
+
+        Ok(ast)
+    }
+
+    fn parse_interval(&mut self, open: &str, close: &str) -> Result<AstKind, Error> {
+        let start = self.stream.pos();
+        self.next_if_str(open);
+
+        let min = self.parse_number()?;
+        let max = if self.stream.next_if_eq(&',').is_none() {
+            RepeatKind::Num(min) // pattern : {n}
+        } else if self.stream.peek().is_some_and(|c| c.is_ascii_digit()) {
+            RepeatKind::Num(self.parse_number()?) // pattern : {n,m}
+        } else {
+            RepeatKind::Infinity // pattern : {n,}
+        };
+
+        if !self.next_if_str(close) {
+            return Err(self.unclosed("repeat", start, open));
+        }
+
+        if let RepeatKind::Num(m) = max {
+            if min > m {
+                return Err(self.error_from(ErrorKind::InvalidRepeat(min, m), start));
+            }
+        }
+
+        Ok(AstKind::Repeat(
+            RepeatKind::Num(min),
+            max,
+            GreedyKind::Greedy,
+        ))
+    }
+
+    fn parse_bracket(&mut self, bracket_start: usize) -> Result<Ast, Error> {
+        let is_positive = self.stream.next_if_eq(&'^').is_none();
+
+        let mut children = vec![];
+        let item_start = self.stream.pos();
+        if let Some(c) = self.stream.next_if_eq(&']') {
+            children.push(self.make_char(c, item_start)); // leading ']' is a literal
+        }
+
+        loop {
+            let item_start = self.stream.pos();
+            if self.next_if_str("[:") {
+                children.extend(self.parse_char_class(item_start)?);
+                continue;
+            }
+
+            let start = match self.stream.peek() {
+                Some(']') => {
+                    self.stream.next();
+                    break; // end loop
+                }
+                Some(_) => self.parse_bracket_item()?,
+                None => return Err(self.unclosed("set", bracket_start, "[")),
+            };
+
+            // '-' right before the closing ']' is a literal
+            if self.starts_with("-") && !self.starts_with("-]") {
+                self.stream.next();
+                let end = self.parse_bracket_item()?;
+                if start > end {
+                    let kind = ErrorKind::InvalidRange(start, end);
+                    return Err(self.error_from(kind, item_start));
+                }
+                children.push(Ast {
+                    kind: AstKind::Match(MatchKind::Range(start, end)),
+                    children: vec![],
+                    span: self.span_from(item_start),
+                });
+            } else {
+                children.push(self.make_char(start, item_start));
+            }
+        }
+
+        if is_positive {
+            Ok(Ast {
+                kind: AstKind::IncludeSet,
+                children,
+                span: self.span_from(bracket_start),
+            })
+        } else {
+            Ok(Ast {
+                kind: AstKind::ExcludeSet,
+                children,
+                span: self.span_from(bracket_start),
+            })
+        }
+    }
+
+    fn parse_bracket_item(&mut self) -> Result<char, Error> {
+        let start = self.stream.pos();
+        for (open, close) in [("[.", ".]"), ("[=", "=]")] {
+            if self.next_if_str(open) {
+                // only single-char collating elements are known
+                let c = self.stream.next();
+                if !self.next_if_str(close) {
+                    let kind = ErrorKind::Unsupported("collating element".to_string());
+                    return Err(self.error_from(kind, start));
+                }
+                return c.ok_or_else(|| self.error(ErrorKind::Unclosed("set")));
+            }
+        }
+
+        match self.stream.next() {
+            Some(c) => Ok(c),
+            None => Err(self.error(ErrorKind::Unclosed("set"))),
+        }
+    }
+
+    fn parse_char_class(&mut self, start: usize) -> Result<Vec<Ast>, Error> {
+        let mut name = String::new();
+        while !self.next_if_str(":]") {
+            match self.stream.next() {
+                Some(c) => name.push(c),
+                None => return Err(self.unclosed("char-class", start, "[:")),
+            }
+        }
+
+        let span = self.span_from(start);
+        match CHAR_CLASSES.iter().find(|(class, _)| *class == name) {
+            Some((_, ranges)) => Ok(ranges
+                .iter()
+                .map(|(a, b)| Ast {
+                    kind: AstKind::Match(MatchKind::Range(*a, *b)),
+                    children: vec![],
+                    span,
+                })
+                .collect()),
+            None => Err(Error::new(ErrorKind::UnknownClass(name), span)),
+        }
+    }
+
+    fn make_char(&self, c: char, start: usize) -> Ast {
+        Ast {
+            kind: AstKind::Match(MatchKind::Char(c)),
+            children: vec![],
+            span: self.span_from(start),
+        }
+    }
+
+    fn make_position(&self, pos: PositionKind, start: usize) -> Ast {
+        Ast {
+            kind: AstKind::Position(pos),
+            children: vec![],
+            span: self.span_from(start),
+        }
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span {
+            start,
+            end: self.stream.pos(),
+        }
+    }
+
+    fn starts_with(&self, token: &str) -> bool {
+        let mut stream = self.stream.clone();
+        token.chars().all(|c| stream.next() == Some(c))
+    }
+
+    fn starts_with_interval(&self, open: &str) -> bool {
+        let mut stream = self.stream.clone().skip(open.chars().count());
+        self.starts_with(open) && stream.next().is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn next_if_str(&mut self, token: &str) -> bool {
+        if !self.starts_with(token) {
+            return false;
+        }
+        for _ in token.chars() {
+            self.stream.next();
+        }
+        true
+    }
+
+    fn parse_number(&mut self) -> Result<u32, Error> {
+        let start = self.stream.pos();
+        let mut num = String::new();
+        while let Some(c) = self.stream.next_if(|c| c.is_ascii_digit()) {
+            num.push(c);
+        }
+
+        num.parse().map_err(|err: ParseIntError| {
+            self.error_from(ErrorKind::InvalidNumber(err.to_string()), start)
+        })
+    }
+
+    // one level deeper, an error past the limit
+    fn nest(&mut self, start: usize) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > self.nest_limit {
+            return Err(self.error_from(ErrorKind::NestTooDeep(self.nest_limit), start));
+        }
+        Ok(())
+    }
+
+    // at the next char, or at EOL
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error::new(kind, self.stream.next_span())
+    }
+
+    fn error_from(&self, kind: ErrorKind, start: usize) -> Error {
+        Error::new(kind, self.span_from(start))
+    }
+
+    // the close token of what was opened at start is missing
+    fn unclosed(&self, what: &'static str, start: usize, open: &str) -> Error {
+        let opened = Span {
+            start,
+            end: start + open.len(),
+        };
+        self.error(ErrorKind::Unclosed(what)).opened(opened)
+    }
+}
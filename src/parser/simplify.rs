@@ -0,0 +1,236 @@
+use super::{
+    ast::{AstKind, MatchKind, Span},
+    Ast, ClassSet, Error,
+};
+
+// rewrite the tree into a smaller one that matches the same text with the same captures:
+//
+//   (?:a(?:bc))  => abc          groups in a sequence are spliced into it
+//   (?:a)*       => a*           groups of one item are the item
+//   [a-cb-d]     => [a-d]        overlapping ranges are merged
+//   [a]          => a
+//   a|b|[c-e]|fg => [a-e]|fg     adjacent single-char branches become a set
+//   abc|abd|x    => ab[cd]|x     adjacent branches with a fixed common prefix share it
+//
+// branches are never reordered, and a prefix is only factored out when it can match
+// in one way only, so the backtracking engines try everything in the same order.
+// a set made in code may hold what no set can, that's the only error
+pub(crate) fn simplify(ast: &Ast) -> Result<Ast, Error> {
+    // bottom up, the children are simple before their node is
+    ast.fold(|ast, children: Vec<Result<Ast, Error>>| {
+        simplify_node(ast, children.into_iter().collect::<Result<_, _>>()?)
+    })
+}
+
+fn simplify_node(ast: &Ast, children: Vec<Ast>) -> Result<Ast, Error> {
+    match &ast.kind {
+        AstKind::NonCaptureGroup => {
+            let mut children = splice(children);
+            if children.len() == 1 {
+                return Ok(children.pop().unwrap());
+            }
+            Ok(make(AstKind::NonCaptureGroup, children, ast.span))
+        }
+        AstKind::CaptureGroup(_) | AstKind::AtomicGroup => {
+            Ok(make(ast.kind.clone(), splice(children), ast.span))
+        }
+        AstKind::Union => simplify_union(children, ast.span),
+        AstKind::IncludeSet => {
+            let set = ClassSet::from_asts(&children)?;
+            if let [(a, b)] = set.ranges()[..] {
+                if a == b {
+                    return Ok(make(AstKind::Match(MatchKind::Char(a)), vec![], ast.span));
+                }
+            }
+            Ok(make(AstKind::IncludeSet, set.to_asts(ast.span), ast.span))
+        }
+        AstKind::ExcludeSet => {
+            let set = ClassSet::from_asts(&children)?;
+            Ok(make(AstKind::ExcludeSet, set.to_asts(ast.span), ast.span))
+        }
+        _ => Ok(make(ast.kind.clone(), children, ast.span)),
+    }
+}
+
+// the items of a sequence, nested sequences spliced in and comments dropped
+fn splice(asts: Vec<Ast>) -> Vec<Ast> {
+    let mut children = vec![];
+    for mut child in asts.into_iter() {
+        match child.kind {
+            AstKind::NonCaptureGroup => children.append(&mut child.children),
+            AstKind::Comment(_) => {}
+            _ => children.push(child),
+        }
+    }
+    children
+}
+
+// the branches of a union being factored, abc|abd => ab(?:c|d). the union of the
+// rests after a shared prefix is factored the same way, it waits on a stack
+struct UnionFrame {
+    seqs: Vec<(Vec<Ast>, Span)>,
+    span: Span,
+    i: usize,
+    result: Vec<Ast>,
+    prefix: Vec<Ast>, // shared by the branches i..next, whose rests are factored above
+    next: usize,
+}
+
+impl UnionFrame {
+    fn new(branches: Vec<Ast>, span: Span) -> Self {
+        let seqs = branches
+            .into_iter()
+            .map(|ast| {
+                let span = ast.span;
+                (into_seq(ast), span)
+            })
+            .collect();
+
+        UnionFrame {
+            seqs,
+            span,
+            i: 0,
+            result: vec![],
+            prefix: vec![],
+            next: 0,
+        }
+    }
+
+    // the rests of the next adjacent branches with a fixed common prefix, so the
+    // order stays. None when all branches are done
+    fn factor_next(&mut self) -> Option<Vec<Ast>> {
+        let seqs = &mut self.seqs;
+        while self.i < seqs.len() {
+            let i = self.i;
+            let first = seqs[i].0.first().filter(|ast| is_fixed(ast)).cloned();
+            let mut j = i + 1;
+            while j < seqs.len() && first.is_some() && seqs[j].0.first() == first.as_ref() {
+                j += 1;
+            }
+
+            if j - i < 2 {
+                let (seq, branch_span) = std::mem::take(&mut seqs[i]);
+                self.result.push(from_seq(seq, branch_span));
+                self.i += 1;
+                continue;
+            }
+
+            let group = &mut seqs[i..j];
+            let mut len = 1;
+            while group.iter().all(|(seq, _)| {
+                seq.len() > len && seq[len] == group[0].0[len] && is_fixed(&seq[len])
+            }) {
+                len += 1;
+            }
+
+            self.prefix = group[0].0[..len].to_vec();
+            self.next = j;
+            let rests = group
+                .iter_mut()
+                .map(|(seq, branch_span)| from_seq(seq.split_off(len), *branch_span))
+                .collect();
+            return Some(rests);
+        }
+        None
+    }
+}
+
+fn simplify_union(branches: Vec<Ast>, span: Span) -> Result<Ast, Error> {
+    let mut stack = vec![UnionFrame::new(branches, span)];
+    loop {
+        let frame = stack.last_mut().unwrap();
+        if let Some(rests) = frame.factor_next() {
+            let span = frame.span;
+            stack.push(UnionFrame::new(rests, span));
+            continue;
+        }
+
+        let frame = stack.pop().unwrap();
+        let mut branches = merge_single_chars(frame.result, frame.span)?;
+        let union = if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            make(AstKind::Union, branches, frame.span)
+        };
+
+        match stack.last_mut() {
+            Some(outer) => {
+                let mut children = std::mem::take(&mut outer.prefix);
+                children.push(union);
+                outer.result.push(from_seq(splice(children), outer.span));
+                outer.i = outer.next;
+            }
+            None => return Ok(union),
+        }
+    }
+}
+
+// a|b|[c-e] => [a-e], any branch taking exactly one char can go with its neighbors
+fn merge_single_chars(branches: Vec<Ast>, span: Span) -> Result<Vec<Ast>, Error> {
+    let mut result: Vec<Ast> = vec![];
+    let mut run: Vec<Ast> = vec![];
+
+    for branch in branches.into_iter() {
+        if is_single_char(&branch) {
+            run.push(branch);
+        } else {
+            flush_run(&mut run, &mut result, span)?;
+            result.push(branch);
+        }
+    }
+    flush_run(&mut run, &mut result, span)?;
+
+    Ok(result)
+}
+
+fn flush_run(run: &mut Vec<Ast>, result: &mut Vec<Ast>, span: Span) -> Result<(), Error> {
+    match run.len() {
+        0 => {}
+        1 => result.push(run.pop().unwrap()),
+        _ => {
+            let set = make(AstKind::IncludeSet, std::mem::take(run), span);
+            result.push(simplify(&set)?);
+        }
+    }
+    Ok(())
+}
+
+// matches in one way only, never backtracked into
+fn is_fixed(ast: &Ast) -> bool {
+    matches!(
+        ast.kind,
+        AstKind::Match(_) | AstKind::IncludeSet | AstKind::ExcludeSet | AstKind::Position(_)
+    )
+}
+
+fn is_single_char(ast: &Ast) -> bool {
+    matches!(
+        ast.kind,
+        AstKind::Match(MatchKind::Char(_))
+            | AstKind::Match(MatchKind::Range(_, _))
+            | AstKind::IncludeSet
+            | AstKind::ExcludeSet
+    )
+}
+
+fn into_seq(mut ast: Ast) -> Vec<Ast> {
+    match ast.kind {
+        AstKind::NonCaptureGroup => std::mem::take(&mut ast.children),
+        _ => vec![ast],
+    }
+}
+
+fn from_seq(mut seq: Vec<Ast>, span: Span) -> Ast {
+    if seq.len() == 1 {
+        return seq.pop().unwrap();
+    }
+    make(AstKind::NonCaptureGroup, seq, span)
+}
+
+fn make(kind: AstKind, children: Vec<Ast>, span: Span) -> Ast {
+    Ast {
+        kind,
+        children,
+        span,
+    }
+}
@@ -0,0 +1,63 @@
+use std::rc::Rc;
+
+use super::ast::Span;
+
+// chars of a pattern that remember their byte offsets, used like Peekable<Chars>
+#[derive(Clone)]
+pub(crate) struct CharStream {
+    chars: Rc<[(usize, char)]>, // shared, cloned to look ahead at every '(
+    index: usize,
+    len: usize,
+}
+
+impl CharStream {
+    pub fn new(pattern: &str) -> Self {
+        CharStream {
+            chars: pattern.char_indices().collect::<Vec<_>>().into(),
+            index: 0,
+            len: pattern.len(),
+        }
+    }
+
+    // byte offset of the next char, the pattern length at EOL
+    pub fn pos(&self) -> usize {
+        match self.chars.get(self.index) {
+            Some((pos, _)) => *pos,
+            None => self.len,
+        }
+    }
+
+    // span of the next char, empty at EOL
+    pub fn next_span(&self) -> Span {
+        let start = self.pos();
+        let end = start + self.peek().map_or(0, |c| c.len_utf8());
+        Span { start, end }
+    }
+
+    pub fn peek(&self) -> Option<&char> {
+        self.chars.get(self.index).map(|(_, c)| c)
+    }
+
+    pub fn next_if(&mut self, func: impl FnOnce(&char) -> bool) -> Option<char> {
+        match self.peek() {
+            Some(c) if func(c) => self.next(),
+            _ => None,
+        }
+    }
+
+    pub fn next_if_eq(&mut self, expected: &char) -> Option<char> {
+        self.next_if(|c| c == expected)
+    }
+}
+
+impl Iterator for CharStream {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek().copied();
+        if c.is_some() {
+            self.index += 1;
+        }
+        c
+    }
+}
@@ -1,7 +1,9 @@
 use super::ast::*;
 use super::*;
 
-fn run(pattern: &str) -> Result<Ast, String> {
+const NEST_LIMIT: usize = 250;
+
+fn run(pattern: &str) -> Result<Ast, Error> {
     Parser::parse(pattern)
 }
 
@@ -9,6 +11,7 @@ fn make_top(children: Vec<Ast>) -> Ast {
     Ast {
         kind: AstKind::CaptureGroup(0),
         children,
+        span: Span::default(),
     }
 }
 
@@ -16,11 +19,16 @@ fn make1(kind: AstKind) -> Ast {
     Ast {
         kind,
         children: vec![],
+        span: Span::default(),
     }
 }
 
 fn make2(kind: AstKind, children: Vec<Ast>) -> Ast {
-    Ast { kind, children }
+    Ast {
+        kind,
+        children,
+        span: Span::default(),
+    }
 }
 
 #[cfg(test)]
@@ -195,38 +203,1240 @@ fn noncapture_group() {
             ),
         ]));
 
-        assert_eq!(run(src), expect);
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a(?:bc(?:de)f)(?:gh)";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('b'))),
+                    make1(AstKind::Match(MatchKind::Char('c'))),
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![
+                            make1(AstKind::Match(MatchKind::Char('d'))),
+                            make1(AstKind::Match(MatchKind::Char('e'))),
+                        ],
+                    ),
+                    make1(AstKind::Match(MatchKind::Char('f'))),
+                ],
+            ),
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('g'))),
+                    make1(AstKind::Match(MatchKind::Char('h'))),
+                ],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+}
+
+#[test]
+fn atomic_group() {
+    {
+        let src = "a(?>bc)d";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::AtomicGroup,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('b'))),
+                    make1(AstKind::Match(MatchKind::Char('c'))),
+                ],
+            ),
+            make1(AstKind::Match(MatchKind::Char('d'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a(?<bc)";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn named_group() {
+    {
+        let src = "(?<x>a)(?P<y_1>b)";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::CaptureGroup(1),
+                vec![make1(AstKind::Match(MatchKind::Char('a')))],
+            ),
+            make2(
+                AstKind::CaptureGroup(2),
+                vec![make1(AstKind::Match(MatchKind::Char('b')))],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?<>a)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(?<x>a)(?<x>b)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(?Px>a)";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn conditional() {
+    {
+        let src = "(a)?(?(1)b|c)";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::Option(GreedyKind::Greedy),
+                vec![make2(
+                    AstKind::CaptureGroup(1),
+                    vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                )],
+            ),
+            make2(
+                AstKind::Conditional(1),
+                vec![
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                    ),
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![make1(AstKind::Match(MatchKind::Char('c')))],
+                    ),
+                ],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?<x>a)?(?(<x>)b)";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::Option(GreedyKind::Greedy),
+                vec![make2(
+                    AstKind::CaptureGroup(1),
+                    vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                )],
+            ),
+            make2(
+                AstKind::Conditional(1),
+                vec![
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                    ),
+                    make2(AstKind::NonCaptureGroup, vec![]),
+                ],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?(1)b)(a)";
+        assert_eq!(run(src).is_ok(), true);
+    }
+    {
+        let src = "(?(<x>)a|b)(?<x>x)";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::Conditional(1),
+                vec![
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                    ),
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                    ),
+                ],
+            ),
+            make2(
+                AstKind::CaptureGroup(1),
+                vec![make1(AstKind::Match(MatchKind::Char('x')))],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(a)(?(2)b)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(a)(?(0)b)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(?<x>a)(?(<y>)b)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(a)(?(1)b|c|d)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(a)(?(1b)";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn call() {
+    {
+        let src = "a(?R)?b";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::Option(GreedyKind::Greedy),
+                vec![make1(AstKind::Call(0))],
+            ),
+            make1(AstKind::Match(MatchKind::Char('b'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(a)(?1)";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::CaptureGroup(1),
+                vec![make1(AstKind::Match(MatchKind::Char('a')))],
+            ),
+            make1(AstKind::Call(1)),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?<x>a(?&x)?)";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::CaptureGroup(1),
+            vec![
+                make1(AstKind::Match(MatchKind::Char('a'))),
+                make2(
+                    AstKind::Option(GreedyKind::Greedy),
+                    vec![make1(AstKind::Call(1))],
+                ),
+            ],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?1)(a)";
+        assert_eq!(run(src).is_ok(), true);
+    }
+    {
+        let src = "(?&x)(?<x>a)";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Call(1)),
+            make2(
+                AstKind::CaptureGroup(1),
+                vec![make1(AstKind::Match(MatchKind::Char('a')))],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(a)(?2)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(?<x>a)(?&y)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(a)(?1";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn quote() {
+    {
+        let src = "a\\Q(.*)\\E+";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make1(AstKind::Match(MatchKind::Char('('))),
+            make1(AstKind::Match(MatchKind::Char('.'))),
+            make1(AstKind::Match(MatchKind::Char('*'))),
+            make2(
+                AstKind::Plus(GreedyKind::Greedy),
+                vec![make1(AstKind::Match(MatchKind::Char(')')))],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "\\Q[a\\b";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('['))),
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make1(AstKind::Match(MatchKind::Char('\\'))),
+            make1(AstKind::Match(MatchKind::Char('b'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a\\Q\\E*\\E";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::Star(GreedyKind::Greedy),
+            vec![make1(AstKind::Match(MatchKind::Char('a')))],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+}
+
+#[test]
+fn escape_meta() {
+    assert_eq!(escape("a.b"), "a\\.b");
+    assert_eq!(escape("(1+2)*3"), "\\(1\\+2\\)\\*3");
+    assert_eq!(escape("[^-]{1,}|$\\"), "\\[\\^\\-\\]\\{1\\,\\}\\|\\$\\\\");
+
+    let src = escape("x?|(y)");
+    let expect = Ok(make_top(
+        "x?|(y)"
+            .chars()
+            .map(|c| make1(AstKind::Match(MatchKind::Char(c))))
+            .collect(),
+    ));
+    assert_eq!(run(&src), expect);
+}
+
+#[test]
+fn comment() {
+    {
+        let src = "(?#head)a(?#many a)*b";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Comment("head".to_string())),
+            make2(
+                AstKind::Star(GreedyKind::Greedy),
+                vec![make1(AstKind::Match(MatchKind::Char('a')))],
+            ),
+            make1(AstKind::Comment("many a".to_string())),
+            make1(AstKind::Match(MatchKind::Char('b'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a|(?#(x|y)";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::Union,
+            vec![
+                make2(
+                    AstKind::NonCaptureGroup,
+                    vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                ),
+                make2(
+                    AstKind::NonCaptureGroup,
+                    vec![make1(AstKind::Comment("(x|y".to_string()))],
+                ),
+            ],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?#)*";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "a(?#b";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn posix_basic() {
+    let run = |src| PosixParser::parse(src, false, NEST_LIMIT);
+    {
+        let src = "^*a\\(b*\\)\\{2,\\}c+$";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Position(PositionKind::SoL)),
+            make1(AstKind::Match(MatchKind::Char('*'))),
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::Repeat(RepeatKind::Num(2), RepeatKind::Infinity, GreedyKind::Greedy),
+                vec![make2(
+                    AstKind::CaptureGroup(1),
+                    vec![make2(
+                        AstKind::Star(GreedyKind::Greedy),
+                        vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                    )],
+                )],
+            ),
+            make1(AstKind::Match(MatchKind::Char('c'))),
+            make1(AstKind::Match(MatchKind::Char('+'))),
+            make1(AstKind::Position(PositionKind::EoL)),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a^b$c|d";
+        let expect = Ok(make_top(
+            "a^b$c|d"
+                .chars()
+                .map(|c| make1(AstKind::Match(MatchKind::Char(c))))
+                .collect(),
+        ));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "\\(a";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "\\(a\\)\\1";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "a\\{3,2\\}";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn posix_extended() {
+    let run = |src| PosixParser::parse(src, true, NEST_LIMIT);
+    {
+        let src = "(a|b)+c{2}";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::Plus(GreedyKind::Greedy),
+                vec![make2(
+                    AstKind::CaptureGroup(1),
+                    vec![make2(
+                        AstKind::Union,
+                        vec![
+                            make2(
+                                AstKind::NonCaptureGroup,
+                                vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                            ),
+                            make2(
+                                AstKind::NonCaptureGroup,
+                                vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                            ),
+                        ],
+                    )],
+                )],
+            ),
+            make2(
+                AstKind::Repeat(RepeatKind::Num(2), RepeatKind::Num(2), GreedyKind::Greedy),
+                vec![make1(AstKind::Match(MatchKind::Char('c')))],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "[]a\\[:digit:]-]";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::IncludeSet,
+            vec![
+                make1(AstKind::Match(MatchKind::Char(']'))),
+                make1(AstKind::Match(MatchKind::Char('a'))),
+                make1(AstKind::Match(MatchKind::Char('\\'))),
+                make1(AstKind::Match(MatchKind::Range('0', '9'))),
+                make1(AstKind::Match(MatchKind::Char('-'))),
+            ],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "[^[.-.]-[=z=]]";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::ExcludeSet,
+            vec![make1(AstKind::Match(MatchKind::Range('-', 'z')))],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a{,b}";
+        let expect = Ok(make_top(
+            "a{,b}"
+                .chars()
+                .map(|c| make1(AstKind::Match(MatchKind::Char(c))))
+                .collect(),
+        ));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "*a";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "[[:word:]]";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "[a";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "a)";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn glob() {
+    let run = |src| GlobParser::parse(src, true, NEST_LIMIT);
+    {
+        let src = "*.{rs,md}";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Position(PositionKind::SoL)),
+            make2(
+                AstKind::Star(GreedyKind::Greedy),
+                vec![make2(
+                    AstKind::ExcludeSet,
+                    vec![make1(AstKind::Match(MatchKind::Char('/')))],
+                )],
+            ),
+            make1(AstKind::Match(MatchKind::Char('.'))),
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![make2(
+                    AstKind::Union,
+                    vec![
+                        make2(
+                            AstKind::NonCaptureGroup,
+                            vec![
+                                make1(AstKind::Match(MatchKind::Char('r'))),
+                                make1(AstKind::Match(MatchKind::Char('s'))),
+                            ],
+                        ),
+                        make2(
+                            AstKind::NonCaptureGroup,
+                            vec![
+                                make1(AstKind::Match(MatchKind::Char('m'))),
+                                make1(AstKind::Match(MatchKind::Char('d'))),
+                            ],
+                        ),
+                    ],
+                )],
+            ),
+            make1(AstKind::Position(PositionKind::EoL)),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "[!a-c]?";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Position(PositionKind::SoL)),
+            make2(
+                AstKind::ExcludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Range('a', 'c'))),
+                    make1(AstKind::Match(MatchKind::Char('/'))),
+                ],
+            ),
+            make2(
+                AstKind::ExcludeSet,
+                vec![make1(AstKind::Match(MatchKind::Char('/')))],
+            ),
+            make1(AstKind::Position(PositionKind::EoL)),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "[a";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "{a,b";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "a}";
+        assert_eq!(run(src).is_ok(), true);
+    }
+}
+
+#[test]
+fn like() {
+    let run = |src| LikeParser::parse(src);
+    {
+        let src = "a%_\\%";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Position(PositionKind::SoL)),
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::Star(GreedyKind::Greedy),
+                vec![make1(AstKind::Match(MatchKind::Any))],
+            ),
+            make1(AstKind::Match(MatchKind::Any)),
+            make1(AstKind::Match(MatchKind::Char('%'))),
+            make1(AstKind::Position(PositionKind::EoL)),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a\\";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn branch_reset() {
+    {
+        let src = "(?|(a)|(b)(c))(d)";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![make2(
+                    AstKind::Union,
+                    vec![
+                        make2(
+                            AstKind::NonCaptureGroup,
+                            vec![make2(
+                                AstKind::CaptureGroup(1),
+                                vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                            )],
+                        ),
+                        make2(
+                            AstKind::NonCaptureGroup,
+                            vec![
+                                make2(
+                                    AstKind::CaptureGroup(1),
+                                    vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                                ),
+                                make2(
+                                    AstKind::CaptureGroup(2),
+                                    vec![make1(AstKind::Match(MatchKind::Char('c')))],
+                                ),
+                            ],
+                        ),
+                    ],
+                )],
+            ),
+            make2(
+                AstKind::CaptureGroup(3),
+                vec![make1(AstKind::Match(MatchKind::Char('d')))],
+            ),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?|(?<x>a)|(?<x>b))";
+        assert_eq!(run(src).is_ok(), true);
+    }
+    {
+        let src = "(?|(?<x>a)|(b)(?<x>c))";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(?|(a)|(b))(?(2)c)";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn case_insensitive() {
+    {
+        let src = "(?i)k1";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Range('K', 'K'))),
+                    make1(AstKind::Match(MatchKind::Range('k', 'k'))),
+                    make1(AstKind::Match(MatchKind::Range('\u{212A}', '\u{212A}'))),
+                ],
+            ),
+            make1(AstKind::Match(MatchKind::Char('1'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a(?i:b)c";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![make2(
+                    AstKind::IncludeSet,
+                    vec![
+                        make1(AstKind::Match(MatchKind::Range('B', 'B'))),
+                        make1(AstKind::Match(MatchKind::Range('b', 'b'))),
+                    ],
+                )],
+            ),
+            make1(AstKind::Match(MatchKind::Char('c'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "((?i)a)a(?i)[^x-y](?-i)b";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::CaptureGroup(1),
+                vec![make2(
+                    AstKind::IncludeSet,
+                    vec![
+                        make1(AstKind::Match(MatchKind::Range('A', 'A'))),
+                        make1(AstKind::Match(MatchKind::Range('a', 'a'))),
+                    ],
+                )],
+            ),
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::ExcludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Range('X', 'Y'))),
+                    make1(AstKind::Match(MatchKind::Range('x', 'y'))),
+                ],
+            ),
+            make1(AstKind::Match(MatchKind::Char('b'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(?x)";
+        assert_eq!(run(src).is_err(), true);
+    }
+    {
+        let src = "(?i-)a";
+        assert_eq!(run(src).is_ok(), true);
+    }
+}
+
+#[test]
+fn grapheme() {
+    {
+        let src = "\\X+.";
+        let expect = Ok(make_top(vec![
+            make2(
+                AstKind::Plus(GreedyKind::Greedy),
+                vec![make1(AstKind::Match(MatchKind::Grapheme))],
+            ),
+            make1(AstKind::Match(MatchKind::Any)),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "[\\X]";
+        assert_eq!(run(src).is_err(), true);
+    }
+}
+
+#[test]
+fn reset_start() {
+    let src = "a\\Kb";
+    let expect = Ok(make_top(vec![
+        make1(AstKind::Match(MatchKind::Char('a'))),
+        make1(AstKind::ResetStart),
+        make1(AstKind::Match(MatchKind::Char('b'))),
+    ]));
+
+    assert_eq!(run(src), expect);
+}
+
+#[test]
+fn search_start() {
+    let src = "\\Ga|\\G";
+    let expect = Ok(make_top(vec![make2(
+        AstKind::Union,
+        vec![
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![
+                    make1(AstKind::Position(PositionKind::SoS)),
+                    make1(AstKind::Match(MatchKind::Char('a'))),
+                ],
+            ),
+            make2(
+                AstKind::NonCaptureGroup,
+                vec![make1(AstKind::Position(PositionKind::SoS))],
+            ),
+        ],
+    )]));
+
+    assert_eq!(run(src), expect);
+}
+
+#[test]
+fn span() {
+    fn spans(ast: &Ast, out: &mut Vec<(usize, usize)>) {
+        out.push((ast.span.start, ast.span.end));
+        ast.children.iter().for_each(|child| spans(child, out));
+    }
+    let run = |result: Result<Ast, Error>| {
+        let mut out = vec![];
+        spans(&result.unwrap(), &mut out);
+        out
+    };
+
+    assert_eq!(
+        run(Parser::parse("a(b|c)*")),
+        vec![
+            (0, 7),
+            (0, 1),
+            (1, 7),
+            (1, 6),
+            (2, 5),
+            (2, 3),
+            (2, 3),
+            (4, 5),
+            (4, 5)
+        ]
+    );
+    assert_eq!(
+        run(Parser::parse("é[x-z]+?\\.{2}")),
+        vec![(0, 14), (0, 2), (2, 9), (2, 7), (3, 6), (9, 14), (9, 11)]
+    );
+    assert_eq!(
+        run(Parser::parse("(a)(?(1)x|yz)")),
+        vec![
+            (0, 13),
+            (0, 3),
+            (1, 2),
+            (3, 13),
+            (8, 9),
+            (8, 9),
+            (10, 12),
+            (10, 11),
+            (11, 12)
+        ]
+    );
+    assert_eq!(
+        run(PosixParser::parse("a|b\\{2\\}", false, NEST_LIMIT)),
+        vec![(0, 8), (0, 1), (1, 2), (2, 8), (2, 3)]
+    );
+    assert_eq!(
+        run(GlobParser::parse("**/*.{rs,é}", false, NEST_LIMIT)),
+        vec![
+            (0, 12),
+            (0, 0),
+            (0, 3),
+            (0, 3),
+            (0, 2),
+            (0, 2),
+            (2, 3),
+            (3, 4),
+            (3, 4),
+            (4, 5),
+            (5, 12),
+            (5, 12),
+            (6, 8),
+            (6, 7),
+            (7, 8),
+            (9, 11),
+            (9, 11),
+            (12, 12)
+        ]
+    );
+    assert_eq!(
+        run(LikeParser::parse("a%\\_")),
+        vec![(0, 4), (0, 0), (0, 1), (1, 2), (1, 2), (2, 4), (4, 4)]
+    );
+}
+
+#[test]
+fn error() {
+    let render = |src| Parser::parse(src).unwrap_err().render(src);
+
+    assert_eq!(
+        render("a(bc"),
+        "ERROR: want group close token\n  a(bc\n   |  ^\n   unclosed group opened here"
+    );
+    assert_eq!(
+        render("ab)"),
+        "ERROR: unexpected ')'\n  ab)\n    ^\n  hint: did you mean `\\)`?"
+    );
+    assert_eq!(
+        render("é[z-a]"),
+        "ERROR: char-range is invalid z-a\n  é[z-a]\n    ^^^\n  hint: did you mean `a-z`?"
+    );
+    assert_eq!(
+        render("x(?(1)a)"),
+        "ERROR: reference to undefined group 1\n  x(?(1)a)\n      ^"
+    );
+
+    let hint = |src| Parser::parse(src).unwrap_err().hint(src);
+    assert_eq!(hint("*a"), Some("did you mean `\\*`?".to_string()));
+    assert_eq!(hint("a{3,1}"), Some("did you mean `{1,3}`?".to_string()));
+    assert_eq!(hint("a\\"), Some("did you mean `\\\\`?".to_string()));
+    assert_eq!(hint("(?<n>a)(?<n>b)"), None);
+
+    let err = PosixParser::parse("a{1", true, NEST_LIMIT).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Unclosed("repeat"));
+    assert_eq!(err.span, Span { start: 3, end: 3 });
+    assert_eq!(err.opened, Some(Span { start: 1, end: 2 }));
+
+    let err = GlobParser::parse("{a,b", false, NEST_LIMIT).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::Unclosed("brace"));
+    assert_eq!(err.opened, Some(Span { start: 0, end: 1 }));
+
+    let err = crate::RegexBuilder::new("a(?<>b)").check().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::EmptyName);
+    assert_eq!(err.to_string(), "ERROR: group name is empty");
+}
+
+#[test]
+fn recover() {
+    let run = |src| {
+        let (ast, errors) = Parser::parse_recover(src, &crate::RegexBuilder::new(src));
+        (
+            ast,
+            errors.into_iter().map(|err| err.kind).collect::<Vec<_>>(),
+        )
+    };
+    {
+        let src = "a\\dc[ab";
+        let expect = Some(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make1(AstKind::Match(MatchKind::Char('d'))),
+            make1(AstKind::Match(MatchKind::Char('c'))),
+            make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('a'))),
+                    make1(AstKind::Match(MatchKind::Char('b'))),
+                ],
+            ),
+        ]));
+        let errors = vec![
+            ErrorKind::Unsupported("control sequence '\\d'".to_string()),
+            ErrorKind::Unclosed("set"),
+        ];
+        assert_eq!(run(src), (expect, errors));
+        assert_eq!(Parser::parse(src).is_err(), true);
+    }
+    {
+        let src = "*a)b(c";
+        let expect = Some(make_top(vec![
+            make2(
+                AstKind::Star(GreedyKind::Greedy),
+                vec![make2(AstKind::NonCaptureGroup, vec![])],
+            ),
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make1(AstKind::Match(MatchKind::Char('b'))),
+            make2(
+                AstKind::CaptureGroup(1),
+                vec![make1(AstKind::Match(MatchKind::Char('c')))],
+            ),
+        ]));
+        let errors = vec![
+            ErrorKind::EmptyTarget("star"),
+            ErrorKind::Unexpected(')'),
+            ErrorKind::Unclosed("group"),
+        ];
+        assert_eq!(run(src), (expect, errors));
+    }
+    {
+        let src = "(?1)x(?2)";
+        let errors = vec![
+            ErrorKind::UndefinedGroup("1".to_string()),
+            ErrorKind::UndefinedGroup("2".to_string()),
+        ];
+        assert_eq!(run(src).1, errors);
+    }
+    {
+        let src = "a{2";
+        assert_eq!(run(src), (None, vec![ErrorKind::Unclosed("repeat")]));
+    }
+    {
+        let src = "a|b";
+        assert_eq!(run(src), (Parser::parse(src).ok(), vec![]));
+    }
+    {
+        let (ast, errors) = crate::RegexBuilder::new("[a")
+            .syntax(crate::Syntax::Glob)
+            .check_all();
+        assert_eq!(ast, None);
+        assert_eq!(errors.len(), 1);
+    }
+}
+
+#[test]
+fn visitor() {
+    #[derive(Default)]
+    struct Trace(Vec<String>);
+    impl Visitor for Trace {
+        fn visit_pre(&mut self, ast: &Ast) {
+            self.0.push(format!("+{:?}", ast.kind));
+        }
+        fn visit_post(&mut self, ast: &Ast) {
+            self.0.push(format!("-{:?}", ast.kind));
+        }
+    }
+
+    let mut trace = Trace::default();
+    crate::ast::parse("a(b)*").unwrap().walk(&mut trace);
+    assert_eq!(
+        trace.0,
+        vec![
+            "+CaptureGroup(0)",
+            "+Match(Char('a'))",
+            "-Match(Char('a'))",
+            "+Star(Greedy)",
+            "+CaptureGroup(1)",
+            "+Match(Char('b'))",
+            "-Match(Char('b'))",
+            "-CaptureGroup(1)",
+            "-Star(Greedy)",
+            "-CaptureGroup(0)",
+        ]
+    );
+
+    // the literal words and the capture groups of a pattern
+    #[derive(Default)]
+    struct Words {
+        words: Vec<String>,
+        word: String,
+        groups: Vec<usize>,
+    }
+    impl Visitor for Words {
+        fn visit_pre(&mut self, ast: &Ast) {
+            match ast.kind {
+                AstKind::Match(MatchKind::Char(c)) => return self.word.push(c),
+                AstKind::CaptureGroup(id) => self.groups.push(id),
+                _ => {}
+            }
+            self.visit_post(ast);
+        }
+        fn visit_post(&mut self, ast: &Ast) {
+            let is_char = matches!(ast.kind, AstKind::Match(MatchKind::Char(_)));
+            if !is_char && !self.word.is_empty() {
+                self.words.push(std::mem::take(&mut self.word));
+            }
+        }
+    }
+
+    let mut words = Words::default();
+    crate::ast::parse("foo(bar|(?<x>baz))+.qux")
+        .unwrap()
+        .walk(&mut words);
+    assert_eq!(words.words, vec!["foo", "bar", "baz", "qux"]);
+    assert_eq!(words.groups, vec![0, 1, 2]);
+}
+
+#[test]
+fn validate() {
+    // trees from the parser always hold what the engines count on
+    let patterns = [
+        "a(b|c)*d+?e{2,}",
+        "(?i)[a-z&&[^aeiou]]\\X.",
+        "(?<x>a)(?(<x>)b|c)(?&x)(?R)?",
+        "(?|(a)|(b))(?1)?",
+        "^(?>a+)\\G\\K$(?#note)",
+        "\\Q.*\\E[]a-]",
+    ];
+    for src in patterns {
+        let ast = Parser::parse(src).unwrap();
+        let options = crate::RegexBuilder::new(src);
+        assert_eq!(crate::ast::validate(&ast, &options), Ok(()), "{}", src);
+    }
+}
+
+#[test]
+fn deep_tree() {
+    // made in code a tree can be deeper than any pattern, nothing may recurse on it
+    let mut ast = crate::ast::literal("a");
+    for id in 1..=100_000 {
+        ast = crate::ast::group(id, ast);
     }
-    {
-        let src = "a(?:bc(?:de)f)(?:gh)";
-        let expect = Ok(make_top(vec![
-            make1(AstKind::Match(MatchKind::Char('a'))),
+    let ast = crate::ast::group(0, ast);
+
+    #[derive(Default)]
+    struct Count(usize, usize);
+    impl Visitor for Count {
+        fn visit_pre(&mut self, _: &Ast) {
+            self.0 += 1;
+        }
+        fn visit_post(&mut self, _: &Ast) {
+            self.1 += 1;
+        }
+    }
+    let mut count = Count::default();
+    ast.walk(&mut count);
+    assert_eq!((count.0, count.1), (100_003, 100_003));
+
+    assert_eq!(ast.can_be_empty(), false);
+    assert_eq!(ast.find_group(100_000).is_some(), true);
+    assert_eq!(ast.find_group(100_001).is_none(), true);
+
+    let mut options = crate::RegexBuilder::new("");
+    assert_eq!(crate::ast::validate(&ast, &options).is_err(), true);
+    options.nest_limit(200_000);
+    assert_eq!(crate::ast::validate(&ast, &options), Ok(()));
+    ast.teardown();
+
+    // a tree made in code is still moved apart like any struct
+    let Ast { kind, children, .. } = crate::ast::literal("ab");
+    assert_eq!(kind, AstKind::NonCaptureGroup);
+    assert_eq!(children.len(), 2);
+}
+
+#[test]
+fn simplify() {
+    let simple = |src: &str| super::simplify(&run(src).unwrap()).unwrap();
+
+    // already as small as it gets
+    for src in [
+        "abc", "a*", "[a-d]", "a", "a*b|a*c", "ab|xy|ac", "(a)|(b)", "(?>a)|b",
+    ] {
+        let ast = run(src).unwrap();
+        assert_eq!(
+            super::simplify(&ast).unwrap(),
+            super::simplify(&super::simplify(&ast).unwrap()).unwrap(),
+            "{}",
+            src
+        );
+    }
+    assert_eq!(simple("abc"), run("abc").unwrap());
+    assert_eq!(simple("a*"), run("a*").unwrap());
+    assert_eq!(simple("[a-d]"), run("[a-d]").unwrap());
+
+    assert_eq!(simple("(?:a(?:bc))"), simple("abc"));
+    assert_eq!(simple("(?:a)*"), simple("a*"));
+    assert_eq!(simple("(?#note)a(?:)"), simple("a"));
+    assert_eq!(simple("[a-cb-d]"), simple("[a-d]"));
+    assert_eq!(simple("[a]"), simple("a"));
+    assert_eq!(simple("(?:a|b|[c-e]|fg)"), simple("(?:[a-e]|fg)"));
+
+    let expect = make_top(vec![make2(
+        AstKind::Union,
+        vec![
             make2(
                 AstKind::NonCaptureGroup,
                 vec![
+                    make1(AstKind::Match(MatchKind::Char('a'))),
                     make1(AstKind::Match(MatchKind::Char('b'))),
-                    make1(AstKind::Match(MatchKind::Char('c'))),
                     make2(
-                        AstKind::NonCaptureGroup,
-                        vec![
-                            make1(AstKind::Match(MatchKind::Char('d'))),
-                            make1(AstKind::Match(MatchKind::Char('e'))),
-                        ],
+                        AstKind::IncludeSet,
+                        vec![make1(AstKind::Match(MatchKind::Range('c', 'd')))],
                     ),
-                    make1(AstKind::Match(MatchKind::Char('f'))),
-                ],
-            ),
-            make2(
-                AstKind::NonCaptureGroup,
-                vec![
-                    make1(AstKind::Match(MatchKind::Char('g'))),
-                    make1(AstKind::Match(MatchKind::Char('h'))),
                 ],
             ),
-        ]));
+            make1(AstKind::Match(MatchKind::Char('x'))),
+        ],
+    )]);
+    assert_eq!(simple("abc|abd|x"), expect);
 
-        assert_eq!(run(src), expect);
+    // the prefix is only shared when it can match in one way only
+    assert_eq!(simple("a*b|a*c"), run("a*b|a*c").unwrap());
+    assert_eq!(simple("ab|xy|ac"), run("ab|xy|ac").unwrap());
+}
+
+#[test]
+fn nest_limit() {
+    use crate::Syntax;
+
+    let parse = |src: &str, syntax, limit| {
+        let mut builder = crate::RegexBuilder::new(src);
+        builder.syntax(syntax).nest_limit(limit);
+        builder.parse()
+    };
+    let kind = |result: Result<Ast, Error>| result.map_err(|err| err.kind).map(Ast::teardown);
+    let too_deep = Err(ErrorKind::NestTooDeep(250));
+
+    // the parser stops before the stack runs out
+    let deep = "(".repeat(50_000);
+    let err = parse(&deep, Syntax::Default, 250).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NestTooDeep(250));
+    assert_eq!(err.span.start, 250);
+    let deep = "[".repeat(50_000);
+    assert_eq!(kind(parse(&deep, Syntax::Default, 250)), too_deep);
+    let deep = "(?:".repeat(50_000);
+    assert_eq!(kind(parse(&deep, Syntax::Default, 250)), too_deep);
+    let deep = "\\(".repeat(50_000);
+    assert_eq!(kind(parse(&deep, Syntax::PosixBasic, 250)), too_deep);
+    let deep = "(".repeat(50_000);
+    assert_eq!(kind(parse(&deep, Syntax::PosixExtended, 250)), too_deep);
+    let deep = "{".repeat(50_000);
+    assert_eq!(kind(parse(&deep, Syntax::Glob, 250)), too_deep);
+
+    // so do stacked quantifiers
+    let deep = format!("a{}", "{1}".repeat(300));
+    let err = parse(&deep, Syntax::Default, 250).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::NestTooDeep(250));
+    assert_eq!(err.span.start, 751);
+    let deep = format!("a{}", "*".repeat(50_000));
+    assert_eq!(kind(parse(&deep, Syntax::Default, 250)), too_deep);
+    let deep = format!("a{}", "*".repeat(300));
+    assert_eq!(kind(parse(&deep, Syntax::PosixExtended, 250)), too_deep);
+
+    // a raised limit parses deep patterns, nothing recurses per level
+    let nested =
+        |open: &str, close: &str| format!("{}a{}", open.repeat(50_000), close.repeat(50_000));
+    let cases = [
+        (nested("(", ")"), Syntax::Default),
+        (nested("(?:", ")"), Syntax::Default),
+        (format!("(a){}", nested("(?(1)", ")")), Syntax::Default),
+        (nested("(a|", ")"), Syntax::Default),
+        (nested("[", "]"), Syntax::Default),
+        (format!("a{}", "*".repeat(50_000)), Syntax::Default),
+        (nested("\\(", "\\)"), Syntax::PosixBasic),
+        (nested("(", ")"), Syntax::PosixExtended),
+        (format!("a{}", "*".repeat(50_000)), Syntax::PosixExtended),
+        (nested("{", "}"), Syntax::Glob),
+    ];
+    for (deep, syntax) in cases {
+        assert_eq!(kind(parse(&deep, syntax, 200_000)), Ok(()), "{:?}", syntax);
     }
+
+    // each group, set, union and quantifier is a level
+    assert_eq!(kind(parse("((a))", Syntax::Default, 2)), Ok(()));
+    assert_eq!(kind(parse("[a](a*)", Syntax::Default, 2)), Ok(()));
+    assert_eq!(
+        kind(parse("(((a)))", Syntax::Default, 2)),
+        Err(ErrorKind::NestTooDeep(2))
+    );
+    assert_eq!(
+        kind(parse("(a|b)", Syntax::Default, 2)),
+        Err(ErrorKind::NestTooDeep(2))
+    );
+    assert_eq!(
+        kind(parse("(a*)*", Syntax::Default, 2)),
+        Err(ErrorKind::NestTooDeep(2))
+    );
+
+    // branches are looped over, not nested
+    let wide = ["a"; 50_000].join("|");
+    let ast = parse(&wide, Syntax::Default, 250).unwrap();
+    assert_eq!(ast.children[0].children.len(), 50_000);
+
+    let mut builder = crate::RegexBuilder::new(&"(".repeat(300));
+    let (ast, errors) = builder.check_all();
+    assert_eq!(ast, None);
+    assert_eq!(errors.last().unwrap().kind, ErrorKind::NestTooDeep(250));
+    let (ast, errors) = builder.nest_limit(1000).check_all();
+    assert_eq!(ast.is_some(), true);
+    assert_eq!(errors.len(), 300); // each group is unclosed
 }
 
 #[test]
@@ -265,6 +1475,96 @@ fn union() {
     assert_eq!(run(src), expect);
 }
 
+#[test]
+fn empty_union() {
+    {
+        let src = "a|";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::Union,
+            vec![
+                make2(
+                    AstKind::NonCaptureGroup,
+                    vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                ),
+                make2(AstKind::NonCaptureGroup, vec![]),
+            ],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "|a";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::Union,
+            vec![
+                make2(AstKind::NonCaptureGroup, vec![]),
+                make2(
+                    AstKind::NonCaptureGroup,
+                    vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                ),
+            ],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "a||b";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::Union,
+            vec![
+                make2(
+                    AstKind::NonCaptureGroup,
+                    vec![make1(AstKind::Match(MatchKind::Char('a')))],
+                ),
+                make2(AstKind::NonCaptureGroup, vec![]),
+                make2(
+                    AstKind::NonCaptureGroup,
+                    vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                ),
+            ],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+}
+
+#[test]
+fn empty_group() {
+    {
+        let src = "a()";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(AstKind::CaptureGroup(1), vec![]),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "(|b)";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::CaptureGroup(1),
+            vec![make2(
+                AstKind::Union,
+                vec![
+                    make2(AstKind::NonCaptureGroup, vec![]),
+                    make2(
+                        AstKind::NonCaptureGroup,
+                        vec![make1(AstKind::Match(MatchKind::Char('b')))],
+                    ),
+                ],
+            )],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+    {
+        let src = "";
+        let expect = Ok(make_top(vec![]));
+
+        assert_eq!(run(src), expect);
+    }
+}
+
 #[cfg(test)]
 mod greedy {
     use super::*;
@@ -759,6 +2059,71 @@ mod non_greedy {
     }
 }
 
+#[cfg(test)]
+mod possessive {
+    use super::*;
+
+    #[test]
+    fn star() {
+        let src = "ab*+c";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::Star(GreedyKind::Possessive),
+                vec![make1(AstKind::Match(MatchKind::Char('b')))],
+            ),
+            make1(AstKind::Match(MatchKind::Char('c'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn plus() {
+        let src = "ab++c";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::Plus(GreedyKind::Possessive),
+                vec![make1(AstKind::Match(MatchKind::Char('b')))],
+            ),
+            make1(AstKind::Match(MatchKind::Char('c'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn option() {
+        let src = "ab?+c";
+        let expect = Ok(make_top(vec![
+            make1(AstKind::Match(MatchKind::Char('a'))),
+            make2(
+                AstKind::Option(GreedyKind::Possessive),
+                vec![make1(AstKind::Match(MatchKind::Char('b')))],
+            ),
+            make1(AstKind::Match(MatchKind::Char('c'))),
+        ]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn repeat() {
+        let src = "a{1,10}+";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::Repeat(
+                RepeatKind::Num(1),
+                RepeatKind::Num(10),
+                GreedyKind::Possessive,
+            ),
+            vec![make1(AstKind::Match(MatchKind::Char('a')))],
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+}
+
 #[cfg(test)]
 mod set {
     use super::*;
@@ -832,6 +2197,102 @@ mod set {
         }
     }
 
+    #[test]
+    fn literal() {
+        {
+            let src = "[-a]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('-'))),
+                    make1(AstKind::Match(MatchKind::Char('a'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[a-]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('a'))),
+                    make1(AstKind::Match(MatchKind::Char('-'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[]a]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char(']'))),
+                    make1(AstKind::Match(MatchKind::Char('a'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[^]-]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::ExcludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char(']'))),
+                    make1(AstKind::Match(MatchKind::Char('-'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[a^]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('a'))),
+                    make1(AstKind::Match(MatchKind::Char('^'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[a-c-e]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Range('a', 'c'))),
+                    make1(AstKind::Match(MatchKind::Char('-'))),
+                    make1(AstKind::Match(MatchKind::Char('e'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[.*+?]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                vec![
+                    make1(AstKind::Match(MatchKind::Char('.'))),
+                    make1(AstKind::Match(MatchKind::Char('*'))),
+                    make1(AstKind::Match(MatchKind::Char('+'))),
+                    make1(AstKind::Match(MatchKind::Char('?'))),
+                ],
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            assert_eq!(run("[]").is_err(), true);
+            assert_eq!(run("[^]").is_err(), true);
+            assert_eq!(run("[-]").is_err(), false);
+        }
+    }
+
     #[test]
     fn negative() {
         {
@@ -901,3 +2362,92 @@ mod set {
         }
     }
 }
+
+#[cfg(test)]
+mod set_operation {
+    use super::*;
+
+    fn make_ranges(ranges: &[(char, char)]) -> Vec<Ast> {
+        ranges
+            .iter()
+            .map(|(a, b)| make1(AstKind::Match(MatchKind::Range(*a, *b))))
+            .collect()
+    }
+
+    #[test]
+    fn nested() {
+        let src = "[a-c[x-z]d]";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::IncludeSet,
+            make_ranges(&[('a', 'd'), ('x', 'z')]),
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn intersection() {
+        let src = "[a-z&&[^b-y]]";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::IncludeSet,
+            make_ranges(&[('a', 'a'), ('z', 'z')]),
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn difference() {
+        {
+            let src = "[a-z--[aeiou]]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                make_ranges(&[('b', 'd'), ('f', 'h'), ('j', 'n'), ('p', 't'), ('v', 'z')]),
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+        {
+            let src = "[^a-z--b-y]";
+            let expect = Ok(make_top(vec![make2(
+                AstKind::IncludeSet,
+                make_ranges(&[
+                    ('\0', '`'),
+                    ('b', 'y'),
+                    ('{', '\u{D7FF}'),
+                    ('\u{E000}', char::MAX),
+                ]),
+            )]));
+
+            assert_eq!(run(src), expect);
+        }
+    }
+
+    #[test]
+    fn symmetric_difference() {
+        let src = "[abc~~bcd]";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::IncludeSet,
+            make_ranges(&[('a', 'a'), ('d', 'd')]),
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn left_to_right() {
+        let src = "[a-z--b-y&&a-m]";
+        let expect = Ok(make_top(vec![make2(
+            AstKind::IncludeSet,
+            make_ranges(&[('a', 'a')]),
+        )]));
+
+        assert_eq!(run(src), expect);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(run("[[a]-z]").is_err(), true);
+        assert_eq!(run("[a-z&&[b]").is_err(), true);
+    }
+}
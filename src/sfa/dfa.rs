@@ -1,12 +1,14 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::HashMap;
 
 use self::{builder::Builder, matcher::Matcher};
-use crate::sfa::Nfa;
+use crate::{ir::Look, parser::Ast, sfa::Nfa, RegexBuilder};
 
+#[allow(clippy::needless_return)]
 mod builder;
 mod matcher;
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests;
 
 pub struct Dfa {
@@ -17,20 +19,48 @@ pub struct Dfa {
 
 impl Dfa {
     pub fn new(pattern: &str) -> Result<Dfa, String> {
-        let nfa = Nfa::new(pattern)?;
-        let dfa = Builder::build(nfa);
+        RegexBuilder::new(pattern).build_dfa()
+    }
+
+    // compile a tree from the ast module, made in code or parsed, with the default options
+    pub fn from_ast(ast: &Ast) -> Result<Dfa, String> {
+        let nfa = Nfa::from_ast(ast)?;
+        Dfa::build(nfa, &RegexBuilder::new(""))
+    }
 
-        Ok(dfa)
+    pub(crate) fn build(nfa: Nfa, options: &RegexBuilder) -> Result<Dfa, String> {
+        Builder::build(nfa, options.size_limit)
     }
 
-    pub fn is_match<'a>(&self, str: &'a str) -> bool {
-        let mut matcher = Matcher::new(&self);
+    pub fn is_match(&self, str: &str) -> bool {
+        if self.nodes.is_empty() {
+            // pattern is not regular, no states were built
+            return self.nfa.is_match(str);
+        }
+
+        let mut matcher = Matcher::new(self);
         matcher.execute(str).is_some()
     }
 
     pub fn captures<'a>(&self, str: &'a str) -> Vec<&'a str> {
         self.nfa.captures(str)
     }
+
+    // search from the byte offset start, '\G' matches only there.
+    // returns the byte range of the leftmost-first match, like the Vm and Nfa,
+    // None when start is past the end or not on a char boundary
+    pub fn find_at(&self, str: &str, start: usize) -> Option<(usize, usize)> {
+        if !str.is_char_boundary(start) {
+            return None;
+        }
+
+        if self.nodes.is_empty() {
+            return self.nfa.find_at(str, start);
+        }
+
+        let mut matcher = Matcher::new(self);
+        matcher.find_at(str, start)
+    }
 }
 
 struct Node {
@@ -38,33 +68,34 @@ struct Node {
     is_match: bool,
 }
 
+// transitions on sorted, non-overlapping code point ranges, and on the positions
+// that hold, a bit for each of POSITIONS
 struct Transition {
-    table: Vec<IndexSet>,
-    sol_next_index: IndexSet,
-    eol_next_index: IndexSet,
+    ranges: Vec<(u32, u32, IndexSet)>,
+    position_next_index: Vec<IndexSet>,
 }
 
+const POSITIONS: [Look; 3] = [Look::SoL, Look::EoL, Look::SoS];
+
 impl Transition {
-    pub fn new(size: usize) -> Self {
-        let mut table = Vec::with_capacity(size);
-        for _ in 0..size {
-            table.push(IndexSet::default());
-        }
-        Transition {
-            table,
-            sol_next_index: IndexSet::default(),
-            eol_next_index: IndexSet::default(),
-        }
+    fn positions(holds: usize) -> Vec<Look> {
+        let bits = POSITIONS.iter().enumerate();
+        bits.filter(|(i, _)| holds & 1 << i != 0)
+            .map(|(_, look)| *look)
+            .collect()
     }
 
-    pub fn merge(&mut self, other: &Transition) {
-        assert_eq!(self.table.len(), other.table.len());
-        for i in 0..self.table.len() {
-            self.table[i] = &self.table[i] | &other.table[i];
+    pub fn next(&self, c: char) -> &IndexSet {
+        static EMPTY: IndexSet = IndexSet::new();
+
+        let c = c as u32;
+        let i = self.ranges.partition_point(|(_, b, _)| *b < c);
+        match self.ranges.get(i) {
+            Some((a, _, index)) if *a <= c => index,
+            _ => &EMPTY,
         }
-        self.sol_next_index = &self.sol_next_index | &other.sol_next_index;
-        self.eol_next_index = &self.eol_next_index | &other.eol_next_index;
     }
 }
 
-type IndexSet = BTreeSet<usize>;
+// nfa node ids, in the order the nfa tries them
+type IndexSet = Vec<usize>;
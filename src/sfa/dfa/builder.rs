@@ -1,37 +1,41 @@
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, VecDeque};
 
-use super::{Dfa, IndexSet, Node, Transition};
-use crate::sfa::nfa;
+use super::{Dfa, IndexSet, Node, Transition, POSITIONS};
+use crate::{ir::Look, parser::ClassSet, sfa::nfa};
 
 pub(crate) struct Builder {
     nfa: nfa::Nfa,
     dfa_nodes: Vec<Node>,
     dfa_indexmap: HashMap<IndexSet, usize>,
+    size_limit: usize,
 }
 
 impl Builder {
-    pub fn build(nfa: nfa::Nfa) -> Dfa {
+    pub fn build(nfa: nfa::Nfa, size_limit: usize) -> Result<Dfa, String> {
         let mut builder = Builder {
             nfa,
             dfa_nodes: Vec::new(),
             dfa_indexmap: HashMap::new(),
+            size_limit,
         };
 
-        builder.build_();
+        builder.build_()?;
 
-        return Dfa {
+        return Ok(Dfa {
             nfa: builder.nfa,
             nodes: builder.dfa_nodes,
             indexmap: builder.dfa_indexmap,
-        };
+        });
     }
 
-    fn build_(&mut self) {
+    fn build_(&mut self) -> Result<(), String> {
+        if !Self::is_supported(&self.nfa) {
+            return Ok(()); // leave matching to the nfa
+        }
+
         let mut q = VecDeque::new();
         {
-            let mut index = IndexSet::new();
-            index.insert(0);
-            index = self.resolve_empty_transition(&index);
+            let index = self.resolve_empty_transition(&[0], &[]);
             q.push_back(index);
         }
 
@@ -45,122 +49,161 @@ impl Builder {
 
             let is_match = index.contains(&1);
 
-            let mut trans = Transition::new(256);
-            for i in index.iter() {
-                let trans_map = self.build_trans_map(&self.nfa.nodes[*i], is_match);
-                trans.merge(&trans_map);
-            }
+            let trans = self.build_trans(&index);
 
-            let uniq_index_list: HashSet<_> = trans.table.iter().cloned().collect();
-            q.extend(uniq_index_list.into_iter());
-            if !trans.sol_next_index.is_empty() {
-                q.push_back(trans.sol_next_index.clone());
-            }
-            if !trans.eol_next_index.is_empty() {
-                q.push_back(trans.eol_next_index.clone());
-            }
+            q.extend(trans.ranges.iter().map(|(_, _, next)| next.clone()));
+            q.extend(trans.position_next_index.iter().cloned());
 
             self.dfa_indexmap.insert(index, self.dfa_nodes.len());
             self.dfa_nodes.push(Node { trans, is_match });
+
+            if self.dfa_nodes.len() > self.size_limit {
+                return Err(format!(
+                    "ERROR: compiled program is too large, over {} states",
+                    self.size_limit
+                ));
+            }
         }
+
+        Ok(())
     }
 
-    fn build_trans_map(&self, node: &nfa::Node, is_match: bool) -> Transition {
-        let mut trans = Transition::new(256);
+    fn is_supported(nfa: &nfa::Nfa) -> bool {
+        nfa.nodes.iter().enumerate().all(|(id, node)| {
+            node.nexts.iter().all(|edge| match edge.action {
+                nfa::EdgeAction::Atomic(_)
+                | nfa::EdgeAction::Call(_)
+                | nfa::EdgeAction::Return
+                | nfa::EdgeAction::IfCaptured(_)
+                | nfa::EdgeAction::IfNotCaptured(_)
+                | nfa::EdgeAction::MatchGrapheme => false,
+                nfa::EdgeAction::CaptureStart(0) => id == 0, // '\K' moves the match start
+                _ => true,
+            })
+        })
+    }
 
-        for edge in node.nexts.iter() {
-            if is_match && !edge.is_greedy {
-                continue;
-            }
+    fn build_trans(&self, index: &IndexSet) -> Transition {
+        let mut moves = vec![]; // (first, last, next_id)
+        let mut looks = vec![];
 
-            match &edge.action {
-                nfa::EdgeAction::Asap
-                | nfa::EdgeAction::CaptureStart(_)
-                | nfa::EdgeAction::CaptureEnd(_) => { /* nothing */ }
-                nfa::EdgeAction::Match(c) => {
-                    trans.table[*c as usize].insert(edge.next_id);
-                }
-                nfa::EdgeAction::MatchAny => {
-                    for indexset in trans.table.iter_mut() {
-                        indexset.insert(edge.next_id);
+        for i in index.iter() {
+            for edge in self.nfa.nodes[*i].nexts.iter() {
+                match &edge.action {
+                    nfa::EdgeAction::Asap
+                    | nfa::EdgeAction::CaptureStart(_)
+                    | nfa::EdgeAction::CaptureEnd(_)
+                    | nfa::EdgeAction::SaveProgress(_)
+                    | nfa::EdgeAction::CheckProgress(_) => { /* nothing */ }
+                    nfa::EdgeAction::Match(c) => {
+                        moves.push((*c as u32, *c as u32, edge.next_id));
                     }
-                }
-                nfa::EdgeAction::MatchIncludeSet(set) => {
-                    for m in set.iter() {
-                        match m {
-                            nfa::MatchSet::Char(c) => {
-                                trans.table[*c as usize].insert(edge.next_id);
-                            }
-                            nfa::MatchSet::Range(a, b) => {
-                                for c in *a..=*b {
-                                    trans.table[c as usize].insert(edge.next_id);
-                                }
-                            }
-                        }
+                    nfa::EdgeAction::MatchAny => {
+                        moves.push((0, char::MAX as u32, edge.next_id));
                     }
-                }
-                nfa::EdgeAction::MatchExcludeSet(set) => {
-                    let mut exclude_table = Transition::new(256);
-                    let mut next_indexset = IndexSet::new();
-
-                    // calc exclude transition-map
-                    for m in set.iter() {
-                        match m {
-                            nfa::MatchSet::Char(c) => {
-                                exclude_table.table[*c as usize].insert(edge.next_id);
-                                next_indexset.insert(edge.next_id);
-                            }
-                            nfa::MatchSet::Range(a, b) => {
-                                for c in *a..=*b {
-                                    exclude_table.table[c as usize].insert(edge.next_id);
-                                    next_indexset.insert(edge.next_id);
-                                }
-                            }
+                    nfa::EdgeAction::MatchIncludeSet(set) => {
+                        for (a, b) in Self::build_class(set).ranges() {
+                            moves.push((a as u32, b as u32, edge.next_id));
                         }
                     }
-
-                    // apply exclude transition-map
-                    for (i, indexset) in trans.table.iter_mut().enumerate() {
-                        let exclude_set = &exclude_table.table[i];
-                        *indexset = &*indexset | &(&next_indexset - exclude_set);
-                    }
-                }
-                nfa::EdgeAction::MatchSOL => {
-                    trans.sol_next_index.insert(edge.next_id);
-                }
-                nfa::EdgeAction::MatchEOL => {
-                    trans.eol_next_index.insert(edge.next_id);
+                    nfa::EdgeAction::Atomic(_)
+                    | nfa::EdgeAction::Call(_)
+                    | nfa::EdgeAction::Return
+                    | nfa::EdgeAction::IfCaptured(_)
+                    | nfa::EdgeAction::IfNotCaptured(_)
+                    | nfa::EdgeAction::MatchGrapheme => unreachable!(),
+                    nfa::EdgeAction::MatchSOL => looks.push(Look::SoL),
+                    nfa::EdgeAction::MatchEOL => looks.push(Look::EoL),
+                    nfa::EdgeAction::MatchSOS => looks.push(Look::SoS),
                 }
             }
         }
 
-        // resolve empty transition
-        for index in trans.table.iter_mut() {
-            *index = self.resolve_empty_transition(index);
+        // split the moves at every range boundary, so each piece has one next index
+        let mut bounds: Vec<u32> = moves.iter().flat_map(|(a, b, _)| [*a, *b + 1]).collect();
+        bounds.sort();
+        bounds.dedup();
+
+        let mut ranges: Vec<(u32, u32, IndexSet)> = vec![];
+        for bound in bounds.windows(2) {
+            let (a, b) = (bound[0], bound[1] - 1);
+
+            let next_index: IndexSet = moves
+                .iter()
+                .filter(|(first, last, _)| *first <= a && b <= *last)
+                .map(|(_, _, next_id)| *next_id)
+                .collect();
+            if next_index.is_empty() {
+                continue;
+            }
+
+            let next_index = self.resolve_empty_transition(&next_index, &[]);
+            match ranges.last_mut() {
+                Some(last) if last.1 + 1 == a && last.2 == next_index => last.1 = b,
+                _ => ranges.push((a, b, next_index)),
+            }
         }
-        trans.sol_next_index = self.resolve_empty_transition(&trans.sol_next_index);
-        trans.eol_next_index = self.resolve_empty_transition(&trans.eol_next_index);
 
-        trans
+        // the positions that hold at once are passed together, so the order they are
+        // taken in never changes the next index
+        let position_next_index = (0..1 << POSITIONS.len())
+            .map(|holds| {
+                let passed = Transition::positions(holds);
+                if !looks.iter().any(|look| passed.contains(look)) {
+                    return IndexSet::new();
+                }
+                self.resolve_empty_transition(index, &passed)
+            })
+            .collect();
+
+        Transition {
+            ranges,
+            position_next_index,
+        }
+    }
+
+    fn build_class(set: &[nfa::MatchSet]) -> ClassSet {
+        ClassSet::new(
+            set.iter()
+                .map(|m| match m {
+                    nfa::MatchSet::Char(c) => (*c, *c),
+                    nfa::MatchSet::Range(a, b) => (*a, *b),
+                })
+                .collect(),
+        )
     }
 
-    fn resolve_empty_transition(&self, index: &IndexSet) -> IndexSet {
-        let mut result_index = BTreeSet::new();
+    // the nodes reached without consuming, in the order the nfa would try them, through
+    // the passed positions too. the nodes after the submit node never win against its
+    // match, they are dropped
+    fn resolve_empty_transition(&self, index: &[usize], passed: &[Look]) -> IndexSet {
+        let mut result_index = IndexSet::new();
+        let mut visited = BTreeSet::new();
 
-        let mut q: VecDeque<_> = index.iter().collect();
-        while let Some(i) = q.pop_front() {
-            if !result_index.insert(*i) {
+        let mut stack: Vec<_> = index.iter().rev().copied().collect();
+        while let Some(i) = stack.pop() {
+            if !visited.insert(i) {
                 continue;
             }
+            result_index.push(i);
+            if i == 1 {
+                break; // submit
+            }
 
-            for edge in self.nfa.nodes[*i].nexts.iter() {
-                match edge.action {
+            for edge in self.nfa.nodes[i].nexts.iter().rev() {
+                let is_empty = match edge.action {
                     nfa::EdgeAction::Asap
                     | nfa::EdgeAction::CaptureStart(_)
-                    | nfa::EdgeAction::CaptureEnd(_) => {
-                        q.push_back(&edge.next_id);
-                    }
-                    _ => { /* nothing */ }
+                    | nfa::EdgeAction::CaptureEnd(_)
+                    | nfa::EdgeAction::SaveProgress(_)
+                    | nfa::EdgeAction::CheckProgress(_) => true,
+                    nfa::EdgeAction::MatchSOL => passed.contains(&Look::SoL),
+                    nfa::EdgeAction::MatchEOL => passed.contains(&Look::EoL),
+                    nfa::EdgeAction::MatchSOS => passed.contains(&Look::SoS),
+                    _ => false,
+                };
+                if is_empty {
+                    stack.push(edge.next_id);
                 }
             }
         }
@@ -1,7 +1,10 @@
-use super::Dfa;
+use std::iter::once;
+
+use super::{Dfa, IndexSet};
 
 pub(crate) struct Matcher<'a> {
     dfa: &'a Dfa,
+    search_index: usize, // where the search started, for '\G'
     start_index: i32,
     last_index: i32,
 }
@@ -10,6 +13,7 @@ impl<'a> Matcher<'a> {
     pub fn new(dfa: &'a Dfa) -> Self {
         Matcher {
             dfa,
+            search_index: 0,
             start_index: -1,
             last_index: -1,
         }
@@ -18,16 +22,25 @@ impl<'a> Matcher<'a> {
     fn reset(&mut self) {
         *self = Matcher {
             dfa: self.dfa,
+            search_index: self.search_index,
             start_index: -1,
             last_index: -1,
         }
     }
 
     pub fn execute<'b>(&mut self, str: &'b str) -> Option<&'b str> {
-        for i in 0..str.len() {
+        self.find_at(str, 0).map(|(s, e)| &str[s..e])
+    }
+
+    pub fn find_at(&mut self, str: &str, start: usize) -> Option<(usize, usize)> {
+        self.search_index = start;
+
+        // the end is a start too, an empty match can be there
+        let ends = once(str.len() - start);
+        for i in str[start..].char_indices().map(|(i, _)| i).chain(ends) {
             self.reset();
 
-            let result = self.execute_(str, i);
+            let result = self.execute_(str, start + i);
             if result.is_some() {
                 return result;
             }
@@ -35,7 +48,7 @@ impl<'a> Matcher<'a> {
         None
     }
 
-    fn execute_<'b>(&mut self, str: &'b str, sp: usize) -> Option<&'b str> {
+    fn execute_(&mut self, str: &str, sp: usize) -> Option<(usize, usize)> {
         self.start_index = sp as i32;
 
         let mut index = self.dfa.indexmap.iter().find(|v| *v.1 == 0).unwrap().0;
@@ -49,29 +62,35 @@ impl<'a> Matcher<'a> {
                 self.last_index = sp as i32;
             }
 
-            if sp == 0 && !node.trans.sol_next_index.is_empty() {
-                index = &node.trans.sol_next_index;
-                continue;
-            }
-            if sp == str.len() && !node.trans.eol_next_index.is_empty() {
-                index = &node.trans.eol_next_index;
+            // positions never consume, the ones that hold are passed at once
+            let trans = &node.trans;
+            let holds = [sp == 0, sp == str.len(), sp == self.search_index] // as in POSITIONS
+                .iter()
+                .enumerate()
+                .fold(0, |holds, (i, yes)| holds | (*yes as usize) << i);
+            if Self::is_grown(index, &trans.position_next_index[holds]) {
+                index = &trans.position_next_index[holds];
                 continue;
             }
             if sp >= str.len() {
                 break; // end while loop
             }
 
-            let c = str.chars().nth(sp).unwrap();
-            index = &node.trans.table[c as usize];
-            sp += 1;
+            let c = str[sp..].chars().next().unwrap();
+            index = trans.next(c);
+            sp += c.len_utf8();
         }
 
         if self.start_index <= self.last_index {
             let s = self.start_index as usize;
             let e = self.last_index as usize;
-            Some(&str[s..e])
+            Some((s, e))
         } else {
             None
         }
     }
+
+    fn is_grown(index: &IndexSet, next_index: &IndexSet) -> bool {
+        !next_index.is_empty() && next_index != index
+    }
 }
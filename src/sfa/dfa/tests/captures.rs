@@ -158,6 +158,56 @@ fn union() {
     assert_eq!(vm.captures("defz"), vec!["def"]);
 }
 
+#[test]
+fn atomic_group() {
+    {
+        let src = "a(?>(bc)|b)(c)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("abcc"), vec!["abcc", "bc", "c"]);
+        assert_eq!(vm.captures("abc"), Vec::<&str>::new());
+    }
+    {
+        let src = "(?>(a+))(b)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("aab"), vec!["aab", "aa", "b"]);
+        assert_eq!(vm.captures("zaabz"), vec!["aab", "aa", "b"]);
+        assert_eq!(vm.captures("aa"), Vec::<&str>::new());
+    }
+}
+
+#[test]
+fn empty_group() {
+    {
+        let src = "a()b";
+        let vm = Dfa::new(src).unwrap();
+
+        let str = "zab";
+        let caps = vm.captures(str);
+        assert_eq!(caps, vec!["ab", ""]);
+        assert_eq!(caps[1].as_ptr() as usize - str.as_ptr() as usize, 2);
+    }
+    {
+        let src = "a(b|)c";
+        let vm = Dfa::new(src).unwrap();
+
+        let str = "zac";
+        let caps = vm.captures(str);
+        assert_eq!(caps, vec!["ac", ""]);
+        assert_eq!(caps[1].as_ptr() as usize - str.as_ptr() as usize, 2);
+
+        assert_eq!(vm.captures("abc"), vec!["abc", "b"]);
+    }
+    {
+        let src = "(a|)*(b)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("aab"), vec!["aab", "a", "b"]);
+        assert_eq!(vm.captures("b"), vec!["b", "", "b"]);
+    }
+}
+
 #[cfg(test)]
 mod greedy {
     use super::*;
@@ -591,6 +641,48 @@ mod non_greedy {
     }
 }
 
+#[cfg(test)]
+mod possessive {
+    use super::*;
+
+    #[test]
+    fn star() {
+        let src = "(ab*+)(c)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("abbc"), vec!["abbc", "abb", "c"]);
+        assert_eq!(vm.captures("ac"), vec!["ac", "a", "c"]);
+        assert_eq!(vm.captures("abb"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn plus() {
+        let src = "(a++)(b)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("aab"), vec!["aab", "aa", "b"]);
+        assert_eq!(vm.captures("b"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn option() {
+        let src = "(a?+)(a)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("aa"), vec!["aa", "a", "a"]);
+        assert_eq!(vm.captures("za"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn repeat() {
+        let src = "(a{1,3}+)(a)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("aaaa"), vec!["aaaa", "aaa", "a"]);
+        assert_eq!(vm.captures("aaa"), Vec::<&str>::new());
+    }
+}
+
 #[cfg(test)]
 mod set {
     use super::*;
@@ -657,6 +749,15 @@ mod set {
         }
     }
 
+    #[test]
+    fn literal() {
+        let src = "([]-]+)([^]-])";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("-]-a"), vec!["-]-a", "-]-", "a"]);
+        assert_eq!(vm.captures("]]"), Vec::<&str>::new());
+    }
+
     #[test]
     fn negative() {
         {
@@ -720,6 +821,15 @@ mod set {
     }
 }
 
+#[test]
+fn set_operation() {
+    let src = "([a-z--[aeiou]]+)([aeiou])";
+    let vm = Dfa::new(src).unwrap();
+
+    assert_eq!(vm.captures("strap"), vec!["stra", "str", "a"]);
+    assert_eq!(vm.captures("aei"), Vec::<&str>::new());
+}
+
 #[test]
 fn pattern001() {
     {
@@ -749,3 +859,199 @@ fn pattern001() {
         assert_eq!(vm.captures("abc@def@example.com"), Vec::<&str>::new());
     }
 }
+
+#[test]
+fn conditional() {
+    {
+        let src = "(a)?(?(1)(b)|(c))";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("ab"), vec!["ab", "a", "b", ""]);
+        assert_eq!(vm.captures("c"), vec!["c", "", "", "c"]);
+        assert_eq!(vm.captures("ac"), vec!["c", "", "", "c"]);
+    }
+}
+
+#[test]
+fn call() {
+    {
+        let src = "(a|b(?1))(c)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("bbac"), vec!["bbac", "bba", "c"]);
+    }
+    {
+        let src = "a(?R)?b";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("zaaabbbz"), vec!["aaabbb"]);
+        assert_eq!(vm.captures("aaabb"), vec!["aabb"]);
+    }
+}
+
+#[test]
+fn branch_reset() {
+    {
+        let src = "(?|([0-9]+)\\-([0-9]+)|([0-9]+)/([0-9]+))";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("12-34"), vec!["12-34", "12", "34"]);
+        assert_eq!(vm.captures("56/78"), vec!["56/78", "56", "78"]);
+    }
+    {
+        let src = "(?|(a)|(b)(c)|(d(e|f)))(g)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("ag"), vec!["ag", "a", "", "g"]);
+        assert_eq!(vm.captures("bcg"), vec!["bcg", "b", "c", "g"]);
+        assert_eq!(vm.captures("dfg"), vec!["dfg", "df", "f", "g"]);
+    }
+}
+
+#[test]
+fn unicode() {
+    let src = "(é+)(.)";
+    let vm = Dfa::new(src).unwrap();
+
+    assert_eq!(vm.captures("cafééx"), vec!["ééx", "éé", "x"]);
+    assert_eq!(vm.captures("ℝé🦀"), vec!["é🦀", "é", "🦀"]);
+}
+
+#[test]
+fn grapheme() {
+    let src = "(\\X)(\\X)$";
+    let vm = Dfa::new(src).unwrap();
+
+    assert_eq!(vm.captures("ok👍🏽"), vec!["k👍🏽", "k", "👍🏽"]);
+    assert_eq!(
+        vm.captures("e\u{301}\u{302}!"),
+        vec!["e\u{301}\u{302}!", "e\u{301}\u{302}", "!"]
+    );
+}
+
+#[test]
+fn reset_start() {
+    {
+        let src = "user=\\K([a-z]+)";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("id=1 user=alice!"), vec!["alice", "alice"]);
+        assert_eq!(vm.captures("name=bob"), Vec::<&str>::new());
+    }
+    {
+        let src = "(a\\K|b)c";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("ac"), vec!["c", "a"]);
+        assert_eq!(vm.captures("bc"), vec!["bc", "b"]);
+    }
+    {
+        // the last \K passed on the way to the match wins
+        let src = "(?:x\\K)+y";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("-xxxy-"), vec!["y"]);
+    }
+    {
+        let src = "a\\K(?:bc|b)d";
+        let vm = Dfa::new(src).unwrap();
+
+        assert_eq!(vm.captures("abd"), vec!["bd"]);
+        assert_eq!(vm.captures("ab"), Vec::<&str>::new());
+    }
+}
+
+#[test]
+fn from_ast() {
+    use crate::ast::*;
+    {
+        // keywords with meta chars need no escaping
+        let keywords = ["c++", "f#", "(none)"];
+        let ast = alt(keywords.iter().map(|word| literal(word)).collect());
+        let vm = Dfa::from_ast(&ast).unwrap();
+
+        assert_eq!(vm.captures("use c++"), vec!["c++"]);
+        assert_eq!(vm.captures("f#!"), vec!["f#"]);
+        assert_eq!(vm.captures("(none)"), vec!["(none)"]);
+        assert_eq!(vm.captures("c"), Vec::<&str>::new());
+    }
+    {
+        // same as "([a-z]+)-([0-9]{2,4})"
+        let word = repeat(class(&[('a', 'z')]), 1, None);
+        let number = repeat(class(&[('0', '9')]), 2, Some(4));
+        let ast = concat(vec![group(1, word), literal("-"), group(2, number)]);
+        let vm = Dfa::from_ast(&ast).unwrap();
+
+        assert_eq!(vm.captures("id abc-12345"), vec!["abc-1234", "abc", "1234"]);
+        assert_eq!(vm.captures("abc-1"), Vec::<&str>::new());
+    }
+    {
+        // a parsed tree compiles the same as the pattern
+        let ast = parse("(a|b)+c").unwrap();
+        let vm = Dfa::from_ast(&ast).unwrap();
+
+        assert_eq!(vm.captures("xabbc"), vec!["abbc", "b"]);
+    }
+    {
+        assert_eq!(
+            Dfa::from_ast(&repeat(literal("a"), 3, Some(2))).is_err(),
+            true
+        );
+        assert_eq!(Dfa::from_ast(&class(&[('z', 'a')])).is_err(), true);
+        assert_eq!(
+            Dfa::from_ast(&concat(vec![group(0, literal("a"))])).is_err(),
+            true
+        );
+
+        let mut ast = literal("a");
+        ast.children.push(Ast {
+            kind: AstKind::Call(1),
+            children: vec![],
+            span: Span::default(),
+        });
+        assert_eq!(Dfa::from_ast(&ast).is_err(), true);
+    }
+}
+
+#[test]
+fn simplified() {
+    // the shared prefix and the merged sets keep the order branches are tried in
+    {
+        let vm = Dfa::new("(ab|abc|x)(c?)").unwrap();
+
+        assert_eq!(vm.captures("abc"), vec!["abc", "ab", "c"]);
+        assert_eq!(vm.captures("xc"), vec!["xc", "x", "c"]);
+    }
+    {
+        let vm = Dfa::new("(?:a|[b-d]|c|(?#note)e)+(bc|bd)").unwrap();
+
+        assert_eq!(vm.captures("zaebd"), vec!["aebd", "bd"]);
+        assert_eq!(vm.captures("cbcz"), vec!["cbc", "bc"]);
+    }
+    {
+        let vm = Dfa::new("(?i)(?:[a][b]|ac)").unwrap();
+
+        assert_eq!(vm.captures("xAcz"), vec!["Ac"]);
+        assert_eq!(vm.captures("AB"), vec!["AB"]);
+    }
+}
+
+#[test]
+fn long_input() {
+    let text = "a".repeat(200_000);
+
+    let vm = Dfa::new("(a*)").unwrap();
+    assert_eq!(vm.captures(&text), vec![text.as_str(), text.as_str()]);
+
+    let vm = Dfa::new("(?>(a+))b").unwrap();
+    assert_eq!(vm.captures(&(text.clone() + "b")).len(), 2);
+}
+
+#[test]
+fn leftmost_first() {
+    let vm = Dfa::new("\\G(?:a|ab)").unwrap();
+    assert_eq!(vm.captures("ab"), vec!["a"]);
+
+    let vm = Dfa::new("(a|ab)(c|bcd)").unwrap();
+    assert_eq!(vm.captures("abcd"), vec!["abcd", "a", "bcd"]);
+}
@@ -1,39 +1,74 @@
+use std::collections::HashMap;
+
 use self::{builder::Builder, matcher::Matcher};
-use crate::parser::Parser;
+use crate::{ir::Ir, parser::Ast, RegexBuilder};
 
+#[allow(clippy::needless_return)]
 mod builder;
 mod matcher;
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests;
 
 pub struct Nfa {
     pub(crate) nodes: Vec<Node>,
+    pub(crate) subroutines: HashMap<usize, usize>,
     pub(crate) capture_size: usize,
+    pub(crate) progress_size: usize,
+    pub(crate) recursion_limit: usize,
 }
 
 impl Nfa {
     pub fn new(pattern: &str) -> Result<Nfa, String> {
-        let syntax = Parser::parse(pattern)?;
-        let (nodes, capture_size) = Builder::build(&syntax);
+        RegexBuilder::new(pattern).build_nfa()
+    }
+
+    // compile a tree from the ast module, made in code or parsed, with the default options
+    pub fn from_ast(ast: &Ast) -> Result<Nfa, String> {
+        let options = RegexBuilder::new("");
+        crate::ast::validate(ast, &options)?;
+        Nfa::build(ast, &options)
+    }
+
+    pub(crate) fn build(ast: &Ast, options: &RegexBuilder) -> Result<Nfa, String> {
+        let ir = Ir::from_ast(ast)?;
+        let (nodes, subroutines, capture_size, progress_size) =
+            Builder::build(&ir, options.size_limit)?;
 
         Ok(Nfa {
             nodes,
+            subroutines,
             capture_size,
+            progress_size,
+            recursion_limit: options.recursion_limit,
         })
     }
 
-    pub fn is_match<'a>(&self, str: &'a str) -> bool {
-        let mut matcher = Matcher::new(&self.nodes, 1, self.capture_size);
+    pub fn is_match(&self, str: &str) -> bool {
+        let mut matcher = Matcher::new(self, 1);
         matcher.capture_mode(false);
         !matcher.execute(str).is_empty()
     }
 
     pub fn captures<'a>(&self, str: &'a str) -> Vec<&'a str> {
-        let mut matcher = Matcher::new(&self.nodes, 1, self.capture_size);
+        let mut matcher = Matcher::new(self, 1);
         matcher.capture_mode(true);
         matcher.execute(str)
     }
+
+    // search from the byte offset start, '\G' matches only there.
+    // returns the byte range of the match,
+    // None when start is past the end or not on a char boundary
+    pub fn find_at(&self, str: &str, start: usize) -> Option<(usize, usize)> {
+        if !str.is_char_boundary(start) {
+            return None;
+        }
+
+        let mut matcher = Matcher::new(self, 1);
+        matcher.capture_mode(false);
+        matcher.find_at(str, start)
+    }
 }
 
 pub(crate) struct Node {
@@ -43,19 +78,26 @@ pub(crate) struct Node {
 pub(crate) struct Edge {
     pub action: EdgeAction,
     pub next_id: usize,
-    pub is_greedy: bool,
 }
 
 pub(crate) enum EdgeAction {
     Asap,
     CaptureStart(usize),
     CaptureEnd(usize),
+    Atomic(usize),
+    Call(usize),
+    Return,
+    IfCaptured(usize),
+    IfNotCaptured(usize),
+    SaveProgress(usize),
+    CheckProgress(usize),
     Match(char),
     MatchAny,
+    MatchGrapheme,
     MatchSOL,
     MatchEOL,
+    MatchSOS,
     MatchIncludeSet(Vec<MatchSet>),
-    MatchExcludeSet(Vec<MatchSet>),
 }
 
 pub(crate) enum MatchSet {
@@ -1,27 +1,57 @@
-use std::collections::{BTreeSet, VecDeque};
+use std::collections::HashMap;
 
 use super::{Edge, EdgeAction, MatchSet, Node};
-use crate::parser::{
-    ast::{AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind},
-    Ast,
-};
+use crate::ir::{Ir, Look, Repeat};
 
 pub(crate) struct Builder {
     nodes: Vec<Node>,
     max_capture_id: usize,
+    progress_size: usize,
+    calls: Vec<usize>,
+    subroutines: HashMap<usize, usize>,
+    size_limit: usize,
 }
 
+// what is left to build, see build_root
+enum Task<'a> {
+    Build(&'a Ir),
+    Push(usize),
+    Edge(EdgeAction),
+    AltEdge(usize),     // the alt node
+    Conditional(usize), // the capture id
+    Star(&'a Repeat),
+    StarEnd(usize, usize, bool), // the loop node, the node after, greedy
+    Plus(&'a Repeat),
+    PlusEnd(&'a Repeat, usize, usize),
+    OptionEnd(usize, bool),
+    Count(&'a Ir, u32),            // the copies left
+    CountNext(&'a Ir, u32, usize), // the entry before the last copy
+    Range(&'a Repeat, usize, u32), // the node after, the optional copies left
+    RangeNext(&'a Repeat, usize, u32, usize),
+}
+
+type BuildResult = (Vec<Node>, HashMap<usize, usize>, usize, usize);
+
 impl Builder {
-    pub fn build(ast: &Ast) -> (Vec<Node>, usize) {
+    pub fn build(ir: &Ir, size_limit: usize) -> Result<BuildResult, String> {
         let mut builder = Builder {
             nodes: vec![],
             max_capture_id: 0,
+            progress_size: 0,
+            calls: vec![],
+            subroutines: HashMap::new(),
+            size_limit,
         };
-        builder.build_(ast);
-        return (builder.nodes, builder.max_capture_id + 1);
+        builder.build_(ir)?;
+        return Ok((
+            builder.nodes,
+            builder.subroutines,
+            builder.max_capture_id + 1,
+            builder.progress_size,
+        ));
     }
 
-    fn build_(&mut self, ast: &Ast) {
+    fn build_(&mut self, ir: &Ir) -> Result<(), String> {
         self.nodes.push(Node { nexts: vec![] }); // root
         self.nodes.push(Node { nexts: vec![] }); // submit
         self.nodes.push(Node { nexts: vec![] }); // fail
@@ -31,373 +61,337 @@ impl Builder {
             nexts: vec![Edge {
                 action: EdgeAction::CaptureEnd(0),
                 next_id: 1,
-                is_greedy: true,
             }],
         });
 
-        let node_id = self.build_root(ast, dst_id);
+        let node_id = self.build_root(ir, dst_id)?;
 
         self.nodes[0].nexts.push(Edge {
             action: EdgeAction::CaptureStart(0),
             next_id: node_id,
-            is_greedy: true,
         });
-    }
 
-    fn build_root(&mut self, ast: &Ast, dst_id: usize) -> usize {
-        match &ast.kind {
-            AstKind::CaptureGroup(_) | AstKind::NonCaptureGroup => self.build_group(ast, dst_id),
-            AstKind::Union => self.build_union(ast, dst_id),
-            AstKind::IncludeSet => self.build_include_set(ast, dst_id),
-            AstKind::ExcludeSet => self.build_exclude_set(ast, dst_id),
-            AstKind::Star(greedy) => self.build_star(ast, greedy, dst_id),
-            AstKind::Plus(greedy) => self.build_plus(ast, greedy, dst_id),
-            AstKind::Option(greedy) => self.build_option(ast, greedy, dst_id),
-            AstKind::Repeat(n, m, greedy) => self.build_repeat(ast, n, m, greedy, dst_id),
-            AstKind::Match(kind) => self.build_match(kind, dst_id),
-            AstKind::Position(kind) => self.build_position(kind, dst_id),
-        }
-    }
-
-    fn build_group(&mut self, ast: &Ast, dst_id: usize) -> usize {
-        let mut dst_id = dst_id;
-
-        let mut cap_id = 0;
-        if let AstKind::CaptureGroup(id) = &ast.kind {
-            cap_id = *id;
-            if self.max_capture_id < cap_id {
-                self.max_capture_id = cap_id;
+        // called groups get their own graph, ending at a return node
+        while let Some(cap_id) = self.calls.pop() {
+            if self.subroutines.contains_key(&cap_id) {
+                continue;
             }
 
-            let node_id = self.nodes.len();
-            self.nodes.push(Node {
-                nexts: vec![Edge {
-                    action: EdgeAction::CaptureEnd(cap_id),
-                    next_id: dst_id,
-                    is_greedy: true,
-                }],
-            });
-            dst_id = node_id;
-        }
-
-        for child in ast.children.iter().rev() {
-            let match_id = self.build_root(child, dst_id);
-            dst_id = match_id;
-        }
+            let group = ir.find_group(cap_id).unwrap();
+            let return_id = self.nodes.len();
+            self.build_edge(EdgeAction::Return, return_id);
 
-        if cap_id > 0 {
-            let node_id = self.nodes.len();
-            self.nodes.push(Node {
-                nexts: vec![Edge {
-                    action: EdgeAction::CaptureStart(cap_id),
-                    next_id: dst_id,
-                    is_greedy: true,
-                }],
-            });
-            dst_id = node_id;
+            let entry_id = self.build_root(group, return_id)?;
+            self.subroutines.insert(cap_id, entry_id);
         }
 
-        dst_id
+        self.check_size()
     }
 
-    fn build_union(&mut self, ast: &Ast, dst_id: usize) -> usize {
-        let node_id = self.nodes.len();
-        self.nodes.push(Node { nexts: vec![] });
-
-        for child in ast.children.iter() {
-            let match_id = self.build_root(child, dst_id);
-            self.nodes[node_id].nexts.push(Edge {
-                action: EdgeAction::Asap,
-                next_id: match_id,
-                is_greedy: true,
-            });
+    // checked on every repetition, so a huge count stops early
+    fn check_size(&self) -> Result<(), String> {
+        if self.nodes.len() > self.size_limit {
+            return Err(format!(
+                "ERROR: compiled program is too large, over {} nodes",
+                self.size_limit
+            ));
         }
-        node_id
-    }
-
-    fn build_include_set(&mut self, ast: &Ast, dst_id: usize) -> usize {
-        let set_items = Self::build_set_items(ast);
-
-        let node_id = self.nodes.len();
-        self.nodes.push(Node {
-            nexts: vec![Edge {
-                action: EdgeAction::MatchIncludeSet(set_items),
-                next_id: dst_id,
-                is_greedy: true,
-            }],
-        });
-        node_id
-    }
-
-    fn build_exclude_set(&mut self, ast: &Ast, dst_id: usize) -> usize {
-        let set_items = Self::build_set_items(ast);
-
-        let node_id = self.nodes.len();
-        self.nodes.push(Node {
-            nexts: vec![Edge {
-                action: EdgeAction::MatchExcludeSet(set_items),
-                next_id: dst_id,
-                is_greedy: true,
-            }],
-        });
-        node_id
+        Ok(())
     }
 
-    fn build_set_items(ast: &Ast) -> Vec<MatchSet> {
-        let mut set_items = vec![];
-
-        for child in ast.children.iter() {
-            match &child.kind {
-                AstKind::Match(kind) => match kind {
-                    MatchKind::Char(c) => set_items.push(MatchSet::Char(*c)),
-                    MatchKind::Range(a, b) => set_items.push(MatchSet::Range(*a, *b)),
-                    _ => unreachable!(),
-                },
-                _ => unreachable!(),
+    // the graph is built back to front on a task stack, a node is built before
+    // the nodes leading to it. a build pops the id it leads to and pushes its
+    // entry id, the other tasks finish a node once its children are there
+    fn build_root(&mut self, ir: &Ir, dst_id: usize) -> Result<usize, String> {
+        let mut ids = vec![dst_id];
+        let mut tasks = vec![Task::Build(ir)];
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Build(ir) => {
+                    let dst_id = ids.pop().unwrap();
+                    self.build_node(ir, dst_id, &mut ids, &mut tasks);
+                }
+                Task::Push(id) => ids.push(id),
+                Task::Edge(action) => {
+                    let dst_id = ids.pop().unwrap();
+                    ids.push(self.build_edge(action, dst_id));
+                }
+                Task::AltEdge(node_id) => {
+                    let match_id = ids.pop().unwrap();
+                    self.nodes[node_id].nexts.push(Edge {
+                        action: EdgeAction::Asap,
+                        next_id: match_id,
+                    });
+                }
+                Task::Conditional(cap_id) => {
+                    let no_id = ids.pop().unwrap();
+                    let yes_id = ids.pop().unwrap();
+                    ids.push(self.build_conditional(cap_id, yes_id, no_id));
+                }
+                Task::Star(repeat) => {
+                    let dst_id = ids.pop().unwrap();
+                    let loop_id = self.nodes.len();
+                    self.nodes.push(Node { nexts: vec![] });
+
+                    tasks.push(Task::StarEnd(loop_id, dst_id, repeat.greedy));
+                    if repeat.item_can_be_empty {
+                        // stop looping once an iteration matches nothing
+                        let progress_id = self.new_progress_id();
+                        ids.push(self.build_edge(EdgeAction::CheckProgress(progress_id), loop_id));
+                        tasks.push(Task::Edge(EdgeAction::SaveProgress(progress_id)));
+                    } else {
+                        ids.push(loop_id);
+                    }
+                    tasks.push(Task::Build(&repeat.item));
+                }
+                Task::StarEnd(loop_id, dst_id, greedy) => {
+                    let match_id = ids.pop().unwrap();
+                    self.nodes[loop_id].nexts.push(Edge {
+                        action: EdgeAction::Asap,
+                        next_id: match_id,
+                    });
+                    self.build_skip(loop_id, dst_id, greedy);
+                    ids.push(loop_id);
+                }
+                Task::Plus(repeat) => {
+                    let dst_id = ids.pop().unwrap();
+                    let loop_id = self.nodes.len();
+                    self.nodes.push(Node { nexts: vec![] });
+
+                    ids.push(loop_id);
+                    tasks.push(Task::PlusEnd(repeat, loop_id, dst_id));
+                    tasks.push(Task::Build(&repeat.item));
+                }
+                Task::PlusEnd(repeat, loop_id, dst_id) => {
+                    let match_id = ids.pop().unwrap();
+                    ids.push(self.build_plus(repeat, loop_id, match_id, dst_id));
+                }
+                Task::OptionEnd(dst_id, greedy) => {
+                    let match_id = *ids.last().unwrap();
+                    // an empty child has nothing to skip
+                    if match_id != dst_id {
+                        self.build_skip(match_id, dst_id, greedy);
+                    }
+                }
+                Task::Count(_, 0) => {}
+                Task::Count(item, count) => {
+                    let prev_id = *ids.last().unwrap();
+                    tasks.push(Task::CountNext(item, count, prev_id));
+                    tasks.push(Task::Build(item));
+                }
+                Task::CountNext(item, count, prev_id) => {
+                    // an empty child has nothing to repeat
+                    if *ids.last().unwrap() != prev_id {
+                        self.check_size()?;
+                        tasks.push(Task::Count(item, count - 1));
+                    }
+                }
+                Task::Range(repeat, _, 0) => tasks.push(Task::Count(&repeat.item, repeat.min)),
+                Task::Range(repeat, dst_id, count) => {
+                    let prev_id = *ids.last().unwrap();
+                    tasks.push(Task::RangeNext(repeat, dst_id, count, prev_id));
+                    tasks.push(Task::Build(&repeat.item));
+                }
+                Task::RangeNext(repeat, dst_id, count, prev_id) => {
+                    let repeat_id = *ids.last().unwrap();
+                    if repeat_id == prev_id {
+                        // an empty child has nothing to skip
+                        tasks.push(Task::Count(&repeat.item, repeat.min));
+                        continue;
+                    }
+                    self.check_size()?;
+                    self.build_skip(repeat_id, dst_id, repeat.greedy);
+                    tasks.push(Task::Range(repeat, dst_id, count - 1));
+                }
             }
         }
 
-        set_items
-    }
-
-    fn build_star(&mut self, ast: &Ast, greedy: &GreedyKind, dst_id: usize) -> usize {
-        let loop_id = self.nodes.len();
-        self.nodes.push(Node { nexts: vec![] });
-
-        let match_id = self.build_root(&ast.children[0], loop_id);
-        self.nodes[loop_id].nexts.push(Edge {
-            action: EdgeAction::Asap,
-            next_id: match_id,
-            is_greedy: true,
-        });
-
-        if matches!(*greedy, GreedyKind::Greedy) {
-            self.nodes[loop_id].nexts.push(Edge {
-                action: EdgeAction::Asap,
-                next_id: dst_id,
-                is_greedy: true,
-            });
-        } else {
-            self.nodes[loop_id].nexts.insert(
-                0,
-                Edge {
-                    action: EdgeAction::Asap,
-                    next_id: dst_id,
-                    is_greedy: true,
-                },
-            );
-        }
-
-        if matches!(*greedy, GreedyKind::NonGreedy) {
-            self.recursive_set_greedy(loop_id, dst_id, false);
-        }
-
-        loop_id
-    }
-
-    fn build_plus(&mut self, ast: &Ast, greedy: &GreedyKind, dst_id: usize) -> usize {
-        let loop_id = self.nodes.len();
-        self.nodes.push(Node { nexts: vec![] });
-
-        let match_id = self.build_root(&ast.children[0], loop_id);
-        self.nodes[loop_id].nexts.push(Edge {
-            action: EdgeAction::Asap,
-            next_id: match_id,
-            is_greedy: true,
-        });
-
-        if matches!(*greedy, GreedyKind::Greedy) {
-            self.nodes[loop_id].nexts.push(Edge {
-                action: EdgeAction::Asap,
-                next_id: dst_id,
-                is_greedy: true,
-            });
-        } else {
-            self.nodes[loop_id].nexts.insert(
-                0,
-                Edge {
-                    action: EdgeAction::Asap,
-                    next_id: dst_id,
-                    is_greedy: true,
-                },
-            );
-        }
-
-        if matches!(*greedy, GreedyKind::NonGreedy) {
-            self.recursive_set_greedy(match_id, dst_id, false);
-        }
-
-        match_id
+        Ok(ids.pop().unwrap())
     }
 
-    fn build_option(&mut self, ast: &Ast, greedy: &GreedyKind, dst_id: usize) -> usize {
-        let match_id = self.build_root(&ast.children[0], dst_id);
-
-        if matches!(*greedy, GreedyKind::Greedy) {
-            self.nodes[match_id].nexts.push(Edge {
-                action: EdgeAction::Asap,
-                next_id: dst_id,
-                is_greedy: true,
-            });
-        } else {
-            self.nodes[match_id].nexts.insert(
-                0,
-                Edge {
-                    action: EdgeAction::Asap,
-                    next_id: dst_id,
-                    is_greedy: true,
-                },
-            );
-        }
-
-        if matches!(*greedy, GreedyKind::NonGreedy) {
-            self.recursive_set_greedy(match_id, dst_id, false);
-        }
-
-        match_id
-    }
-
-    fn build_repeat(
+    fn build_node<'a>(
         &mut self,
-        ast: &Ast,
-        min: &RepeatKind,
-        max: &RepeatKind,
-        greedy: &GreedyKind,
+        ir: &'a Ir,
         dst_id: usize,
-    ) -> usize {
-        match (min, max) {
-            (RepeatKind::Num(n), RepeatKind::Num(m)) if n == m => {
-                self.build_repeat_count(ast, *n, dst_id)
+        ids: &mut Vec<usize>,
+        tasks: &mut Vec<Task<'a>>,
+    ) {
+        match ir {
+            Ir::Concat(items) => {
+                ids.push(dst_id);
+                tasks.extend(items.iter().map(Task::Build));
+            }
+            Ir::Alt(items) => {
+                let node_id = self.nodes.len();
+                self.nodes.push(Node { nexts: vec![] });
+
+                tasks.push(Task::Push(node_id));
+                for item in items.iter().rev() {
+                    tasks.extend([
+                        Task::AltEdge(node_id),
+                        Task::Build(item),
+                        Task::Push(dst_id),
+                    ]);
+                }
+            }
+            Ir::Capture(0, item) => {
+                // group 0 is captured around the whole graph
+                ids.push(dst_id);
+                tasks.push(Task::Build(item));
             }
-            (RepeatKind::Num(n), RepeatKind::Num(m)) => {
-                self.build_repeat_range(ast, *n, *m, greedy, dst_id)
+            Ir::Capture(cap_id, item) => {
+                if self.max_capture_id < *cap_id {
+                    self.max_capture_id = *cap_id;
+                }
+                ids.push(self.build_edge(EdgeAction::CaptureEnd(*cap_id), dst_id));
+                tasks.extend([
+                    Task::Edge(EdgeAction::CaptureStart(*cap_id)),
+                    Task::Build(item),
+                ]);
             }
-            (RepeatKind::Num(c), RepeatKind::Infinity) => {
-                self.build_repeat_min(ast, *c, greedy, dst_id)
+            Ir::Atomic(item) => {
+                let end_id = self.build_edge(EdgeAction::Asap, dst_id);
+                ids.push(end_id);
+                tasks.extend([Task::Edge(EdgeAction::Atomic(end_id)), Task::Build(item)]);
             }
-            (RepeatKind::Infinity, _) => {
-                unreachable!()
+            Ir::Conditional(cap_id, yes, no) => {
+                ids.push(dst_id);
+                tasks.extend([
+                    Task::Conditional(*cap_id),
+                    Task::Build(no),
+                    Task::Push(dst_id),
+                    Task::Build(yes),
+                ]);
             }
+            Ir::Call(cap_id) => {
+                self.calls.push(*cap_id);
+                ids.push(self.build_edge(EdgeAction::Call(*cap_id), dst_id));
+            }
+            Ir::Repeat(repeat) => {
+                ids.push(dst_id);
+                tasks.extend(Self::build_repeat(repeat, dst_id));
+            }
+            Ir::Char(c) => ids.push(self.build_edge(EdgeAction::Match(*c), dst_id)),
+            Ir::Class(ranges) => ids.push(self.build_class(ranges, dst_id)),
+            Ir::Any => ids.push(self.build_edge(EdgeAction::MatchAny, dst_id)),
+            Ir::Grapheme => ids.push(self.build_edge(EdgeAction::MatchGrapheme, dst_id)),
+            Ir::Look(look) => ids.push(self.build_look(look, dst_id)),
+            Ir::ResetStart => ids.push(self.build_edge(EdgeAction::CaptureStart(0), dst_id)),
         }
     }
 
-    fn build_repeat_count(&mut self, ast: &Ast, count: u32, dst_id: usize) -> usize {
-        let mut dst_id = dst_id;
-
-        let child = &ast.children[0];
-        for _ in 0..count {
-            let match_id = self.build_root(child, dst_id);
-            dst_id = match_id;
-        }
+    fn build_conditional(&mut self, cap_id: usize, yes_id: usize, no_id: usize) -> usize {
+        let node_id = self.nodes.len();
+        self.nodes.push(Node {
+            nexts: vec![
+                Edge {
+                    action: EdgeAction::IfCaptured(cap_id),
+                    next_id: yes_id,
+                },
+                Edge {
+                    action: EdgeAction::IfNotCaptured(cap_id),
+                    next_id: no_id,
+                },
+            ],
+        });
+        node_id
+    }
 
-        dst_id
+    fn build_class(&mut self, ranges: &[(char, char)], dst_id: usize) -> usize {
+        let set_items = ranges
+            .iter()
+            .map(|(a, b)| {
+                if a == b {
+                    MatchSet::Char(*a)
+                } else {
+                    MatchSet::Range(*a, *b)
+                }
+            })
+            .collect();
+
+        self.build_edge(EdgeAction::MatchIncludeSet(set_items), dst_id)
     }
 
-    fn build_repeat_min(
-        &mut self,
-        ast: &Ast,
-        count: u32,
-        greedy: &GreedyKind,
-        dst_id: usize,
-    ) -> usize {
-        let loop_id = self.build_star(ast, greedy, dst_id);
-        self.build_repeat_count(ast, count, loop_id)
+    // the tasks in reverse order, each takes the id on top of the stack
+    fn build_repeat(repeat: &Repeat, dst_id: usize) -> Vec<Task<'_>> {
+        let Repeat {
+            min,
+            max,
+            greedy,
+            item,
+            ..
+        } = repeat;
+        match (*min, *max) {
+            (0, None) => vec![Task::Star(repeat)],
+            (1, None) => vec![Task::Plus(repeat)],
+            (0, Some(1)) => vec![Task::OptionEnd(dst_id, *greedy), Task::Build(item)],
+            (n, Some(m)) if n == m => vec![Task::Count(item, n)],
+            (n, Some(m)) => vec![Task::Range(repeat, dst_id, m - n)],
+            (n, None) => vec![Task::Count(item, n), Task::Star(repeat)],
+        }
     }
 
-    fn build_repeat_range(
+    fn build_plus(
         &mut self,
-        ast: &Ast,
-        min: u32,
-        max: u32,
-        greedy: &GreedyKind,
+        repeat: &Repeat,
+        loop_id: usize,
+        match_id: usize,
         dst_id: usize,
     ) -> usize {
-        let mut match_id = dst_id;
-
-        let child = &ast.children[0];
-        for _ in min..max {
-            let repeat_id = self.build_root(child, match_id);
-            if matches!(*greedy, GreedyKind::Greedy) {
-                self.nodes[repeat_id].nexts.push(Edge {
-                    action: EdgeAction::Asap,
-                    next_id: dst_id,
-                    is_greedy: true,
-                });
-            } else {
-                self.nodes[repeat_id].nexts.insert(
-                    0,
-                    Edge {
-                        action: EdgeAction::Asap,
-                        next_id: dst_id,
-                        is_greedy: true,
-                    },
-                );
-            }
-            match_id = repeat_id;
+        let mut match_id = match_id;
+        let mut back_id = match_id;
+        if repeat.item_can_be_empty {
+            // the first iteration may be empty, the following ones may not
+            let progress_id = self.new_progress_id();
+            match_id = self.build_edge(EdgeAction::SaveProgress(progress_id), match_id);
+            back_id = self.build_edge(EdgeAction::CheckProgress(progress_id), match_id);
         }
 
-        if matches!(*greedy, GreedyKind::NonGreedy) {
-            self.recursive_set_greedy(match_id, dst_id, false);
-        }
+        self.nodes[loop_id].nexts.push(Edge {
+            action: EdgeAction::Asap,
+            next_id: back_id,
+        });
+        self.build_skip(loop_id, dst_id, repeat.greedy);
 
-        self.build_repeat_count(ast, min, match_id)
+        match_id
     }
 
-    fn build_match(&mut self, kind: &MatchKind, dst_id: usize) -> usize {
-        let node_id = self.nodes.len();
-
-        let action = match kind {
-            MatchKind::Any => EdgeAction::MatchAny,
-            MatchKind::Char(c) => EdgeAction::Match(*c),
-            MatchKind::Range(_, _) => unreachable!(),
+    // the edge leaving a repeat, tried last when greedy
+    fn build_skip(&mut self, node_id: usize, dst_id: usize, greedy: bool) {
+        let edge = Edge {
+            action: EdgeAction::Asap,
+            next_id: dst_id,
         };
-
-        self.nodes.push(Node {
-            nexts: vec![Edge {
-                action,
-                next_id: dst_id,
-                is_greedy: true,
-            }],
-        });
-
-        node_id
+        if greedy {
+            self.nodes[node_id].nexts.push(edge);
+        } else {
+            self.nodes[node_id].nexts.insert(0, edge);
+        }
     }
 
-    fn build_position(&mut self, position: &PositionKind, dst_id: usize) -> usize {
+    fn build_edge(&mut self, action: EdgeAction, dst_id: usize) -> usize {
         let node_id = self.nodes.len();
-
-        let action = match position {
-            PositionKind::SoL => EdgeAction::MatchSOL,
-            PositionKind::EoL => EdgeAction::MatchEOL,
-        };
-
         self.nodes.push(Node {
             nexts: vec![Edge {
                 action,
                 next_id: dst_id,
-                is_greedy: true,
             }],
         });
-
         node_id
     }
 
-    fn recursive_set_greedy(&mut self, start_id: usize, end_id: usize, is_greedy: bool) {
-        let mut finished = BTreeSet::new();
-        finished.insert(end_id);
-
-        let mut q = VecDeque::new();
-        q.push_back(start_id);
-
-        while let Some(id) = q.pop_front() {
-            if !finished.insert(id) {
-                continue;
-            }
+    fn new_progress_id(&mut self) -> usize {
+        self.progress_size += 1;
+        self.progress_size - 1
+    }
 
-            for edge in self.nodes[id].nexts.iter_mut() {
-                edge.is_greedy = is_greedy;
-                q.push_back(edge.next_id);
-            }
-        }
+    fn build_look(&mut self, look: &Look, dst_id: usize) -> usize {
+        let action = match look {
+            Look::SoL => EdgeAction::MatchSOL,
+            Look::EoL => EdgeAction::MatchEOL,
+            Look::SoS => EdgeAction::MatchSOS,
+        };
+        self.build_edge(action, dst_id)
     }
 }
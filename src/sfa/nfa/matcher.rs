@@ -1,33 +1,85 @@
-use super::{EdgeAction, MatchSet, Node};
+use std::{collections::HashMap, iter::once};
+
+use super::{EdgeAction, MatchSet, Nfa, Node};
+use crate::grapheme;
+
+struct Frame {
+    return_id: usize,
+    cap_starts: Vec<usize>,
+    cap_ends: Vec<usize>,
+    progress_pos: Vec<usize>,
+}
+
+// the entries pushed while matching, a branch is an edge left to try, the others
+// undo a change to the state
+enum Backtrack {
+    Branch(usize, usize, usize), // node id, edge index, sp
+    CaptureStart(usize, usize),  // capture id, the old sp
+    CaptureEnd(usize, usize),
+    Progress(usize, usize),
+    Call,
+    Return(Frame),
+    AtomicStart,
+    AtomicEnd(Atomic),
+}
+
+// an atomic group being matched, it is done at its end node
+struct Atomic {
+    end_id: usize,
+    depth: usize,      // the frames at the start
+    stack_size: usize, // the branches above are dropped at the end
+}
 
 pub(crate) struct Matcher<'a> {
     nodes: &'a Vec<Node>,
+    subroutines: &'a HashMap<usize, usize>,
+    recursion_limit: usize,
     success_id: usize,
+    start: usize, // where the search started, for '\G'
     capture_needed: bool,
     cap_starts: Vec<usize>,
     cap_ends: Vec<usize>,
+    progress_pos: Vec<usize>,
+    frames: Vec<Frame>,
+    stack: Vec<Backtrack>,
+    atomics: Vec<Atomic>,
 }
 
 impl<'a> Matcher<'a> {
-    pub fn new(nodes: &'a Vec<Node>, success_id: usize, captuire_size: usize) -> Self {
+    pub fn new(nfa: &'a Nfa, success_id: usize) -> Self {
         Matcher {
-            nodes,
+            nodes: &nfa.nodes,
+            subroutines: &nfa.subroutines,
+            recursion_limit: nfa.recursion_limit,
             success_id,
+            start: 0,
             capture_needed: true,
-            cap_starts: vec![0; captuire_size],
-            cap_ends: vec![0; captuire_size],
+            cap_starts: vec![0; nfa.capture_size],
+            cap_ends: vec![usize::MAX; nfa.capture_size],
+            progress_pos: vec![0; nfa.progress_size],
+            frames: vec![],
+            stack: vec![],
+            atomics: vec![],
         }
     }
 
     fn reset(&mut self) {
         let captuire_size = self.cap_starts.len();
+        let progress_size = self.progress_pos.len();
 
         *self = Matcher {
             nodes: self.nodes,
+            subroutines: self.subroutines,
+            recursion_limit: self.recursion_limit,
             success_id: self.success_id,
+            start: 0,
             capture_needed: self.capture_needed,
             cap_starts: vec![0; captuire_size],
-            cap_ends: vec![0; captuire_size],
+            cap_ends: vec![usize::MAX; captuire_size],
+            progress_pos: vec![0; progress_size],
+            frames: vec![],
+            stack: vec![],
+            atomics: vec![],
         }
     }
 
@@ -36,112 +88,210 @@ impl<'a> Matcher<'a> {
     }
 
     pub fn execute<'b>(&mut self, str: &'b str) -> Vec<&'b str> {
-        for i in 0..str.len() {
-            self.reset();
+        if !self.search(str, 0) {
+            return vec![]; // unmatch
+        }
 
-            let result = self.execute_(str, i, 0);
-            if result.is_some() {
-                let mut captures = vec![];
-                captures.push(&str[self.cap_starts[0]..self.cap_ends[0]]);
-
-                if self.capture_needed {
-                    for cap_id in 1..self.cap_starts.len() {
-                        let start = self.cap_starts[cap_id];
-                        let end = self.cap_ends[cap_id];
-
-                        if start < end {
-                            captures.push(&str[start..end]);
-                        } else {
-                            captures.push("");
-                        }
-                    }
+        let mut captures = vec![];
+        captures.push(&str[self.cap_starts[0]..self.cap_ends[0]]);
+
+        if self.capture_needed {
+            for cap_id in 1..self.cap_starts.len() {
+                let start = self.cap_starts[cap_id];
+                let end = self.cap_ends[cap_id];
+
+                if end != usize::MAX && start <= end {
+                    captures.push(&str[start..end]);
+                } else {
+                    captures.push("");
                 }
-                return captures;
             }
         }
+        captures
+    }
 
-        vec![] // unmatch
+    pub fn find_at(&mut self, str: &str, start: usize) -> Option<(usize, usize)> {
+        if !self.search(str, start) {
+            return None;
+        }
+        Some((self.cap_starts[0], self.cap_ends[0]))
     }
 
-    fn execute_<'b>(&mut self, str: &'b str, sp: usize, id: usize) -> Option<usize> {
-        if id == self.success_id {
-            return Some(id);
+    fn search(&mut self, str: &str, start: usize) -> bool {
+        // the end is a start too, an empty match can be there
+        let ends = once(str.len() - start);
+        for i in str[start..].char_indices().map(|(i, _)| i).chain(ends) {
+            self.reset();
+            self.start = start;
+
+            if self.execute_(str, start + i, 0).is_some() {
+                return true;
+            }
         }
+        false
+    }
 
-        let node = &self.nodes[id];
-        for edge in node.nexts.iter() {
-            #[rustfmt::skip]
-            let result = match &edge.action {
-                EdgeAction::Asap =>
-                    self.execute_(str, sp, edge.next_id),
-                EdgeAction::CaptureStart(cap_id) => {
-                    let old_sp = self.cap_starts[*cap_id];
-                    self.cap_starts[*cap_id] = sp;
-
-                    let result = self.execute_(str, sp, edge.next_id);
-                    if result.is_none() {
-                        self.cap_starts[*cap_id] = old_sp;
-                    }
-                    result
-                },
-                EdgeAction::CaptureEnd(cap_id) => {
-                    let old_sp = self.cap_ends[*cap_id];
-                    self.cap_ends[*cap_id] = sp;
-
-                    let result = self.execute_(str, sp, edge.next_id);
-                    if result.is_none() {
-                        self.cap_ends[*cap_id] = old_sp;
+    fn execute_(&mut self, str: &str, sp: usize, id: usize) -> Option<usize> {
+        self.stack.clear();
+        self.atomics.clear();
+
+        let (mut sp, mut id, mut edge_index) = (sp, id, 0);
+        loop {
+            if edge_index == 0 {
+                if let Some(atomic) = self.atomics.last() {
+                    if atomic.end_id == id && atomic.depth == self.frames.len() {
+                        // the group matched alone, never backtrack into it
+                        let atomic = self.atomics.pop().unwrap();
+                        let inner = self.stack.split_off(atomic.stack_size);
+                        let undos = inner
+                            .into_iter()
+                            .filter(|entry| !matches!(entry, Backtrack::Branch(..)));
+                        self.stack.extend(undos);
+                        self.stack.push(Backtrack::AtomicEnd(atomic));
                     }
-                    result
-                },
-                EdgeAction::Match(t) =>
-                    str
-                    .chars()
-                    .nth(sp)
-                    .filter(|c| *c == *t)
-                    .and_then(|_| self.execute_(str, sp + 1, edge.next_id)),
-                EdgeAction::MatchAny =>
-                    str
-                    .chars()
-                    .nth(sp)
-                    .and_then(|_| self.execute_(str, sp + 1, edge.next_id)),
-                EdgeAction::MatchSOL =>
-                    Some(sp)
-                    .filter(|p| *p == 0)
-                    .and_then(|_| self.execute_(str, sp, edge.next_id)),
-                EdgeAction::MatchEOL =>
-                    Some(sp)
-                    .filter(|p| *p == str.len())
-                    .and_then(|_| self.execute_(str, sp, edge.next_id)),
-                EdgeAction::MatchIncludeSet(set) =>
-                    str
-                    .chars()
-                    .nth(sp)
-                    .filter(|c| {
-                        set.iter().any(|m| match m {
-                            MatchSet::Char(t) => *t == *c,
-                            MatchSet::Range(a, b) => *a <= *c && *c <= *b,
-                        })
-                    })
-                    .and_then(|_| self.execute_(str, sp + 1, edge.next_id)),
-                EdgeAction::MatchExcludeSet(set) =>
-                    str
-                    .chars()
-                    .nth(sp)
-                    .filter(|c| {
-                        set.iter().all(|m| match m {
-                            MatchSet::Char(t) => *t != *c,
-                            MatchSet::Range(a, b) => *c < *a || *b < *c,
-                        })
-                    })
-                    .and_then(|_| self.execute_(str, sp + 1, edge.next_id)),
-            };
+                }
+                if id == self.success_id && self.frames.is_empty() && self.atomics.is_empty() {
+                    return Some(sp);
+                }
+            }
 
-            if result.is_some() {
-                return result;
+            let nexts = &self.nodes[id].nexts;
+            if edge_index + 1 < nexts.len() {
+                self.stack.push(Backtrack::Branch(id, edge_index + 1, sp));
+            }
+
+            let next = match nexts.get(edge_index) {
+                Some(edge) => self.step(str, sp, &edge.action, edge.next_id),
+                None => None,
+            };
+            match next {
+                Some((next_sp, next_id)) => (sp, id, edge_index) = (next_sp, next_id, 0),
+                None => (sp, id, edge_index) = self.backtrack()?,
             }
         }
+    }
+
+    // follows one edge, the state it changes is undone on backtracking
+    fn step(
+        &mut self,
+        str: &str,
+        sp: usize,
+        action: &EdgeAction,
+        next_id: usize,
+    ) -> Option<(usize, usize)> {
+        #[rustfmt::skip]
+        let next_sp = match action {
+            EdgeAction::Asap => Some(sp),
+            EdgeAction::CaptureStart(cap_id) => {
+                self.stack.push(Backtrack::CaptureStart(*cap_id, self.cap_starts[*cap_id]));
+                self.cap_starts[*cap_id] = sp;
+                Some(sp)
+            },
+            EdgeAction::CaptureEnd(cap_id) => {
+                self.stack.push(Backtrack::CaptureEnd(*cap_id, self.cap_ends[*cap_id]));
+                self.cap_ends[*cap_id] = sp;
+                Some(sp)
+            },
+            EdgeAction::Atomic(end_id) => {
+                // match the group alone, until it reaches its end node
+                self.stack.push(Backtrack::AtomicStart);
+                self.atomics.push(Atomic {
+                    end_id: *end_id,
+                    depth: self.frames.len(),
+                    stack_size: self.stack.len(),
+                });
+                Some(sp)
+            },
+            EdgeAction::Call(cap_id) => {
+                if self.frames.len() >= self.recursion_limit {
+                    return None;
+                }
+                self.frames.push(Frame {
+                    return_id: next_id,
+                    cap_starts: self.cap_starts.clone(),
+                    cap_ends: self.cap_ends.clone(),
+                    progress_pos: self.progress_pos.clone(),
+                });
+                self.stack.push(Backtrack::Call);
+                return Some((sp, self.subroutines[cap_id]));
+            },
+            EdgeAction::Return => {
+                // captures made inside the call are dropped on return
+                let mut frame = self.frames.pop().unwrap();
+                self.swap_frame(&mut frame);
+                let return_id = frame.return_id;
+                self.stack.push(Backtrack::Return(frame));
+                return Some((sp, return_id));
+            },
+            EdgeAction::IfCaptured(cap_id) =>
+                Some(sp).filter(|_| self.cap_ends[*cap_id] != usize::MAX),
+            EdgeAction::IfNotCaptured(cap_id) =>
+                Some(sp).filter(|_| self.cap_ends[*cap_id] == usize::MAX),
+            EdgeAction::SaveProgress(progress_id) => {
+                self.stack.push(Backtrack::Progress(*progress_id, self.progress_pos[*progress_id]));
+                self.progress_pos[*progress_id] = sp;
+                Some(sp)
+            },
+            EdgeAction::CheckProgress(progress_id) =>
+                Some(sp).filter(|p| *p != self.progress_pos[*progress_id]),
+            EdgeAction::Match(t) =>
+                str[sp..]
+                .chars()
+                .next()
+                .filter(|c| *c == *t)
+                .map(|c| sp + c.len_utf8()),
+            EdgeAction::MatchAny =>
+                str[sp..]
+                .chars()
+                .next()
+                .map(|c| sp + c.len_utf8()),
+            EdgeAction::MatchGrapheme => grapheme::next_boundary(str, sp),
+            EdgeAction::MatchSOL => Some(sp).filter(|p| *p == 0),
+            EdgeAction::MatchEOL => Some(sp).filter(|p| *p == str.len()),
+            EdgeAction::MatchSOS => Some(sp).filter(|p| *p == self.start),
+            EdgeAction::MatchIncludeSet(set) =>
+                str[sp..]
+                .chars()
+                .next()
+                .filter(|c| {
+                    set.iter().any(|m| match m {
+                        MatchSet::Char(t) => *t == *c,
+                        MatchSet::Range(a, b) => *a <= *c && *c <= *b,
+                    })
+                })
+                .map(|c| sp + c.len_utf8()),
+        };
+
+        next_sp.map(|next_sp| (next_sp, next_id))
+    }
 
+    // undoes the state down to the last branch, and resumes there
+    fn backtrack(&mut self) -> Option<(usize, usize, usize)> {
+        while let Some(entry) = self.stack.pop() {
+            match entry {
+                Backtrack::Branch(id, edge_index, sp) => return Some((sp, id, edge_index)),
+                Backtrack::CaptureStart(cap_id, old_sp) => self.cap_starts[cap_id] = old_sp,
+                Backtrack::CaptureEnd(cap_id, old_sp) => self.cap_ends[cap_id] = old_sp,
+                Backtrack::Progress(progress_id, old_sp) => self.progress_pos[progress_id] = old_sp,
+                Backtrack::Call => {
+                    self.frames.pop();
+                }
+                Backtrack::Return(mut frame) => {
+                    self.swap_frame(&mut frame);
+                    self.frames.push(frame);
+                }
+                Backtrack::AtomicStart => {
+                    self.atomics.pop();
+                }
+                Backtrack::AtomicEnd(atomic) => self.atomics.push(atomic),
+            }
+        }
         None
     }
+
+    fn swap_frame(&mut self, frame: &mut Frame) {
+        std::mem::swap(&mut self.cap_starts, &mut frame.cap_starts);
+        std::mem::swap(&mut self.cap_ends, &mut frame.cap_ends);
+        std::mem::swap(&mut self.progress_pos, &mut frame.progress_pos);
+    }
 }
@@ -1,41 +1,86 @@
 use self::{compile::Compiler, exec::Executer, inst::Inst};
-use crate::parser::Parser;
+use crate::{ir::Ir, parser::Ast, RegexBuilder};
 
 mod compile;
+#[allow(clippy::needless_return)]
 mod exec;
 mod inst;
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests;
 
 pub struct Vm {
     insts: Vec<Inst>,
     capture_size: usize,
+    progress_size: usize,
+    recursion_limit: usize,
 }
 
 impl Vm {
     pub fn new(pattern: &str) -> Result<Vm, String> {
-        let ast = Parser::parse(pattern)?;
-        let (insts, capture_size) = Compiler::compile(&ast);
+        RegexBuilder::new(pattern).build_vm()
+    }
+
+    // compile a tree from the ast module, made in code or parsed, with the default options
+    pub fn from_ast(ast: &Ast) -> Result<Vm, String> {
+        let options = RegexBuilder::new("");
+        crate::ast::validate(ast, &options)?;
+        Vm::build(ast, &options)
+    }
+
+    pub(crate) fn build(ast: &Ast, options: &RegexBuilder) -> Result<Vm, String> {
+        let ir = Ir::from_ast(ast)?;
+        let (insts, capture_size, progress_size) = Compiler::compile(&ir, options.size_limit)?;
 
         Ok(Vm {
             insts,
             capture_size,
+            progress_size,
+            recursion_limit: options.recursion_limit,
         })
     }
 
-    pub fn is_match<'a>(&self, str: &'a str) -> bool {
-        let mut exec = Executer::new(&self.insts, self.capture_size);
+    pub fn is_match(&self, str: &str) -> bool {
+        let mut exec = Executer::new(
+            &self.insts,
+            self.capture_size,
+            self.progress_size,
+            self.recursion_limit,
+        );
         exec.capture_mode(false);
         !exec.execute(str).is_empty()
     }
 
     pub fn captures<'a>(&self, str: &'a str) -> Vec<&'a str> {
-        let mut exec = Executer::new(&self.insts, self.capture_size);
+        let mut exec = Executer::new(
+            &self.insts,
+            self.capture_size,
+            self.progress_size,
+            self.recursion_limit,
+        );
         exec.capture_mode(true);
         exec.execute(str)
     }
 
+    // search from the byte offset start, '\G' matches only there.
+    // returns the byte range of the match,
+    // None when start is past the end or not on a char boundary
+    pub fn find_at(&self, str: &str, start: usize) -> Option<(usize, usize)> {
+        if !str.is_char_boundary(start) {
+            return None;
+        }
+
+        let mut exec = Executer::new(
+            &self.insts,
+            self.capture_size,
+            self.progress_size,
+            self.recursion_limit,
+        );
+        exec.capture_mode(false);
+        exec.find_at(str, start)
+    }
+
     #[cfg(test)]
     pub fn dump(&self) {
         for inst in self.insts.iter() {
@@ -1,275 +1,360 @@
+use std::collections::HashMap;
+
 use super::inst::Inst;
-use crate::parser::{
-    ast::{AstKind, GreedyKind, MatchKind, PositionKind, RepeatKind},
-    Ast,
-};
+use crate::ir::{Ir, Look, Repeat};
+
+// what is left to do for a node, see compile_root
+enum Step<'a> {
+    Compile(&'a Ir),
+    Emit(Inst),
+    Mark, // the next address, for a later step to patch or copy from
+    AltBranch,
+    AltEnd(usize), // the number of branches
+    ConditionalNo,
+    ConditionalYes,
+    Star(bool, Option<usize>), // greedy, the progress id of an item that can be empty
+    Plus(bool, Option<usize>),
+    Option(bool),
+    Count(u32),
+    Min(u32, bool, Option<usize>),
+    Range(u32, u32, bool),
+}
 
 pub(crate) struct Compiler {
     max_capture_id: usize,
+    progress_size: usize,
+    calls: Vec<usize>,
+    size_limit: usize,
 }
 
 impl Compiler {
-    pub fn compile(ast: &Ast) -> (Vec<Inst>, usize) {
-        let mut compiler = Compiler { max_capture_id: 0 };
-
-        let mut insts = compiler.compile_root(ast);
-        insts.insert(0, Inst::CaptureStart(0));
+    pub fn compile(ir: &Ir, size_limit: usize) -> Result<(Vec<Inst>, usize, usize), String> {
+        let mut compiler = Compiler {
+            max_capture_id: 0,
+            progress_size: 0,
+            calls: vec![],
+            size_limit,
+        };
+
+        let mut insts = vec![Inst::CaptureStart(0)];
+        compiler.compile_root(ir, &mut insts)?;
         insts.push(Inst::CaptureEnd(0));
         insts.push(Inst::Success);
 
-        (insts, compiler.max_capture_id + 1)
-    }
-
-    fn compile_root(&mut self, ast: &Ast) -> Vec<Inst> {
-        match &ast.kind {
-            AstKind::CaptureGroup(cap_id) => {
-                if self.max_capture_id < *cap_id {
-                    self.max_capture_id = *cap_id;
-                }
-                self.compile_group(ast, *cap_id)
+        // called groups are placed after the program as subroutines
+        let mut subroutines = HashMap::new();
+        while let Some(cap_id) = compiler.calls.pop() {
+            if subroutines.contains_key(&cap_id) {
+                continue;
             }
-            AstKind::NonCaptureGroup => self.compile_group(ast, 0),
-            AstKind::Union => self.compile_union(ast),
-            AstKind::IncludeSet => self.compile_include_set(ast),
-            AstKind::ExcludeSet => self.compile_exclude_set(ast),
-            AstKind::Star(greedy) => self.compile_star(ast, greedy),
-            AstKind::Plus(greedy) => self.compile_plus(ast, greedy),
-            AstKind::Option(greedy) => self.compile_option(ast, greedy),
-            AstKind::Repeat(n, m, greedy) => self.compile_repeat(ast, n, m, greedy),
-            AstKind::Match(kind) => Self::compile_match(kind),
-            AstKind::Position(kind) => Self::compile_position(kind),
-        }
-    }
 
-    fn compile_group(&mut self, ast: &Ast, cap_id: usize) -> Vec<Inst> {
-        let mut insts = Vec::new();
-
-        if cap_id > 0 {
-            insts.push(Inst::CaptureStart(cap_id))
-        }
-        for child in ast.children.iter() {
-            insts.extend(self.compile_root(child));
+            let group = ir.find_group(cap_id).unwrap();
+            subroutines.insert(cap_id, insts.len());
+            compiler.compile_root(group, &mut insts)?;
+            insts.push(Inst::Return);
         }
-        if cap_id > 0 {
-            insts.push(Inst::CaptureEnd(cap_id))
+        compiler.check_size(insts.len())?;
+
+        // resolve the called group ids to subroutine addresses
+        for inst in insts.iter_mut() {
+            if let Inst::Call(cap_id) = inst {
+                *inst = Inst::Call(subroutines[cap_id]);
+            }
         }
 
-        insts
+        Ok((insts, compiler.max_capture_id + 1, compiler.progress_size))
     }
 
-    fn compile_union(&mut self, ast: &Ast) -> Vec<Inst> {
-        let mut insts = Vec::new();
-
-        let mut dst_addr = 2;
-        insts.push(Inst::Fail);
-
-        for child in ast.children.iter().rev() {
-            let mut child_insts = self.compile_root(child);
-            child_insts.reverse();
-
-            let next_addr = child_insts.len() as isize + 2;
-
-            insts.push(Inst::Jmp(dst_addr));
-            insts.extend(child_insts);
-            insts.push(Inst::Split(1, next_addr));
-
-            dst_addr += next_addr;
+    // checked with the whole program so far before a repeat is copied, a huge count
+    // stops before it is allocated
+    fn check_size(&self, size: usize) -> Result<(), String> {
+        if size > self.size_limit {
+            return Err(format!(
+                "ERROR: compiled program is too large, over {} instructions",
+                self.size_limit
+            ));
         }
-
-        insts.reverse();
-        insts
+        Ok(())
     }
 
-    fn compile_include_set(&mut self, ast: &Ast) -> Vec<Inst> {
-        let mut insts = Vec::new();
-        insts.push(Inst::Seek(1));
-        insts.push(Inst::Fail);
-
-        let mut dst_addr = 2;
-
-        for child in ast.children.iter().rev() {
-            match &child.kind {
-                AstKind::Match(kind) => match kind {
-                    MatchKind::Char(c) => {
-                        insts.push(Inst::JmpIfTrue(dst_addr));
-                        insts.push(Inst::CheckInclude(*c, *c));
-                        dst_addr += 2;
+    // the program is emitted in order at the end of insts, each node pushes the steps
+    // that are left of it after its children. a jump is patched once the code it
+    // jumps over is there, marks keep the addresses to patch
+    fn compile_root(&mut self, ir: &Ir, insts: &mut Vec<Inst>) -> Result<(), String> {
+        let mut marks: Vec<usize> = vec![];
+        let mut steps = vec![Step::Compile(ir)];
+
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Compile(ir) => self.compile_node(ir, insts, &mut steps),
+                Step::Emit(inst) => insts.push(inst),
+                Step::Mark => marks.push(insts.len()),
+                Step::AltBranch => {
+                    // a failed branch goes on with the next, a matched one leaves the alt
+                    let split_addr = marks.pop().unwrap();
+                    let jmp_addr = insts.len();
+                    insts[split_addr] = Inst::Split(1, (jmp_addr + 1 - split_addr) as isize);
+                    insts.push(Inst::Jmp(0));
+                    marks.push(jmp_addr);
+                }
+                Step::AltEnd(count) => {
+                    insts.push(Inst::Fail);
+                    let end = insts.len();
+                    for jmp_addr in marks.split_off(marks.len() - count) {
+                        insts[jmp_addr] = Inst::Jmp((end - jmp_addr) as isize);
                     }
-                    MatchKind::Range(a, b) => {
-                        insts.push(Inst::JmpIfTrue(dst_addr));
-                        insts.push(Inst::CheckInclude(*a, *b));
-                        dst_addr += 2;
+                }
+                Step::ConditionalNo => {
+                    let test_addr = marks.pop().unwrap();
+                    let jmp_addr = insts.len();
+                    if let Inst::JmpIfCaptured(_, offset) = &mut insts[test_addr] {
+                        *offset = (jmp_addr + 1 - test_addr) as isize;
+                    }
+                    insts.push(Inst::Jmp(0));
+                    marks.push(jmp_addr);
+                }
+                Step::ConditionalYes => {
+                    let jmp_addr = marks.pop().unwrap();
+                    insts[jmp_addr] = Inst::Jmp((insts.len() - jmp_addr) as isize);
+                }
+                Step::Star(greedy, progress_id) => {
+                    if let Some(progress_id) = progress_id {
+                        insts.push(Inst::CheckProgress(progress_id));
                     }
-                    _ => unreachable!(),
-                },
-                _ => unreachable!(),
+                    let split_addr = marks.pop().unwrap();
+                    let child_size = (insts.len() - split_addr - 1) as isize;
+                    insts.push(Inst::Jmp(-child_size - 1));
+                    insts[split_addr] = Self::split(greedy, 1, child_size + 2);
+                }
+                Step::Plus(greedy, None) => {
+                    let start = marks.pop().unwrap();
+                    let child_size = (insts.len() - start) as isize;
+                    insts.push(Self::split(greedy, -child_size, 1));
+                }
+                Step::Plus(greedy, Some(progress_id)) => {
+                    // the first iteration may be empty, the following ones may not
+                    let save_addr = marks.pop().unwrap();
+                    let child_size = (insts.len() - save_addr - 1) as isize;
+                    insts.push(Self::split(greedy, 1, 3));
+                    insts.push(Inst::CheckProgress(progress_id));
+                    insts.push(Inst::Jmp(-child_size - 3));
+                }
+                Step::Option(greedy) => {
+                    let split_addr = marks.pop().unwrap();
+                    let child_size = (insts.len() - split_addr - 1) as isize;
+                    insts[split_addr] = Self::split(greedy, 1, child_size + 1);
+                }
+                Step::Count(count) => {
+                    let child_insts = insts.split_off(marks.pop().unwrap());
+                    self.compile_repeat_count(insts, &child_insts, count)?;
+                }
+                Step::Min(count, greedy, progress_id) => {
+                    let child_insts = insts.split_off(marks.pop().unwrap());
+                    self.compile_repeat_count(insts, &child_insts, count)?;
+                    Self::compile_star(insts, &child_insts, greedy, progress_id);
+                }
+                Step::Range(min, max, greedy) => {
+                    let child_insts = insts.split_off(marks.pop().unwrap());
+                    self.compile_repeat_range(insts, &child_insts, min, max, greedy)?;
+                }
             }
         }
 
-        insts.reverse();
-        insts
+        Ok(())
     }
 
-    fn compile_exclude_set(&mut self, ast: &Ast) -> Vec<Inst> {
-        let mut insts = Vec::new();
-        insts.push(Inst::Fail);
-        insts.push(Inst::Jmp(2));
-        insts.push(Inst::Seek(1));
-
-        let mut fail_addr = 3;
-
-        for child in ast.children.iter().rev() {
-            match &child.kind {
-                AstKind::Match(kind) => match kind {
-                    MatchKind::Char(c) => {
-                        insts.push(Inst::JmpIfFalse(fail_addr));
-                        insts.push(Inst::CheckExclude(*c, *c));
-                        fail_addr += 2;
-                    }
-                    MatchKind::Range(a, b) => {
-                        insts.push(Inst::JmpIfFalse(fail_addr));
-                        insts.push(Inst::CheckExclude(*a, *b));
-                        fail_addr += 2;
-                    }
-                    _ => unreachable!(),
-                },
-                _ => unreachable!(),
+    // a leaf is emitted right away, anything else schedules its children and the
+    // steps after them
+    fn compile_node<'a>(&mut self, ir: &'a Ir, insts: &mut Vec<Inst>, steps: &mut Vec<Step<'a>>) {
+        let mut schedule = |list: Vec<Step<'a>>| steps.extend(list.into_iter().rev());
+        match ir {
+            Ir::Concat(items) => schedule(items.iter().map(Step::Compile).collect()),
+            Ir::Alt(items) => {
+                let mut list = vec![];
+                for item in items.iter() {
+                    list.extend([
+                        Step::Mark,
+                        Step::Emit(Inst::Split(0, 0)),
+                        Step::Compile(item),
+                        Step::AltBranch,
+                    ]);
+                }
+                list.push(Step::AltEnd(items.len()));
+                schedule(list);
+            }
+            Ir::Capture(0, item) => {
+                // group 0 is captured around the whole program
+                schedule(vec![Step::Compile(item)]);
+            }
+            Ir::Capture(cap_id, item) => {
+                if self.max_capture_id < *cap_id {
+                    self.max_capture_id = *cap_id;
+                }
+                schedule(vec![
+                    Step::Emit(Inst::CaptureStart(*cap_id)),
+                    Step::Compile(item),
+                    Step::Emit(Inst::CaptureEnd(*cap_id)),
+                ]);
             }
+            Ir::Atomic(item) => schedule(vec![
+                Step::Emit(Inst::AtomicStart),
+                Step::Compile(item),
+                Step::Emit(Inst::AtomicEnd),
+            ]),
+            Ir::Conditional(cap_id, yes, no) => schedule(vec![
+                Step::Mark,
+                Step::Emit(Inst::JmpIfCaptured(*cap_id, 0)),
+                Step::Compile(no),
+                Step::ConditionalNo,
+                Step::Compile(yes),
+                Step::ConditionalYes,
+            ]),
+            Ir::Call(cap_id) => {
+                self.calls.push(*cap_id);
+                insts.push(Inst::Call(*cap_id));
+            }
+            Ir::Repeat(repeat) => schedule(self.compile_repeat(repeat)),
+            Ir::Char(c) => insts.push(Inst::MatchChar(*c)),
+            Ir::Class(ranges) => insts.extend(Self::compile_class(ranges)),
+            Ir::Any => insts.push(Inst::MatchCharAny),
+            Ir::Grapheme => insts.push(Inst::MatchGrapheme),
+            Ir::Look(look) => insts.extend(Self::compile_look(look)),
+            Ir::ResetStart => insts.push(Inst::CaptureStart(0)),
         }
-
-        insts.reverse();
-        insts
     }
 
-    fn compile_star(&mut self, ast: &Ast, greedy: &GreedyKind) -> Vec<Inst> {
-        let child_insts = self.compile_root(&ast.children[0]);
-        let child_size = child_insts.len() as isize;
-
+    fn compile_class(ranges: &[(char, char)]) -> Vec<Inst> {
         let mut insts = Vec::new();
-        if matches!(greedy, &GreedyKind::Greedy) {
-            insts.push(Inst::Split(1, child_size + 2));
-        } else {
-            insts.push(Inst::Split(child_size + 2, 1));
-        }
-        insts.extend(child_insts);
-        insts.push(Inst::Jmp(-child_size - 1));
-
-        insts
-    }
+        insts.push(Inst::Seek(1));
+        insts.push(Inst::Fail);
 
-    fn compile_plus(&mut self, ast: &Ast, greedy: &GreedyKind) -> Vec<Inst> {
-        let child_insts = self.compile_root(&ast.children[0]);
-        let child_size = child_insts.len() as isize;
+        let mut dst_addr = 2;
 
-        let mut insts = Vec::new();
-        insts.extend(child_insts);
-        if matches!(greedy, &GreedyKind::Greedy) {
-            insts.push(Inst::Split(-child_size, 1));
-        } else {
-            insts.push(Inst::Split(1, -child_size));
+        for (a, b) in ranges.iter().rev() {
+            insts.push(Inst::JmpIfTrue(dst_addr));
+            insts.push(Inst::CheckInclude(*a, *b));
+            dst_addr += 2;
         }
 
+        insts.reverse();
         insts
     }
 
-    fn compile_option(&mut self, ast: &Ast, greedy: &GreedyKind) -> Vec<Inst> {
-        let child_insts = self.compile_root(&ast.children[0]);
-        let child_size = child_insts.len() as isize;
-
-        let mut insts = Vec::new();
-        if matches!(greedy, &GreedyKind::Greedy) {
-            insts.push(Inst::Split(1, child_size + 1));
-        } else {
-            insts.push(Inst::Split(child_size + 1, 1));
+    fn compile_repeat<'a>(&mut self, repeat: &'a Repeat) -> Vec<Step<'a>> {
+        let Repeat {
+            min,
+            max,
+            greedy,
+            item,
+            item_can_be_empty,
+        } = repeat;
+        let item = Step::Compile(item);
+
+        // stop looping once an iteration matches nothing
+        let mut progress_id = None;
+        if *item_can_be_empty && max.is_none() {
+            progress_id = Some(self.new_progress_id());
         }
-        insts.extend(child_insts);
 
-        insts
-    }
-
-    #[rustfmt::skip]
-    fn compile_repeat(&mut self,
-        ast: &Ast,
-        min: &RepeatKind,
-        max: &RepeatKind,
-        greedy: &GreedyKind,
-    ) -> Vec<Inst> {
-        match (min, max) {
-            (RepeatKind::Num(n), RepeatKind::Num(m)) if n == m => {
-                self.compile_repeat_count(ast, *n)
-            }
-            (RepeatKind::Num(n), RepeatKind::Num(m)) => {
-                self.compile_repeat_range(ast, *n, *m, greedy)
+        match (*min, *max) {
+            (0, None) => {
+                let mut steps = vec![Step::Mark, Step::Emit(Inst::Split(0, 0))];
+                steps.extend(progress_id.map(|id| Step::Emit(Inst::SaveProgress(id))));
+                steps.extend([item, Step::Star(*greedy, progress_id)]);
+                steps
             }
-            (RepeatKind::Num(c), RepeatKind::Infinity) => {
-                self.compile_repeat_min(ast, *c, greedy)
-            }
-            (RepeatKind::Infinity, _) => {
-                unreachable!()
+            (1, None) => {
+                let mut steps = vec![Step::Mark];
+                steps.extend(progress_id.map(|id| Step::Emit(Inst::SaveProgress(id))));
+                steps.extend([item, Step::Plus(*greedy, progress_id)]);
+                steps
             }
+            (0, Some(1)) => vec![
+                Step::Mark,
+                Step::Emit(Inst::Split(0, 0)),
+                item,
+                Step::Option(*greedy),
+            ],
+            (n, Some(m)) if n == m => vec![Step::Mark, item, Step::Count(n)],
+            (n, Some(m)) => vec![Step::Mark, item, Step::Range(n, m, *greedy)],
+            (n, None) => vec![Step::Mark, item, Step::Min(n, *greedy, progress_id)],
         }
     }
 
-    fn compile_repeat_count(&mut self, ast: &Ast, count: u32) -> Vec<Inst> {
-        let child_insts = self.compile_root(&ast.children[0]);
+    fn compile_star(
+        insts: &mut Vec<Inst>,
+        child_insts: &[Inst],
+        greedy: bool,
+        progress_id: Option<usize>,
+    ) {
+        let progress_size = if progress_id.is_some() { 2 } else { 0 };
+        let child_size = (child_insts.len() + progress_size) as isize;
+
+        insts.push(Self::split(greedy, 1, child_size + 2));
+        insts.extend(progress_id.map(Inst::SaveProgress));
+        insts.extend_from_slice(child_insts);
+        insts.extend(progress_id.map(Inst::CheckProgress));
+        insts.push(Inst::Jmp(-child_size - 1));
+    }
 
-        let mut insts = Vec::new();
-        for _ in 0..count {
-            insts.extend(child_insts.clone());
+    fn compile_repeat_count(
+        &self,
+        insts: &mut Vec<Inst>,
+        child_insts: &[Inst],
+        count: u32,
+    ) -> Result<(), String> {
+        if child_insts.is_empty() {
+            return Ok(());
         }
-        insts
-    }
+        let size = child_insts.len().saturating_mul(count as usize);
+        self.check_size(insts.len().saturating_add(size))?;
 
-    fn compile_repeat_min(&mut self, ast: &Ast, count: u32, greedy: &GreedyKind) -> Vec<Inst> {
-        let mut insts = Vec::new();
-        insts.extend(self.compile_repeat_count(ast, count));
-        insts.extend(self.compile_star(ast, greedy));
-        insts
+        for _ in 0..count {
+            insts.extend_from_slice(child_insts);
+        }
+        Ok(())
     }
 
     fn compile_repeat_range(
-        &mut self,
-        ast: &Ast,
+        &self,
+        insts: &mut Vec<Inst>,
+        child_insts: &[Inst],
         min: u32,
         max: u32,
-        greedy: &GreedyKind,
-    ) -> Vec<Inst> {
-        let mut child_insts = self.compile_root(&ast.children[0]);
-        child_insts.reverse();
-
-        let mut insts = Vec::new();
-        let mut dst_addr = 1;
-        for _ in min..max {
-            dst_addr += child_insts.len() as isize;
-
-            insts.extend(child_insts.clone());
-            if matches!(greedy, &GreedyKind::Greedy) {
-                insts.push(Inst::Split(1, dst_addr));
-            } else {
-                insts.push(Inst::Split(dst_addr, 1));
-            }
+        greedy: bool,
+    ) -> Result<(), String> {
+        let step = child_insts.len() + 1;
+        let size = step.saturating_mul((max - min) as usize);
+        self.check_size(insts.len().saturating_add(size))?;
+        self.compile_repeat_count(insts, child_insts, min)?;
+
+        // each optional copy holds the rest, skipping it skips them all
+        for rest in (1..=max - min).rev() {
+            insts.push(Self::split(greedy, 1, (step * rest as usize) as isize));
+            insts.extend_from_slice(child_insts);
         }
-
-        let mut repeat_insts = self.compile_repeat_count(ast, min);
-        repeat_insts.reverse();
-        insts.extend(repeat_insts);
-
-        insts.reverse();
-        insts
+        Ok(())
     }
 
-    fn compile_match(kind: &MatchKind) -> Vec<Inst> {
-        match kind {
-            MatchKind::Any => [Inst::MatchCharAny].into(),
-            MatchKind::Char(c) => [Inst::MatchChar(*c)].into(),
-            MatchKind::Range(_, _) => unreachable!(),
+    // the first address is tried first when greedy
+    fn split(greedy: bool, a: isize, b: isize) -> Inst {
+        if greedy {
+            Inst::Split(a, b)
+        } else {
+            Inst::Split(b, a)
         }
     }
 
-    fn compile_position(position: &PositionKind) -> Vec<Inst> {
-        match position {
-            PositionKind::SoL => [Inst::MatchPosSOL].into(),
-            PositionKind::EoL => [Inst::MatchPosEOL].into(),
+    fn new_progress_id(&mut self) -> usize {
+        self.progress_size += 1;
+        self.progress_size - 1
+    }
+
+    fn compile_look(look: &Look) -> Vec<Inst> {
+        match look {
+            Look::SoL => [Inst::MatchPosSOL].into(),
+            Look::EoL => [Inst::MatchPosEOL].into(),
+            Look::SoS => [Inst::MatchPosSOS].into(),
         }
     }
 }
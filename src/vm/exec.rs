@@ -1,31 +1,60 @@
+use std::iter::once;
+
 use super::inst::Inst;
+use crate::grapheme;
+
+enum Backtrack {
+    Branch(usize, usize, Vec<usize>, Vec<usize>, Vec<usize>, Vec<Frame>),
+    AtomicMark,
+}
+
+#[derive(Clone)]
+struct Frame {
+    return_pc: usize,
+    cap_pos_start: Vec<usize>,
+    cap_pos_end: Vec<usize>,
+    progress_pos: Vec<usize>,
+}
 
 pub(crate) struct Executer<'a> {
     insts: &'a Vec<Inst>,
-    stack: Vec<(usize, usize, Vec<usize>, Vec<usize>)>,
+    stack: Vec<Backtrack>,
     pc: usize,
     sp: usize,
+    start: usize, // where the search started, for '\G'
     is_fail: bool,
     is_match: bool,
     check_result: bool,
     capture_needed: bool,
     cap_pos_start: Vec<usize>,
     cap_pos_end: Vec<usize>,
+    progress_pos: Vec<usize>,
+    frames: Vec<Frame>,
+    recursion_limit: usize,
 }
 
 impl<'a> Executer<'a> {
-    pub fn new(insts: &'a Vec<Inst>, capture_size: usize) -> Self {
+    pub fn new(
+        insts: &'a Vec<Inst>,
+        capture_size: usize,
+        progress_size: usize,
+        recursion_limit: usize,
+    ) -> Self {
         Executer {
             insts,
             stack: vec![],
             pc: 0,
             sp: 0,
+            start: 0,
             is_fail: false,
             is_match: false,
             check_result: false,
             capture_needed: true,
             cap_pos_start: vec![0; capture_size],
-            cap_pos_end: vec![0; capture_size],
+            cap_pos_end: vec![usize::MAX; capture_size],
+            progress_pos: vec![0; progress_size],
+            frames: vec![],
+            recursion_limit,
         }
     }
 
@@ -33,18 +62,24 @@ impl<'a> Executer<'a> {
         let insts = self.insts;
         let capture_needed = self.capture_needed;
         let capture_size = self.cap_pos_start.len();
+        let progress_size = self.progress_pos.len();
+        let recursion_limit = self.recursion_limit;
 
         *self = Executer {
             insts,
             stack: vec![],
             pc: 0,
             sp: 0,
+            start: 0,
             is_fail: false,
             is_match: false,
             check_result: false,
             capture_needed,
             cap_pos_start: vec![0; capture_size],
-            cap_pos_end: vec![0; capture_size],
+            cap_pos_end: vec![usize::MAX; capture_size],
+            progress_pos: vec![0; progress_size],
+            frames: vec![],
+            recursion_limit,
         }
     }
 
@@ -53,36 +88,8 @@ impl<'a> Executer<'a> {
     }
 
     pub fn execute<'b>(&mut self, str: &'b str) -> Vec<&'b str> {
-        for i in 0..str.len() {
-            self.reset();
-            self.sp = i;
-
-            let result = self.execute_(str);
-            if !result.is_empty() {
-                return result;
-            }
-        }
-        vec![]
-    }
-
-    fn execute_<'b>(&mut self, str: &'b str) -> Vec<&'b str> {
-        loop {
-            self.execute_step(str);
-
-            if self.is_match {
-                break; // match
-            }
-            if self.is_fail {
-                if let Some((sp, pc, cap_s, cap_e)) = self.stack.pop() {
-                    self.sp = sp;
-                    self.pc = pc;
-                    self.cap_pos_start = cap_s;
-                    self.cap_pos_end = cap_e;
-                    self.is_fail = false;
-                } else {
-                    return vec![]; // unmatch
-                }
-            }
+        if !self.search(str, 0) {
+            return vec![]; // unmatch
         }
 
         let mut captures = vec![];
@@ -93,7 +100,7 @@ impl<'a> Executer<'a> {
                 let pos1 = self.cap_pos_start[cap_id];
                 let pos2 = self.cap_pos_end[cap_id];
 
-                if pos1 < pos2 {
+                if pos2 != usize::MAX && pos1 <= pos2 {
                     captures.push(&str[pos1..pos2]);
                 } else {
                     captures.push("");
@@ -104,6 +111,59 @@ impl<'a> Executer<'a> {
         return captures;
     }
 
+    pub fn find_at(&mut self, str: &str, start: usize) -> Option<(usize, usize)> {
+        if !self.search(str, start) {
+            return None;
+        }
+        Some((self.cap_pos_start[0], self.cap_pos_end[0]))
+    }
+
+    fn search(&mut self, str: &str, start: usize) -> bool {
+        // the end is a start too, an empty match can be there
+        let ends = once(str.len() - start);
+        for i in str[start..].char_indices().map(|(i, _)| i).chain(ends) {
+            self.reset();
+            self.start = start;
+            self.sp = start + i;
+
+            if self.execute_(str) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn execute_(&mut self, str: &str) -> bool {
+        loop {
+            self.execute_step(str);
+
+            if self.is_match {
+                break; // match
+            }
+            if self.is_fail {
+                match self.stack.pop() {
+                    Some(Backtrack::Branch(sp, pc, cap_s, cap_e, progress, frames)) => {
+                        self.sp = sp;
+                        self.pc = pc;
+                        self.cap_pos_start = cap_s;
+                        self.cap_pos_end = cap_e;
+                        self.progress_pos = progress;
+                        self.frames = frames;
+                        self.is_fail = false;
+                    }
+                    Some(Backtrack::AtomicMark) => {
+                        // leave the failed atomic group, keep unwinding
+                    }
+                    None => {
+                        return false; // unmatch
+                    }
+                }
+            }
+        }
+
+        return true;
+    }
+
     fn execute_step(&mut self, str: &str) {
         match &self.insts[self.pc] {
             Inst::Fail => {
@@ -124,8 +184,61 @@ impl<'a> Executer<'a> {
                 self.pc += 1;
                 return;
             }
+            Inst::AtomicStart => {
+                self.stack.push(Backtrack::AtomicMark);
+                self.pc += 1;
+                return;
+            }
+            Inst::AtomicEnd => {
+                // discard the branches left inside the group
+                while let Some(entry) = self.stack.pop() {
+                    if matches!(entry, Backtrack::AtomicMark) {
+                        break;
+                    }
+                }
+                self.pc += 1;
+                return;
+            }
+            Inst::Call(addr) => {
+                if self.frames.len() < self.recursion_limit {
+                    self.frames.push(Frame {
+                        return_pc: self.pc + 1,
+                        cap_pos_start: self.cap_pos_start.clone(),
+                        cap_pos_end: self.cap_pos_end.clone(),
+                        progress_pos: self.progress_pos.clone(),
+                    });
+                    self.pc = *addr;
+                    return;
+                }
+            }
+            Inst::Return => {
+                // captures made inside the call are dropped on return
+                let frame = self.frames.pop().unwrap();
+                self.pc = frame.return_pc;
+                self.cap_pos_start = frame.cap_pos_start;
+                self.cap_pos_end = frame.cap_pos_end;
+                self.progress_pos = frame.progress_pos;
+                return;
+            }
+            Inst::SaveProgress(progress_id) => {
+                self.progress_pos[*progress_id] = self.sp;
+                self.pc += 1;
+                return;
+            }
+            Inst::CheckProgress(progress_id) => {
+                if self.progress_pos[*progress_id] != self.sp {
+                    self.pc += 1;
+                    return;
+                }
+            }
             Inst::Seek(offset) => {
-                self.sp = self.sp.saturating_add_signed(*offset);
+                // offset counts chars, sp is a byte position
+                let len: usize = str[self.sp..]
+                    .chars()
+                    .take(*offset as usize)
+                    .map(char::len_utf8)
+                    .sum();
+                self.sp += len;
                 self.pc += 1;
                 return;
             }
@@ -141,8 +254,8 @@ impl<'a> Executer<'a> {
                 }
                 return;
             }
-            Inst::JmpIfFalse(addr) => {
-                if !self.check_result {
+            Inst::JmpIfCaptured(cap_id, addr) => {
+                if self.cap_pos_end[*cap_id] != usize::MAX {
                     self.pc = self.pc.saturating_add_signed(*addr);
                 } else {
                     self.pc += 1;
@@ -150,27 +263,36 @@ impl<'a> Executer<'a> {
                 return;
             }
             Inst::Split(addr1, addr2) => {
-                self.stack.push((
+                self.stack.push(Backtrack::Branch(
                     self.sp,
                     self.pc.saturating_add_signed(*addr2),
                     self.cap_pos_start.clone(),
                     self.cap_pos_end.clone(),
+                    self.progress_pos.clone(),
+                    self.frames.clone(),
                 ));
                 self.pc = self.pc.saturating_add_signed(*addr1);
                 return;
             }
             Inst::MatchChar(s) => {
-                if let Some(c) = str.chars().nth(self.sp) {
+                if let Some(c) = str[self.sp..].chars().next() {
                     if *s == c {
-                        self.sp += 1;
+                        self.sp += c.len_utf8();
                         self.pc += 1;
                         return;
                     }
                 }
             }
             Inst::MatchCharAny => {
-                if let Some(_) = str.chars().nth(self.sp) {
-                    self.sp += 1;
+                if let Some(c) = str[self.sp..].chars().next() {
+                    self.sp += c.len_utf8();
+                    self.pc += 1;
+                    return;
+                }
+            }
+            Inst::MatchGrapheme => {
+                if let Some(end) = grapheme::next_boundary(str, self.sp) {
+                    self.sp = end;
                     self.pc += 1;
                     return;
                 }
@@ -187,16 +309,15 @@ impl<'a> Executer<'a> {
                     return;
                 }
             }
-            Inst::CheckInclude(a, b) => {
-                if let Some(c) = str.chars().nth(self.sp) {
-                    self.check_result = *a <= c && c <= *b;
+            Inst::MatchPosSOS => {
+                if self.sp == self.start {
                     self.pc += 1;
                     return;
                 }
             }
-            Inst::CheckExclude(a, b) => {
-                if let Some(c) = str.chars().nth(self.sp) {
-                    self.check_result = *a > c || c > *b;
+            Inst::CheckInclude(a, b) => {
+                if let Some(c) = str[self.sp..].chars().next() {
+                    self.check_result = *a <= c && c <= *b;
                     self.pc += 1;
                     return;
                 }
@@ -4,15 +4,22 @@ pub(crate) enum Inst {
     Success,
     CaptureStart(usize),
     CaptureEnd(usize),
+    AtomicStart,
+    AtomicEnd,
+    Call(usize),
+    Return,
+    SaveProgress(usize),
+    CheckProgress(usize),
     Seek(isize),
     Jmp(isize),
     JmpIfTrue(isize),
-    JmpIfFalse(isize),
+    JmpIfCaptured(usize, isize),
     Split(isize, isize),
     MatchChar(char),
     MatchCharAny,
+    MatchGrapheme,
     MatchPosSOL,
     MatchPosEOL,
+    MatchPosSOS,
     CheckInclude(char, char),
-    CheckExclude(char, char),
 }
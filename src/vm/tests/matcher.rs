@@ -1,4 +1,5 @@
 use super::super::*;
+use crate::Syntax;
 
 #[cfg(test)]
 mod basic_match {
@@ -155,6 +156,674 @@ fn union() {
     assert_eq!(vm.is_match("defz"), true);
 }
 
+#[test]
+fn atomic_group() {
+    {
+        let src = "a(?>bc|b)c";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("abcc"), true);
+        assert_eq!(vm.is_match("abc"), false);
+        assert_eq!(vm.is_match("zabcc"), true);
+        assert_eq!(vm.is_match("abccz"), true);
+    }
+    {
+        let src = "(?>a*)a";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), false);
+        assert_eq!(vm.is_match("aaa"), false);
+        assert_eq!(vm.is_match("aaab"), false);
+    }
+    {
+        let src = "(?>a+)b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("ab"), true);
+        assert_eq!(vm.is_match("aaab"), true);
+        assert_eq!(vm.is_match("b"), false);
+        assert_eq!(vm.is_match("zaabz"), true);
+    }
+}
+
+#[test]
+fn empty_union() {
+    {
+        let src = "a(b|)c";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("abc"), true);
+        assert_eq!(vm.is_match("ac"), true);
+        assert_eq!(vm.is_match("adc"), false);
+    }
+    {
+        let src = "a(|b)c";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("abc"), true);
+        assert_eq!(vm.is_match("ac"), true);
+        assert_eq!(vm.is_match("adc"), false);
+    }
+    {
+        let src = "a|";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("b"), true);
+        assert_eq!(vm.is_match(""), true);
+    }
+}
+
+#[test]
+fn empty_group() {
+    {
+        let src = "a()b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("ab"), true);
+        assert_eq!(vm.is_match("a"), false);
+        assert_eq!(vm.is_match("zabz"), true);
+    }
+    {
+        let src = "a(?:)?b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("ab"), true);
+        assert_eq!(vm.is_match("a"), false);
+    }
+    {
+        let src = "()";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match(""), true);
+        assert_eq!(vm.is_match("a"), true);
+    }
+}
+
+#[test]
+fn empty_loop() {
+    {
+        let src = "(a|)*b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("aab"), true);
+        assert_eq!(vm.is_match("b"), true);
+        assert_eq!(vm.is_match("c"), false);
+    }
+    {
+        let src = "(a*)*b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("aab"), true);
+        assert_eq!(vm.is_match("b"), true);
+        assert_eq!(vm.is_match("c"), false);
+    }
+    {
+        let src = "(a*)+b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("aab"), true);
+        assert_eq!(vm.is_match("b"), true);
+        assert_eq!(vm.is_match("c"), false);
+    }
+    {
+        let src = "()*a(?:)+";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("b"), false);
+    }
+}
+
+#[test]
+fn conditional() {
+    {
+        let src = "^(<)?a(?(1)>|)$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("<a>"), true);
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("<a"), false);
+        assert_eq!(vm.is_match("a>"), false);
+    }
+    {
+        let src = "^(?<open><)?a(?(<open>)>)$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("<a>"), true);
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("<a"), false);
+        assert_eq!(vm.is_match("a>"), false);
+    }
+    {
+        let src = "(a)?(?(1)b|c)d";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("abd"), true);
+        assert_eq!(vm.is_match("cd"), true);
+        assert_eq!(vm.is_match("acd"), true);
+        assert_eq!(vm.is_match("bd"), false);
+    }
+    {
+        let src = "^(?(<x>)a|b)(?<x>x)$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("bx"), true);
+        assert_eq!(vm.is_match("ax"), false);
+    }
+}
+
+#[test]
+fn call() {
+    {
+        let src = "^(\\((?:[^()]|(?1))*\\))$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("()"), true);
+        assert_eq!(vm.is_match("(a(b)(c(d)))"), true);
+        assert_eq!(vm.is_match("(a(b)"), false);
+        assert_eq!(vm.is_match("(a))"), false);
+    }
+    {
+        let src = "^(?<list>\\[(?&list)*\\])$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("[[][[]]]"), true);
+        assert_eq!(vm.is_match("[[]"), false);
+    }
+    {
+        let src = "^(?&num)=(?<num>[0-9]+)$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("12=345"), true);
+        assert_eq!(vm.is_match("=345"), false);
+    }
+    {
+        let src = "^a(?R)?b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("ab"), true);
+        assert_eq!(vm.is_match("aabb"), false);
+    }
+    {
+        let src = "(?>a(?R)?b)";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("aabb"), true);
+        assert_eq!(vm.is_match("aab"), true);
+        assert_eq!(vm.is_match("aa"), false);
+    }
+    {
+        let src = "(?R)a";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("aaa"), false);
+    }
+}
+
+#[test]
+fn recursion_limit() {
+    let src = "^(a(?1)?b)$";
+    let vm = RegexBuilder::new(src)
+        .recursion_limit(1)
+        .build_vm()
+        .unwrap();
+
+    assert_eq!(vm.is_match("ab"), true);
+    assert_eq!(vm.is_match("aabb"), true);
+    assert_eq!(vm.is_match("aaabbb"), false);
+}
+
+#[test]
+fn quote() {
+    {
+        let src = "^\\Q1+1=(2)\\E$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("1+1=(2)"), true);
+        assert_eq!(vm.is_match("11=2"), false);
+    }
+    {
+        let src = "a\\Q.|b";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a.|b"), true);
+        assert_eq!(vm.is_match("b"), false);
+    }
+}
+
+#[test]
+fn escape() {
+    const CHARS: &str = "ab01 \\|*+?,-^$.{}()[]&~éß☃";
+
+    // deterministic lcg, so failures can be reproduced
+    let mut seed: u64 = 0x2545_f491;
+    let mut next = |n: usize| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (seed >> 33) as usize % n
+    };
+
+    for _ in 0..200 {
+        let len = next(12) + 1;
+        let str: String = (0..len)
+            .map(|_| CHARS.chars().nth(next(CHARS.chars().count())).unwrap())
+            .collect();
+
+        let vm = Vm::new(&format!("^{}$", crate::escape(&str))).unwrap();
+        assert_eq!(vm.is_match(&str), true, "{:?}", str);
+        assert_eq!(vm.is_match(&format!("{}z", str)), false, "{:?}", str);
+
+        let vm = Vm::new(&crate::escape(&str)).unwrap();
+        assert_eq!(vm.captures(&str), vec![str.as_str()], "{:?}", str);
+    }
+}
+
+#[test]
+fn comment() {
+    {
+        let src = "^a(?#one or more)+(?#then)b$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("aab"), true);
+        assert_eq!(vm.is_match("b"), false);
+        assert_eq!(vm.is_match("a(?#then)b"), false);
+    }
+    {
+        let src = "^x((?#nothing))?y$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("xy"), true);
+    }
+}
+
+#[test]
+fn posix_syntax() {
+    {
+        let src = "^\\([a-z]*\\)=\\([[:digit:]]\\{1,3\\}\\)$";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::PosixBasic)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("abc=123"), true);
+        assert_eq!(vm.is_match("abc=1234"), false);
+        assert_eq!(vm.is_match("ABC=1"), false);
+    }
+    {
+        let src = "a+|b?c";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::PosixBasic)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("a+|b?c"), true);
+        assert_eq!(vm.is_match("aa"), false);
+    }
+    {
+        let src = "^(ab|cd)+[^[:space:]]$";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::PosixExtended)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("abcdx"), true);
+        assert_eq!(vm.is_match("abcd "), false);
+        assert_eq!(vm.is_match("x"), false);
+    }
+}
+
+#[test]
+fn glob_syntax() {
+    {
+        let src = "src/**/*.{rs,md}";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Glob)
+            .literal_separator(true)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("src/lib.rs"), true);
+        assert_eq!(vm.is_match("src/vm/tests/mod.rs"), true);
+        assert_eq!(vm.is_match("src/README.md"), true);
+        assert_eq!(vm.is_match("src/lib.rs.bak"), false);
+        assert_eq!(vm.is_match("tests/src/lib.rs"), false);
+    }
+    {
+        let src = "*.rs";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Glob)
+            .literal_separator(true)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("lib.rs"), true);
+        assert_eq!(vm.is_match("src/lib.rs"), false);
+    }
+    {
+        let src = "*.rs";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Glob)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("src/lib.rs"), true);
+    }
+    {
+        let src = "file[0-9][!a].?xt";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Glob)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("file1b.txt"), true);
+        assert_eq!(vm.is_match("file1a.txt"), false);
+        assert_eq!(vm.is_match("filex.txt"), false);
+    }
+    {
+        let src = "*";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Glob)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match(""), true);
+        assert_eq!(vm.is_match("a"), true);
+    }
+}
+
+#[test]
+fn like_syntax() {
+    {
+        let src = "ab%_";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Like)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match("abc"), true);
+        assert_eq!(vm.is_match("ab_cde"), true);
+        assert_eq!(vm.is_match("ab"), false);
+        assert_eq!(vm.is_match("xabc"), false);
+    }
+    {
+        let src = "%";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Like)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match(""), true);
+        assert_eq!(vm.is_match("abc"), true);
+    }
+    {
+        let src = "";
+        let vm = RegexBuilder::new(src)
+            .syntax(Syntax::Like)
+            .build_vm()
+            .unwrap();
+
+        assert_eq!(vm.is_match(""), true);
+        assert_eq!(vm.is_match("a"), false);
+    }
+}
+
+#[test]
+fn case_insensitive() {
+    {
+        let src = "^(?i)straße$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("STRASSE"), false);
+        assert_eq!(vm.is_match("STRAẞE"), true);
+        assert_eq!(vm.is_match("ſtraße"), true);
+    }
+    {
+        let src = "(?i)[a-z]+";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("\u{212A}"), true);
+        assert_eq!(vm.is_match("ı"), false);
+        assert_eq!(vm.is_match("İ"), false);
+    }
+    {
+        let src = "^(?i:σ)+$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("Σσς"), true);
+        assert_eq!(vm.is_match("Σs"), false);
+    }
+    {
+        // folds added after Unicode 14, the tables follow UNICODE_VERSION
+        let src = "^(?i)\u{1C89}\u{A7CB}\u{1FD3}$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("\u{1C8A}\u{264}\u{390}"), true);
+        assert_eq!(crate::UNICODE_VERSION, (17, 0, 0));
+    }
+    {
+        let src = "^k(?i)k$";
+        let vm = RegexBuilder::new(src).build_vm().unwrap();
+        assert_eq!(vm.is_match("kK"), true);
+        assert_eq!(vm.is_match("KK"), false);
+
+        let vm = RegexBuilder::new(src)
+            .case_insensitive(true)
+            .build_vm()
+            .unwrap();
+        assert_eq!(vm.is_match("KK"), true);
+    }
+}
+
+#[test]
+fn case_insensitive_negated_set() {
+    // the set is folded before it's negated, so no case of 'a' is left
+    for src in ["(?i)^[^a--b]$", "(?i)^[^a&&a]$", "(?i)^[^[a]]$"] {
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), false);
+        assert_eq!(vm.is_match("A"), false);
+        assert_eq!(vm.is_match("b"), true);
+        assert_eq!(vm.is_match("C"), true);
+    }
+}
+
+#[test]
+fn unicode() {
+    {
+        let src = "é.☃$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("café☃"), false);
+        assert_eq!(vm.is_match("caféx☃"), true);
+        assert_eq!(vm.is_match("xé🦀☃"), true);
+    }
+    {
+        let src = "[α-ω]+[^α-ω]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("λόγος."), true);
+        assert_eq!(vm.is_match("λογος"), false);
+    }
+}
+
+#[test]
+fn grapheme() {
+    {
+        let src = "^\\X$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("e\u{301}"), true);
+        assert_eq!(vm.is_match("\r\n"), true);
+        assert_eq!(vm.is_match("\n\r"), false);
+        assert_eq!(vm.is_match("👍🏽"), true);
+        assert_eq!(vm.is_match("👨‍👩‍👧"), true);
+        assert_eq!(vm.is_match("🇯🇵"), true);
+        assert_eq!(vm.is_match("🇯🇵🇺"), false);
+        assert_eq!(vm.is_match("각"), true);
+        assert_eq!(vm.is_match("क्ष"), true);
+        assert_eq!(vm.is_match("ab"), false);
+        assert_eq!(vm.is_match(""), false);
+    }
+    {
+        let src = "^\\X{3}$";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("🇯🇵🇺🇸🇫🇷"), true);
+        assert_eq!(vm.is_match("a\u{301}b\u{308}\u{301}c"), true);
+        assert_eq!(vm.is_match("a\u{301}b"), false);
+    }
+    {
+        let src = "^.$";
+        let vm = RegexBuilder::new(src).build_vm().unwrap();
+        assert_eq!(vm.is_match("👍🏽"), false);
+
+        let vm = RegexBuilder::new(src)
+            .grapheme_dot(true)
+            .build_vm()
+            .unwrap();
+        assert_eq!(vm.is_match("👍🏽"), true);
+        assert_eq!(vm.is_match("x"), true);
+    }
+}
+
+#[test]
+fn reset_start() {
+    let src = "^user=\\K[a-z]+$";
+    let vm = Vm::new(src).unwrap();
+
+    assert_eq!(vm.is_match("user=alice"), true);
+    assert_eq!(vm.is_match("alice"), false);
+    assert_eq!(vm.is_match("user="), false);
+}
+
+#[test]
+fn search_start() {
+    {
+        let src = "\\G[0-9]+";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("12ab"), true);
+        assert_eq!(vm.is_match("ab12"), false);
+        assert_eq!(vm.find_at("ab12", 2), Some((2, 4)));
+        assert_eq!(vm.find_at("ab12", 1), None);
+    }
+    {
+        // '^' stays at the start of the text
+        let src = "^a|\\Gb";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.find_at("ab", 0), Some((0, 1)));
+        assert_eq!(vm.find_at("ab", 1), Some((1, 2)));
+        assert_eq!(vm.find_at("aa", 1), None);
+        assert_eq!(vm.is_match("b"), true);
+    }
+    {
+        let src = "(^|x)a";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("xa"), true);
+        assert_eq!(vm.is_match("ya"), false);
+    }
+}
+
+#[test]
+fn find_at() {
+    let src = "\\G(?:[a-z]+|[0-9]+|[ =])";
+    let vm = Vm::new(src).unwrap();
+
+    let text = "x = 42 y";
+    let mut tokens = vec![];
+    let mut start = 0;
+    while let Some((s, e)) = vm.find_at(text, start) {
+        tokens.push(&text[s..e]);
+        start = e;
+        if start == text.len() {
+            break;
+        }
+    }
+    assert_eq!(tokens, vec!["x", " ", "=", " ", "42", " ", "y"]);
+
+    // an unknown char stops the tokens
+    assert_eq!(vm.find_at("ab+cd", 2), None);
+    assert_eq!(vm.find_at("ab+cd", 3), Some((3, 5)));
+    assert_eq!(vm.find_at("é1", 2), Some((2, 3)));
+
+    // a start past the end or inside a char finds nothing
+    let vm = Vm::new("").unwrap();
+    assert_eq!(vm.find_at("ab", 2), Some((2, 2)));
+    assert_eq!(vm.find_at("ab", 3), None);
+    assert_eq!(vm.find_at("é", 1), None);
+
+    // the match starts after '\K'
+    let vm = Vm::new("a\\Kb").unwrap();
+    assert_eq!(vm.find_at("xab", 0), Some((2, 3)));
+}
+
+#[test]
+fn leftmost_first() {
+    // the first alternative that matches wins, not the longest
+    let vm = Vm::new("\\G(?:a|ab)").unwrap();
+    assert_eq!(vm.find_at("ab", 0), Some((0, 1)));
+
+    let vm = Vm::new("(?:a|ab)(?:c|bcd)").unwrap();
+    assert_eq!(vm.find_at("abcd", 0), Some((0, 4)));
+
+    let vm = Vm::new("(?:ab??c|a)").unwrap();
+    assert_eq!(vm.find_at("abc", 0), Some((0, 3)));
+
+    let vm = Vm::new("a+?|aaa").unwrap();
+    assert_eq!(vm.find_at("aaa", 0), Some((0, 1)));
+
+    let vm = Vm::new("^a|\\Gab").unwrap();
+    assert_eq!(vm.find_at("ab", 0), Some((0, 1)));
+}
+
+#[test]
+fn size_limit() {
+    assert_eq!(Vm::new("(a{1000}){1000}").is_err(), true);
+    assert_eq!(Vm::new("a{4294967295}").is_err(), true);
+    assert_eq!(Vm::new("(?:){4294967295}b").is_ok(), true);
+
+    // many repeats under the limit each still add up over it
+    let src = "a{99999}".repeat(1000);
+    assert_eq!(RegexBuilder::new(&src).build_vm().is_err(), true);
+    let src = "(a{99999})".repeat(1000);
+    assert_eq!(RegexBuilder::new(&src).build_vm().is_err(), true);
+    let src = "a{0,99999}".repeat(1000);
+    assert_eq!(RegexBuilder::new(&src).build_vm().is_err(), true);
+
+    let src = "^(?:ab){10}$";
+    assert_eq!(
+        RegexBuilder::new(src).size_limit(10).build_vm().is_err(),
+        true
+    );
+
+    let vm = RegexBuilder::new(src).size_limit(1000).build_vm().unwrap();
+    assert_eq!(vm.is_match(&"ab".repeat(10)), true);
+    assert_eq!(vm.is_match(&"ab".repeat(9)), false);
+}
+
+#[test]
+fn nest_limit() {
+    // deep patterns fail the default limit, a raised one builds them
+    let stars = format!("a{}", "*".repeat(50_000));
+    let groups = format!("{}a{}", "(".repeat(50_000), ")".repeat(50_000));
+    assert_eq!(Vm::new(&stars).is_err(), true);
+    assert_eq!(Vm::new(&groups).is_err(), true);
+
+    let mut builder = RegexBuilder::new(&stars);
+    builder.nest_limit(100_000).size_limit(1_000_000);
+    assert_eq!(builder.build_vm().is_ok(), true);
+
+    let mut builder = RegexBuilder::new(&groups);
+    builder.nest_limit(100_000).size_limit(1_000_000);
+    let vm = builder.build_vm().unwrap();
+    assert_eq!(vm.is_match("xaz"), true);
+    assert_eq!(vm.is_match("xz"), false);
+}
+
 #[cfg(test)]
 mod greedy {
     use super::*;
@@ -386,6 +1055,17 @@ mod greedy {
             assert_eq!(vm.is_match("zabcabc"), true);
             assert_eq!(vm.is_match("abcabcz"), true);
         }
+        {
+            let src = "^(?:ab){0,3}$";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match(""), true);
+            assert_eq!(vm.is_match("ab"), true);
+            assert_eq!(vm.is_match("abab"), true);
+            assert_eq!(vm.is_match("ababab"), true);
+            assert_eq!(vm.is_match("abababab"), false);
+            assert_eq!(vm.is_match("aba"), false);
+        }
     }
 }
 
@@ -597,6 +1277,121 @@ mod non_greedy {
             assert_eq!(vm.is_match("zabcabc"), true);
             assert_eq!(vm.is_match("abcabcz"), true);
         }
+        {
+            let src = "^(?:ab){0,3}?$";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match(""), true);
+            assert_eq!(vm.is_match("ab"), true);
+            assert_eq!(vm.is_match("abab"), true);
+            assert_eq!(vm.is_match("ababab"), true);
+            assert_eq!(vm.is_match("abababab"), false);
+            assert_eq!(vm.is_match("aba"), false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod possessive {
+    use super::*;
+
+    #[test]
+    fn star() {
+        {
+            let src = "ab*+c";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("ac"), true);
+            assert_eq!(vm.is_match("abc"), true);
+            assert_eq!(vm.is_match("abbbc"), true);
+            assert_eq!(vm.is_match("az"), false);
+            assert_eq!(vm.is_match("zac"), true);
+            assert_eq!(vm.is_match("acz"), true);
+        }
+        {
+            let src = "ab*+b";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("ab"), false);
+            assert_eq!(vm.is_match("abb"), false);
+            assert_eq!(vm.is_match("abbb"), false);
+        }
+        {
+            let src = "a.*+b";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("ab"), false);
+            assert_eq!(vm.is_match("axb"), false);
+            assert_eq!(vm.is_match("axbaxb"), false);
+        }
+    }
+
+    #[test]
+    fn plus() {
+        {
+            let src = "ab++c";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("abc"), true);
+            assert_eq!(vm.is_match("abbbc"), true);
+            assert_eq!(vm.is_match("ac"), false);
+            assert_eq!(vm.is_match("zabc"), true);
+            assert_eq!(vm.is_match("abcz"), true);
+        }
+        {
+            let src = "ab++b";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("ab"), false);
+            assert_eq!(vm.is_match("abb"), false);
+            assert_eq!(vm.is_match("abbb"), false);
+        }
+    }
+
+    #[test]
+    fn option() {
+        {
+            let src = "ab?+c";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("ac"), true);
+            assert_eq!(vm.is_match("abc"), true);
+            assert_eq!(vm.is_match("a"), false);
+            assert_eq!(vm.is_match("zac"), true);
+        }
+        {
+            let src = "ab?+b";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("ab"), false);
+            assert_eq!(vm.is_match("abb"), true);
+        }
+    }
+
+    #[test]
+    fn repeat() {
+        {
+            let src = "a{2}+";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("aa"), true);
+            assert_eq!(vm.is_match("a"), false);
+        }
+        {
+            let src = "a{2,}+a";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("aa"), false);
+            assert_eq!(vm.is_match("aaaa"), false);
+        }
+        {
+            let src = "a{1,3}+a";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("aa"), false);
+            assert_eq!(vm.is_match("aaa"), false);
+            assert_eq!(vm.is_match("aaaa"), true);
+        }
     }
 }
 
@@ -666,6 +1461,57 @@ mod set {
         }
     }
 
+    #[test]
+    fn literal() {
+        {
+            let src = "a[-b]";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("a-"), true);
+            assert_eq!(vm.is_match("ab"), true);
+            assert_eq!(vm.is_match("ac"), false);
+        }
+        {
+            let src = "a[b-]";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("a-"), true);
+            assert_eq!(vm.is_match("ab"), true);
+            assert_eq!(vm.is_match("ac"), false);
+        }
+        {
+            let src = "a[]b]";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("a]"), true);
+            assert_eq!(vm.is_match("ab"), true);
+            assert_eq!(vm.is_match("ac"), false);
+        }
+        {
+            let src = "a[^]b]";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("a]"), false);
+            assert_eq!(vm.is_match("ab"), false);
+            assert_eq!(vm.is_match("ac"), true);
+        }
+        {
+            let src = "a[b^]";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("a^"), true);
+            assert_eq!(vm.is_match("ab"), true);
+            assert_eq!(vm.is_match("ac"), false);
+        }
+        {
+            let src = "a[.]";
+            let vm = Vm::new(src).unwrap();
+
+            assert_eq!(vm.is_match("a."), true);
+            assert_eq!(vm.is_match("ab"), false);
+        }
+    }
+
     #[test]
     fn negative() {
         {
@@ -729,6 +1575,78 @@ mod set {
     }
 }
 
+#[test]
+fn set_operation() {
+    {
+        let src = "a[b-d[x-z]]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("ac"), true);
+        assert_eq!(vm.is_match("ay"), true);
+        assert_eq!(vm.is_match("am"), false);
+    }
+    {
+        let src = "[a-z--[aeiou]]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("b"), true);
+        assert_eq!(vm.is_match("z"), true);
+        assert_eq!(vm.is_match("a"), false);
+        assert_eq!(vm.is_match("e"), false);
+        assert_eq!(vm.is_match("B"), false);
+        assert_eq!(vm.is_match("eex"), true);
+    }
+    {
+        let src = "[a-z&&[^b-y]]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("z"), true);
+        assert_eq!(vm.is_match("m"), false);
+    }
+    {
+        let src = "[abc~~bcd]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), true);
+        assert_eq!(vm.is_match("d"), true);
+        assert_eq!(vm.is_match("b"), false);
+        assert_eq!(vm.is_match("c"), false);
+    }
+    {
+        let src = "[^a-z--b-y]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("a"), false);
+        assert_eq!(vm.is_match("z"), false);
+        assert_eq!(vm.is_match("m"), true);
+        assert_eq!(vm.is_match("A"), true);
+    }
+    {
+        let src = "[\u{D7FF}\u{E000}--\u{E000}]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("\u{D7FF}"), true);
+        assert_eq!(vm.is_match("\u{E000}"), false);
+    }
+    {
+        let src = "[\u{D000}-\u{E100}--[\u{D000}-\u{D7FF}]]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("\u{D7FF}"), false);
+        assert_eq!(vm.is_match("\u{E000}"), true);
+        assert_eq!(vm.is_match("\u{E100}"), true);
+    }
+    {
+        let src = "[\u{D000}-\u{E100}--[\u{E000}-\u{E100}]]";
+        let vm = Vm::new(src).unwrap();
+
+        assert_eq!(vm.is_match("\u{D000}"), true);
+        assert_eq!(vm.is_match("\u{D7FF}"), true);
+        assert_eq!(vm.is_match("\u{E000}"), false);
+    }
+}
+
 #[test]
 fn pattern001() {
     {
@@ -752,3 +1670,15 @@ fn pattern001() {
         assert_eq!(vm.is_match("abc@def@example.com"), false);
     }
 }
+
+#[test]
+fn long_input() {
+    // a step per char, no engine recurses on it
+    let text = "a".repeat(200_000);
+
+    let vm = Vm::new("a*").unwrap();
+    assert_eq!(vm.is_match(&text), true);
+
+    let vm = Vm::new("^(?:a|b)*c").unwrap();
+    assert_eq!(vm.is_match(&text), false);
+}